@@ -0,0 +1,77 @@
+//! Measures the memory used to keep a list of version numbers.
+//!
+//! Every allocation is counted, so the numbers include the heap memory used
+//! by identifiers, as well as the list itself.
+extern crate recital;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use recital::compact::CompactVersion;
+use recital::version::Version;
+
+/// Counts the bytes that are currently allocated.
+struct Counter;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst);
+
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static COUNTER: Counter = Counter;
+
+/// Creates a list of version numbers where one in every `every` of them has
+/// pre-release and build identifiers.
+fn inputs(count: usize, every: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| {
+            let (major, minor, patch) = (i / 10000, i / 100 % 100, i % 100);
+
+            if i % every == 0 {
+                format!("{}.{}.{}-rc.{}+build.{}", major, minor, patch, i % 7, i)
+            } else {
+                format!("{}.{}.{}", major, minor, patch)
+            }
+        })
+        .collect()
+}
+
+/// Parses every input, returning the bytes used per version number.
+fn measure<V: std::str::FromStr>(inputs: &[String]) -> usize {
+    let before = ALLOCATED.load(Ordering::SeqCst);
+    let mut versions: Vec<V> = Vec::with_capacity(inputs.len());
+
+    versions.extend(inputs.iter().map(|i| i.parse().ok().unwrap()));
+
+    let used = ALLOCATED.load(Ordering::SeqCst) - before;
+
+    drop(versions);
+
+    used / inputs.len()
+}
+
+fn main() {
+    const COUNT: usize = 100_000;
+
+    println!("{:<28} {:>10} {:>16}", "identifiers", "Version", "CompactVersion");
+
+    for &(label, every) in &[("none", usize::MAX), ("1 in 20", 20), ("all", 1)] {
+        let inputs = inputs(COUNT, every);
+
+        println!("{:<28} {:>8} B {:>14} B",
+                 label,
+                 measure::<Version>(&inputs),
+                 measure::<CompactVersion>(&inputs));
+    }
+}
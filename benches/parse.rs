@@ -0,0 +1,87 @@
+//! Compares parsing into a `Version` with parsing into a `VersionRef`.
+#[macro_use]
+extern crate criterion;
+extern crate recital;
+
+use criterion::{black_box, Criterion, Throughput};
+use recital::borrowed::VersionRef;
+use recital::version::Version;
+
+/// Creates a list of version numbers, a few with pre-release and build
+/// identifiers, similar to what a package registry would list.
+fn inputs() -> Vec<String> {
+    let mut inputs = Vec::new();
+
+    for major in 0..10 {
+        for minor in 0..10 {
+            for patch in 0..10 {
+                inputs.push(match patch % 5 {
+                    0 => format!("{}.{}.{}-rc.{}", major, minor, patch, minor),
+                    1 => format!("{}.{}.{}+build.{}.sha-5114f85", major, minor, patch, patch),
+                    2 => format!("{}.{}.{}-beta.{}+ci", major, minor, patch, major),
+                    _ => format!("{}.{}.{}", major, minor, patch),
+                });
+            }
+        }
+    }
+
+    inputs
+}
+
+fn parse(c: &mut Criterion) {
+    let inputs = inputs();
+    let mut group = c.benchmark_group("parse");
+
+    group.throughput(Throughput::Elements(inputs.len() as u64));
+
+    group.bench_function("Version", |b| {
+        b.iter(|| {
+            for input in &inputs {
+                black_box(input.parse::<Version>().unwrap());
+            }
+        })
+    });
+
+    group.bench_function("VersionRef", |b| {
+        b.iter(|| {
+            for input in &inputs {
+                black_box(VersionRef::parse(input).unwrap());
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn sort(c: &mut Criterion) {
+    let inputs = inputs();
+    let mut group = c.benchmark_group("parse and sort");
+
+    group.throughput(Throughput::Elements(inputs.len() as u64));
+
+    group.bench_function("Version", |b| {
+        b.iter(|| {
+            let mut versions: Vec<Version> = inputs.iter().map(|i| i.parse().unwrap()).collect();
+
+            versions.sort();
+
+            black_box(versions.pop())
+        })
+    });
+
+    group.bench_function("VersionRef", |b| {
+        b.iter(|| {
+            let mut versions: Vec<VersionRef> =
+                inputs.iter().map(|i| VersionRef::parse(i).unwrap()).collect();
+
+            versions.sort();
+
+            black_box(versions.pop().map(|v| v.to_owned()))
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, parse, sort);
+criterion_main!(benches);
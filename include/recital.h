@@ -0,0 +1,251 @@
+#ifndef RECITAL_H
+#define RECITAL_H
+
+/* This file is generated by cbindgen from src/capi.rs. Do not edit it. */
+
+#include <stdbool.h>
+#include <stdint.h>
+
+/*
+ Represents how much a version number is incremented.
+
+ Functions take it as an `int`, since C does not stop other numbers from
+ being passed.
+ */
+typedef enum RecitalBump {
+  /*
+   Increment the patch version number.
+   */
+  RECITAL_BUMP_PATCH = 0,
+  /*
+   Increment the minor version number.
+   */
+  RECITAL_BUMP_MINOR = 1,
+  /*
+   Increment the major version number.
+   */
+  RECITAL_BUMP_MAJOR = 2,
+} RecitalBump;
+
+/*
+ Represents how the constraints in a group are combined.
+
+ Functions take it as an `int`, since C does not stop other numbers from
+ being passed.
+ */
+typedef enum RecitalJoin {
+  /*
+   All constraints must be satisfied.
+   */
+  RECITAL_JOIN_AND = 0,
+  /*
+   At least one constraint must be satisfied.
+   */
+  RECITAL_JOIN_OR = 1,
+} RecitalJoin;
+
+/*
+ Represents a comparison with a version number.
+
+ Functions take it as an `int`, since C does not stop other numbers from
+ being passed.
+ */
+typedef enum RecitalOperator {
+  /*
+   Match the exact version number. (=)
+   */
+  RECITAL_OPERATOR_EXACTLY = 0,
+  /*
+   Exclude the exact version number. (!=)
+   */
+  RECITAL_OPERATOR_EXACTLY_NOT = 1,
+  /*
+   Exclusively match any greater version number. (>)
+   */
+  RECITAL_OPERATOR_GREATER_THAN = 2,
+  /*
+   Inclusively match any greater version number. (>=)
+   */
+  RECITAL_OPERATOR_GREATER_THAN_OR_EQUAL_TO = 3,
+  /*
+   Exclusively match any lesser version number. (<)
+   */
+  RECITAL_OPERATOR_LESS_THAN = 4,
+  /*
+   Inclusively match any lesser version number. (<=)
+   */
+  RECITAL_OPERATOR_LESS_THAN_OR_EQUAL_TO = 5,
+} RecitalOperator;
+
+/*
+ Represents the outcome of a function call.
+ */
+typedef enum RecitalStatus {
+  /*
+   The call succeeded.
+   */
+  RECITAL_STATUS_OK = 0,
+  /*
+   A required pointer was null.
+   */
+  RECITAL_STATUS_NULL_POINTER = 1,
+  /*
+   A string was not valid UTF-8.
+   */
+  RECITAL_STATUS_INVALID_UTF8 = 2,
+  /*
+   A string was not a valid version number.
+   */
+  RECITAL_STATUS_INVALID_VERSION = 3,
+  /*
+   A string was not a valid version requirement.
+   */
+  RECITAL_STATUS_INVALID_REQUIREMENT = 4,
+  /*
+   A number was not one of the values of its enum.
+   */
+  RECITAL_STATUS_INVALID_ENUM = 5,
+  /*
+   A version number was too large to be incremented.
+   */
+  RECITAL_STATUS_OVERFLOW = 6,
+} RecitalStatus;
+
+/*
+ Represents a group of constraints.
+ */
+typedef struct RecitalConstraints RecitalConstraints;
+
+/*
+ Represents a semantic version number.
+ */
+typedef struct RecitalVersion RecitalVersion;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ Parses a string as a version number.
+
+ On success, `out` is set to a new version number. On failure, `error` is
+ set to a message that describes the problem, unless it is null.
+ */
+RecitalStatus recital_version_parse(const char *input, RecitalVersion **out, char **error);
+
+/*
+ Creates a version number without any identifiers.
+ */
+RecitalVersion *recital_version_new(uint64_t major, uint64_t minor, uint64_t patch);
+
+/*
+ Creates a copy of a version number, or returns null if it is null.
+ */
+RecitalVersion *recital_version_clone(const RecitalVersion *version);
+
+/*
+ Frees a version number. Nothing is done if it is null.
+ */
+void recital_version_free(RecitalVersion *version);
+
+/*
+ Returns the major version number, or zero (`0`) if it is null.
+ */
+uint64_t recital_version_major(const RecitalVersion *version);
+
+/*
+ Returns the minor version number, or zero (`0`) if it is null.
+ */
+uint64_t recital_version_minor(const RecitalVersion *version);
+
+/*
+ Returns the patch version number, or zero (`0`) if it is null.
+ */
+uint64_t recital_version_patch(const RecitalVersion *version);
+
+/*
+ Compares two version numbers.
+
+ Returns a negative number if `a` is less than `b`, zero (`0`) if they are
+ equal, and a positive number if `a` is greater than `b`. A null version
+ number is less than any other.
+ */
+int recital_version_compare(const RecitalVersion *a, const RecitalVersion *b);
+
+/*
+ Increments a version number, removing its identifiers.
+
+ `level` is a `RecitalBump`. The version number is not changed if the
+ number that would be incremented is already the largest `uint64_t`.
+ */
+RecitalStatus recital_version_increment(RecitalVersion *version, int level);
+
+/*
+ Displays a version number as a new string, or returns null if it is null.
+
+ The string must be freed with `recital_string_free`.
+ */
+char *recital_version_to_string(const RecitalVersion *version);
+
+/*
+ Frees a string returned by this library. Nothing is done if it is null.
+ */
+void recital_string_free(char *s);
+
+/*
+ Creates an empty group of constraints, or returns null if `join` is not a
+ `RecitalJoin`.
+
+ An empty `RECITAL_JOIN_AND` group allows every version number, and an empty
+ `RECITAL_JOIN_OR` group allows none.
+ */
+RecitalConstraints *recital_constraints_new(int join);
+
+/*
+ Parses a version requirement, using the rules of Cargo (`^1.2, <1.5`).
+
+ On success, `out` is set to a new group of constraints with the
+ requirement in it. On failure, `error` is set to a message that describes
+ the problem, unless it is null.
+ */
+RecitalStatus recital_requirement_parse(const char *input,
+                                        RecitalConstraints **out,
+                                        char **error);
+
+/*
+ Adds a comparison with a copy of a version number to a group.
+
+ `op` is a `RecitalOperator`.
+ */
+RecitalStatus recital_constraints_add_operation(RecitalConstraints *constraints,
+                                                int op,
+                                                const RecitalVersion *version);
+
+/*
+ Adds a group of constraints to another group, to build a tree.
+
+ The added group is owned by the other group from then on, and must not be
+ used or freed. If `constraints` is null, the added group is freed.
+ */
+RecitalStatus recital_constraints_add_group(RecitalConstraints *constraints,
+                                            RecitalConstraints *group);
+
+/*
+ Checks if a version number satisfies a group of constraints.
+
+ Returns `false` if either of them is null.
+ */
+bool recital_constraints_allows(const RecitalConstraints *constraints,
+                                const RecitalVersion *version);
+
+/*
+ Frees a group of constraints, and every group in it. Nothing is done if
+ it is null.
+ */
+void recital_constraints_free(RecitalConstraints *constraints);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* RECITAL_H */
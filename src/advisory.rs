@@ -0,0 +1,432 @@
+//! Matches security advisories against version numbers.
+//!
+//! The **advisory** module provides you with an `Advisory`, which is read
+//! from a vulnerability advisory in the [OSV format][]. The `SEMVER` ranges of
+//! each affected package are converted into a set of constraints, which can
+//! then be checked against an installed version number or a pool of them.
+//!
+//! [OSV format]: https://ossf.github.io/osv-schema/
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate recital;
+//! use recital::advisory::Advisory;
+//!
+//! # fn main() {
+//! let advisory = Advisory::from_json(r#"{
+//!     "id": "GHSA-xxxx-xxxx-xxxx",
+//!     "affected": [{
+//!         "package": { "ecosystem": "crates.io", "name": "example" },
+//!         "ranges": [{
+//!             "type": "SEMVER",
+//!             "events": [{ "introduced": "0" }, { "fixed": "1.2.4" }]
+//!         }]
+//!     }]
+//! }"#).unwrap();
+//!
+//! assert!(advisory.affects("crates.io", "example", &version!(1, 2, 3)));
+//! assert!(!advisory.affects("crates.io", "example", &version!(1, 2, 4)));
+//! assert!(!advisory.affects("npm", "example", &version!(1, 2, 3)));
+//! assert_eq!(Some(version!(1, 2, 4)),
+//!            advisory.nearest_fix("crates.io", "example", &version!(1, 2, 3)));
+//! # }
+//! ```
+//!
+//! Advisories are read from strings or local files only, so auditing can be
+//! done entirely offline. This module requires the `advisory` feature, which
+//! is enabled by default.
+use std::fs;
+use std::path::Path;
+use serde_json::Value;
+use super::resolve::{resolve, Constraint, Constraints};
+use super::resolve::Operation::*;
+use super::version::Version;
+
+/// Represents a single event in an advisory range.
+///
+/// The version number of an `Introduced` event of `"0"` is `0.0.0-0`, which
+/// comes before every other version number.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// The version number that introduced the vulnerability.
+    Introduced(Version),
+
+    /// The version number that fixed the vulnerability.
+    Fixed(Version),
+
+    /// The last version number that is known to be vulnerable.
+    LastAffected(Version),
+
+    /// The version number beyond which the range does not apply.
+    Limit(Version),
+}
+
+impl Event {
+    /// Returns the version number of the event.
+    pub fn version(&self) -> &Version {
+        match *self {
+            Event::Introduced(ref v) |
+            Event::Fixed(ref v) |
+            Event::LastAffected(ref v) |
+            Event::Limit(ref v) => v,
+        }
+    }
+}
+
+/// Represents a package that is affected by an advisory.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Affected {
+    /// The ecosystem of the package (e.g. `crates.io`, `npm`).
+    pub ecosystem: String,
+
+    /// The name of the package.
+    pub name: String,
+
+    /// The events of each `SEMVER` range, sorted by version number.
+    pub ranges: Vec<Vec<Event>>,
+
+    /// The version numbers that are explicitly listed as affected.
+    pub versions: Vec<Version>,
+}
+
+impl Affected {
+    /// Creates a set of constraints that allows every affected version number.
+    ///
+    /// Each `Introduced` event starts an interval that is ended by the next
+    /// `Fixed` (exclusive) or `LastAffected` (inclusive) event. An interval
+    /// that is never ended includes every later version number.
+    pub fn constraints(&self) -> Constraints {
+        let mut intervals: Vec<Box<dyn Constraint>> = Vec::new();
+
+        for events in &self.ranges {
+            let mut introduced: Option<&Version> = None;
+
+            for event in events {
+                match (event, introduced) {
+                    (Event::Introduced(v), None) => introduced = Some(v),
+
+                    (Event::Fixed(v), Some(start)) => {
+                        intervals.push(Box::new(constraints!(And,
+                                                             GreaterThanOrEqualTo(start.clone()),
+                                                             LessThan(v.clone()))));
+                        introduced = None;
+                    }
+
+                    (Event::LastAffected(v), Some(start)) => {
+                        intervals.push(Box::new(constraints!(And,
+                                                             GreaterThanOrEqualTo(start.clone()),
+                                                             LessThanOrEqualTo(v.clone()))));
+                        introduced = None;
+                    }
+
+                    _ => {}
+                }
+            }
+
+            if let Some(start) = introduced {
+                intervals.push(Box::new(GreaterThanOrEqualTo(start.clone())));
+            }
+        }
+
+        for version in &self.versions {
+            intervals.push(Box::new(Exactly(version.clone())));
+        }
+
+        Constraints::Or(intervals)
+    }
+}
+
+/// Represents a vulnerability advisory.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Advisory {
+    /// The identifier of the advisory.
+    pub id: String,
+
+    /// The packages that are affected.
+    pub affected: Vec<Affected>,
+}
+
+impl Advisory {
+    /// Checks if a version number of a package is affected.
+    ///
+    /// The package is matched by both its ecosystem (such as `crates.io` or
+    /// `npm`) and its name, since packages in different ecosystems can have
+    /// the same name.
+    pub fn affects(&self, ecosystem: &str, package: &str, version: &Version) -> bool {
+        self.packages(ecosystem, package).any(|a| a.constraints().allows(version))
+    }
+
+    /// Returns the version numbers in a pool that are affected.
+    pub fn affected_versions(&self,
+                             ecosystem: &str,
+                             package: &str,
+                             versions: &Vec<Version>)
+                             -> Vec<Version> {
+        let mut affected = Vec::new();
+
+        for a in self.packages(ecosystem, package) {
+            for version in resolve(versions, &a.constraints()) {
+                if !affected.contains(&version) {
+                    affected.push(version);
+                }
+            }
+        }
+
+        affected
+    }
+
+    /// Parses an advisory from a string of JSON.
+    ///
+    /// Only `SEMVER` ranges are used, and the ranges of other types are
+    /// ignored. An error is returned if the JSON is not valid, required
+    /// fields are missing, or a version number in a `SEMVER` range can't be
+    /// parsed.
+    pub fn from_json(json: &str) -> Result<Advisory, String> {
+        let value: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+        let id = match value["id"].as_str() {
+            Some(id) => id.to_string(),
+            None => return Err("Advisory is missing an id.".to_string()),
+        };
+
+        let mut affected = Vec::new();
+
+        for package in value["affected"].as_array().unwrap_or(&Vec::new()) {
+            affected.push(parse_affected(package)
+                .map_err(|e| format!("Invalid advisory {}: {}", id, e))?);
+        }
+
+        Ok(Advisory { id, affected })
+    }
+
+    /// Reads an advisory from a local JSON file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Advisory, String> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+
+        Advisory::from_json(&json)
+    }
+
+    /// Returns the nearest version number that fixes an installed one.
+    ///
+    /// This is the least `Fixed` version number of the package that is
+    /// greater than the installed version number, and is not affected by any
+    /// other range in the advisory. If there is no such version number, then
+    /// `None` is returned.
+    pub fn nearest_fix(&self,
+                       ecosystem: &str,
+                       package: &str,
+                       installed: &Version)
+                       -> Option<Version> {
+        let mut fixes: Vec<&Version> = self.packages(ecosystem, package)
+            .flat_map(|a| a.ranges.iter())
+            .flat_map(|events| events.iter())
+            .filter_map(|event| {
+                match *event {
+                    Event::Fixed(ref v) if v > installed => Some(v),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        fixes.sort();
+
+        fixes.into_iter()
+            .find(|v| !self.affects(ecosystem, package, v))
+            .cloned()
+    }
+
+    /// Returns the affected packages with the given ecosystem and name.
+    fn packages<'a>(&'a self,
+                    ecosystem: &'a str,
+                    package: &'a str)
+                    -> impl Iterator<Item = &'a Affected> + 'a {
+        self.affected.iter().filter(move |a| a.ecosystem == ecosystem && a.name == package)
+    }
+}
+
+/// Represents an advisory that affects an installed version number.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Finding {
+    /// The identifier of the advisory.
+    pub id: String,
+
+    /// The nearest version number that fixes the vulnerability, if any.
+    pub fix: Option<Version>,
+}
+
+/// Checks an installed version number of a package against advisories.
+///
+/// A finding is returned for every advisory that affects the version number.
+pub fn audit(advisories: &[Advisory],
+             ecosystem: &str,
+             package: &str,
+             installed: &Version)
+             -> Vec<Finding> {
+    advisories.iter()
+        .filter(|a| a.affects(ecosystem, package, installed))
+        .map(|a| {
+            Finding {
+                id: a.id.clone(),
+                fix: a.nearest_fix(ecosystem, package, installed),
+            }
+        })
+        .collect()
+}
+
+/// Parses a version number from an advisory.
+fn parse_version(value: &Value) -> Result<Version, String> {
+    match value.as_str() {
+        Some("0") => Ok(version!(0, 0, 0, vec![id!(0)])),
+        Some(s) => s.parse(),
+        None => Err(format!("Invalid version number: {}", value)),
+    }
+}
+
+/// Parses an affected package from an advisory.
+fn parse_affected(value: &Value) -> Result<Affected, String> {
+    let mut ranges = Vec::new();
+    let mut versions = Vec::new();
+
+    for range in value["ranges"].as_array().unwrap_or(&Vec::new()) {
+        if range["type"] != "SEMVER" {
+            continue;
+        }
+
+        let mut events = Vec::new();
+
+        for event in range["events"].as_array().unwrap_or(&Vec::new()) {
+            let event = if !event["introduced"].is_null() {
+                Event::Introduced(parse_version(&event["introduced"])?)
+            } else if !event["fixed"].is_null() {
+                Event::Fixed(parse_version(&event["fixed"])?)
+            } else if !event["last_affected"].is_null() {
+                Event::LastAffected(parse_version(&event["last_affected"])?)
+            } else if !event["limit"].is_null() {
+                Event::Limit(parse_version(&event["limit"])?)
+            } else {
+                return Err(format!("Invalid event: {}", event));
+            };
+
+            events.push(event);
+        }
+
+        events.sort_by(|a, b| a.version().cmp(b.version()));
+        ranges.push(events);
+    }
+
+    // Listed versions may use any format, so only the valid ones are kept.
+    for version in value["versions"].as_array().unwrap_or(&Vec::new()) {
+        if let Ok(version) = parse_version(version) {
+            versions.push(version);
+        }
+    }
+
+    Ok(Affected {
+        ecosystem: value["package"]["ecosystem"].as_str().unwrap_or("").to_string(),
+        name: value["package"]["name"].as_str().unwrap_or("").to_string(),
+        ranges,
+        versions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{audit, Advisory, Event, Finding};
+    use super::super::version::Version;
+
+    fn fixture(name: &str) -> Advisory {
+        Advisory::load(format!("{}/fixtures/advisory/{}.json", env!("CARGO_MANIFEST_DIR"), name))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_load() {
+        let advisory = fixture("GHSA-demo-0001");
+
+        assert_eq!("GHSA-demo-0001", advisory.id);
+        assert_eq!(2, advisory.affected.len());
+        assert_eq!("crates.io", advisory.affected[0].ecosystem);
+        assert_eq!(1, advisory.affected[0].ranges.len());
+        assert_eq!(vec![Event::Introduced("0.0.0-0".parse::<Version>().unwrap()),
+                        Event::Fixed(version!(1, 2, 4)),
+                        Event::Introduced("2.0.0-alpha.1".parse::<Version>().unwrap()),
+                        Event::Fixed("2.0.0-rc.2".parse::<Version>().unwrap())],
+                   advisory.affected[0].ranges[0]);
+
+        assert!(Advisory::load("missing.json").is_err());
+        assert!(Advisory::from_json("{}").is_err());
+        assert!(Advisory::from_json("{").is_err());
+    }
+
+    #[test]
+    fn test_affects() {
+        let advisory = fixture("GHSA-demo-0001");
+
+        assert!(advisory.affects("crates.io", "example", &"0.1.0-alpha".parse().unwrap()));
+        assert!(advisory.affects("crates.io", "example", &version!(1, 2, 3)));
+        assert!(advisory.affects("crates.io", "example", &"1.2.4-rc.1".parse().unwrap()));
+        assert!(!advisory.affects("crates.io", "example", &version!(1, 2, 4)));
+        assert!(!advisory.affects("crates.io", "example", &"2.0.0-alpha".parse().unwrap()));
+        assert!(advisory.affects("crates.io", "example", &"2.0.0-beta.3".parse().unwrap()));
+        assert!(!advisory.affects("crates.io", "example", &"2.0.0-rc.2".parse().unwrap()));
+        assert!(!advisory.affects("crates.io", "example", &version!(2, 0, 0)));
+
+        assert!(advisory.affects("npm", "example-js", &version!(1, 0, 0)));
+        assert!(advisory.affects("npm", "example-js", &version!(3, 4, 2)));
+        assert!(!advisory.affects("npm", "example-js", &version!(3, 4, 3)));
+        assert!(!advisory.affects("crates.io", "other", &version!(1, 0, 0)));
+        assert!(!advisory.affects("npm", "example", &version!(1, 2, 3)));
+        assert!(!advisory.affects("crates.io", "example-js", &version!(1, 0, 0)));
+    }
+
+    #[test]
+    fn test_affected_versions() {
+        let advisory = fixture("GHSA-demo-0002");
+        let pool = vec![version!(1, 1, 0),
+                        version!(1, 2, 0),
+                        version!(1, 2, 9),
+                        version!(1, 3, 0),
+                        version!(1, 5, 0),
+                        version!(2, 0, 0)];
+
+        assert_eq!(vec![version!(1, 2, 0), version!(1, 2, 9), version!(1, 5, 0), version!(2, 0, 0)],
+                   advisory.affected_versions("crates.io", "example", &pool));
+    }
+
+    #[test]
+    fn test_nearest_fix() {
+        let first = fixture("GHSA-demo-0001");
+        let second = fixture("GHSA-demo-0002");
+
+        assert_eq!(Some(version!(1, 2, 4)),
+                   first.nearest_fix("crates.io", "example", &version!(1, 0, 0)));
+        assert_eq!(Some("2.0.0-rc.2".parse::<Version>().unwrap()),
+                   first.nearest_fix("crates.io", "example", &"2.0.0-beta".parse().unwrap()));
+        assert_eq!(None, first.nearest_fix("npm", "example-js", &version!(3, 2, 0)));
+        assert_eq!(Some(version!(1, 3, 0)),
+                   second.nearest_fix("crates.io", "example", &version!(1, 2, 5)));
+        assert_eq!(None, second.nearest_fix("crates.io", "example", &version!(1, 6, 0)));
+    }
+
+    #[test]
+    fn test_audit() {
+        let advisories = vec![fixture("GHSA-demo-0001"), fixture("GHSA-demo-0002")];
+
+        assert_eq!(vec![Finding {
+                            id: "GHSA-demo-0001".to_string(),
+                            fix: Some(version!(1, 2, 4)),
+                        },
+                        Finding {
+                            id: "GHSA-demo-0002".to_string(),
+                            fix: Some(version!(1, 3, 0)),
+                        }],
+                   audit(&advisories, "crates.io", "example", &version!(1, 2, 3)));
+
+        assert!(audit(&advisories, "crates.io", "example", &version!(1, 4, 0)).is_empty());
+        assert!(audit(&advisories, "npm", "example", &version!(1, 2, 3)).is_empty());
+    }
+
+}
@@ -0,0 +1,191 @@
+//! Generates arbitrary version numbers and constraints for property testing.
+//!
+//! The **arbitrary** module provides you with [proptest][] strategies, and
+//! `Arbitrary` implementations, for `Identifier`, `Version`, and trees of
+//! constraints. Every version number that is generated is valid according to
+//! the specification, and survives being displayed and parsed again.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate proptest;
+//! extern crate recital;
+//!
+//! use proptest::prelude::*;
+//! use proptest::test_runner::TestRunner;
+//! use recital::arbitrary::ConstraintTree;
+//! use recital::resolve::{Constraint, Constraints};
+//! use recital::version::Version;
+//!
+//! # fn main() {
+//! let mut runner = TestRunner::default();
+//!
+//! runner.run(&any::<Version>(), |version| {
+//!         prop_assert_eq!(version.to_string().parse::<Version>(), Ok(version));
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//!
+//! runner.run(&any::<(ConstraintTree, Version)>(), |(tree, version)| {
+//!         let constraints: Constraints = tree.clone().into();
+//!
+//!         prop_assert_eq!(tree.allows(&version), constraints.allows(&version));
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # }
+//! ```
+//!
+//! The module is available with the `proptest` feature.
+//!
+//! [proptest]: https://docs.rs/proptest/
+use proptest::collection::vec;
+use proptest::prelude::*;
+use super::resolve::{Constraint, Constraints, Operation};
+use super::version::{Identifier, Version};
+
+/// Generates a version number, or a numeric identifier.
+///
+/// Small numbers are generated more often, so that generated version numbers
+/// are often equal to one another, or only differ in their identifiers.
+pub fn number() -> impl Strategy<Value = u64> {
+    prop_oneof![3 => 0..4u64, 1 => any::<u64>()]
+}
+
+/// Generates an identifier.
+///
+/// Alphanumeric identifiers always have at least one letter or hyphen, since
+/// they would otherwise be parsed as numeric identifiers.
+pub fn identifier() -> impl Strategy<Value = Identifier> {
+    prop_oneof![number().prop_map(Identifier::Number),
+                "[a-c]".prop_map(Identifier::Alpha),
+                "[0-9A-Za-z-]{0,4}[A-Za-z-][0-9A-Za-z-]{0,4}".prop_map(Identifier::Alpha)]
+}
+
+/// Generates a list of pre-release or build identifiers, which may be empty.
+pub fn identifiers() -> impl Strategy<Value = Vec<Identifier>> {
+    prop_oneof![Just(Vec::new()), vec(identifier(), 1..4)]
+}
+
+/// Generates a version number.
+pub fn version() -> impl Strategy<Value = Version> {
+    let parts = (number(), number(), number(), identifiers(), identifiers());
+
+    parts.prop_map(|(major, minor, patch, pre, build)| {
+        Version {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        }
+    })
+}
+
+/// Generates a single operation.
+pub fn operation() -> impl Strategy<Value = Operation> {
+    (0..6u8, version()).prop_map(|(kind, version)| match kind {
+        0 => Operation::Exactly(version),
+        1 => Operation::ExactlyNot(version),
+        2 => Operation::GreaterThan(version),
+        3 => Operation::GreaterThanOrEqualTo(version),
+        4 => Operation::LessThan(version),
+        _ => Operation::LessThanOrEqualTo(version),
+    })
+}
+
+/// Represents a set of constraints that can be cloned and debugged.
+///
+/// Since a `Constraints` holds any kind of constraint, it cannot be generated
+/// directly. A `ConstraintTree` holds the same operations and sets, and can be
+/// converted into a `Constraints` when it is needed.
+#[derive(Clone, Debug)]
+pub enum ConstraintTree {
+    /// A single operation.
+    Operation(Operation),
+
+    /// All constraints must be satisfied.
+    And(Vec<ConstraintTree>),
+
+    /// At least one constraint must be satisfied.
+    Or(Vec<ConstraintTree>),
+}
+
+impl ConstraintTree {
+    /// Converts the tree into a set of constraints.
+    ///
+    /// A single operation is converted into a set of its own.
+    pub fn into_constraints(self) -> Constraints {
+        match self {
+            ConstraintTree::Operation(operation) => Constraints::And(vec![Box::new(operation)]),
+            ConstraintTree::And(trees) => Constraints::And(boxed(trees)),
+            ConstraintTree::Or(trees) => Constraints::Or(boxed(trees)),
+        }
+    }
+}
+
+/// Converts each tree into a boxed set of constraints.
+fn boxed(trees: Vec<ConstraintTree>) -> Vec<Box<dyn Constraint>> {
+    trees.into_iter()
+        .map(|tree| Box::new(tree.into_constraints()) as Box<dyn Constraint>)
+        .collect()
+}
+
+impl Constraint for ConstraintTree {
+    fn allows(&self, version: &Version) -> bool {
+        match *self {
+            ConstraintTree::Operation(ref operation) => operation.allows(version),
+            ConstraintTree::And(ref trees) => trees.iter().all(|t| t.allows(version)),
+            ConstraintTree::Or(ref trees) => trees.iter().any(|t| t.allows(version)),
+        }
+    }
+}
+
+impl From<ConstraintTree> for Constraints {
+    fn from(tree: ConstraintTree) -> Constraints {
+        tree.into_constraints()
+    }
+}
+
+/// Generates a tree of constraints, up to three sets deep.
+pub fn constraint_tree() -> impl Strategy<Value = ConstraintTree> {
+    operation().prop_map(ConstraintTree::Operation).prop_recursive(3, 24, 4, |inner| {
+        prop_oneof![vec(inner.clone(), 1..4).prop_map(ConstraintTree::And),
+                    vec(inner, 1..4).prop_map(ConstraintTree::Or)]
+    })
+}
+
+impl Arbitrary for Identifier {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Identifier>;
+
+    fn arbitrary_with(_: ()) -> BoxedStrategy<Identifier> {
+        identifier().boxed()
+    }
+}
+
+impl Arbitrary for Version {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Version>;
+
+    fn arbitrary_with(_: ()) -> BoxedStrategy<Version> {
+        version().boxed()
+    }
+}
+
+impl Arbitrary for Operation {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Operation>;
+
+    fn arbitrary_with(_: ()) -> BoxedStrategy<Operation> {
+        operation().boxed()
+    }
+}
+
+impl Arbitrary for ConstraintTree {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<ConstraintTree>;
+
+    fn arbitrary_with(_: ()) -> BoxedStrategy<ConstraintTree> {
+        constraint_tree().boxed()
+    }
+}
@@ -0,0 +1,413 @@
+//! Parses and compares semantic version numbers without copying them.
+//!
+//! The **borrowed** module provides you with a `VersionRef`, which is a
+//! semantic version number whose pre-release and build identifiers are slices
+//! of the string it was parsed from. Parsing one does not allocate, which
+//! makes it a good fit when a large number of version numbers have to be read,
+//! sorted, or filtered, and only a few of them need to be kept.
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate recital;
+//! use recital::borrowed::VersionRef;
+//! use recital::resolve::Operation::LessThan;
+//!
+//! # fn main() {
+//! let input = String::from("1.2.3-rc.1+sha.5114f85");
+//! let version = VersionRef::parse(&input).unwrap();
+//!
+//! assert_eq!("rc.1", version.pre);
+//! assert!(version < VersionRef::parse("1.2.3").unwrap());
+//! assert!(LessThan(version!(1, 2, 3)).allows_ref(&version));
+//! assert_eq!(version!(1, 2, 3, vec![id!("rc"), id!(1)], vec![id!("sha"), id!("5114f85")]),
+//!            version.to_owned());
+//! # }
+//! ```
+//!
+//! A `VersionRef` is ordered, displayed, and checked against constraints in
+//! the same way as the `Version` it would be parsed as.
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::str::Split;
+use super::parser::parse_ref;
+use super::resolve::{Constraint, Operation};
+use super::version::{Identifier, Version};
+
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+
+/// Represents a borrowed alphanumeric or numeric identifier.
+///
+/// An identifier is compared in the same way as an `Identifier`: numbers are
+/// compared numerically, and are always less than alphanumeric identifiers.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum IdentifierRef<'a> {
+    /// Represents a numeric identifier.
+    Number(u64),
+
+    /// Represents an alphanumeric identifier.
+    Alpha(&'a str),
+}
+
+impl<'a> Display for IdentifierRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IdentifierRef::Alpha(a) => write!(f, "{}", a),
+            IdentifierRef::Number(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for IdentifierRef<'a> {
+    fn from(s: &'a str) -> IdentifierRef<'a> {
+        match s.parse::<u64>() {
+            Ok(n) => IdentifierRef::Number(n),
+            Err(_) => IdentifierRef::Alpha(s),
+        }
+    }
+}
+
+impl<'a> From<&'a Identifier> for IdentifierRef<'a> {
+    fn from(identifier: &'a Identifier) -> IdentifierRef<'a> {
+        match *identifier {
+            Identifier::Alpha(ref a) => IdentifierRef::Alpha(a),
+            Identifier::Number(n) => IdentifierRef::Number(n),
+        }
+    }
+}
+
+impl<'a> From<IdentifierRef<'a>> for Identifier {
+    fn from(identifier: IdentifierRef<'a>) -> Identifier {
+        match identifier {
+            IdentifierRef::Alpha(a) => Identifier::Alpha(a.to_string()),
+            IdentifierRef::Number(n) => Identifier::Number(n),
+        }
+    }
+}
+
+/// Iterates through the dot (`.`) separated identifiers in a string.
+#[derive(Clone, Debug)]
+pub struct Identifiers<'a> {
+    split: Option<Split<'a, char>>,
+}
+
+impl<'a> Identifiers<'a> {
+    /// Creates an iterator for the identifiers in the string.
+    fn new(s: &'a str) -> Identifiers<'a> {
+        Identifiers {
+            split: if s.is_empty() { None } else { Some(s.split('.')) },
+        }
+    }
+}
+
+impl<'a> Iterator for Identifiers<'a> {
+    type Item = IdentifierRef<'a>;
+
+    fn next(&mut self) -> Option<IdentifierRef<'a>> {
+        self.split.as_mut().and_then(|s| s.next()).map(IdentifierRef::from)
+    }
+}
+
+/// Represents a semantic version number borrowed from a string.
+///
+/// The pre-release and build identifiers are kept as they were written, less
+/// the leading hyphen (`-`) or plus sign (`+`), and are empty if there are
+/// none. Use `pre_identifiers` and `build_identifiers` to iterate through
+/// them one at a time.
+#[derive(Clone, Copy, Debug, Default, Eq)]
+pub struct VersionRef<'a> {
+    /// The major version number.
+    pub major: u64,
+
+    /// The minor version number.
+    pub minor: u64,
+
+    /// The patch version number.
+    pub patch: u64,
+
+    /// The pre-release identifiers (`rc.1`).
+    pub pre: &'a str,
+
+    /// The build identifiers (`sha.5114f85`).
+    pub build: &'a str,
+}
+
+impl<'a> VersionRef<'a> {
+    /// Parses a string as a borrowed semantic version number.
+    ///
+    /// The same strings are accepted as when parsing a `Version`.
+    pub fn parse(s: &'a str) -> Result<VersionRef<'a>, String> {
+        parse_ref(s)
+    }
+
+    /// Returns an iterator over the pre-release identifiers.
+    pub fn pre_identifiers(&self) -> Identifiers<'a> {
+        Identifiers::new(self.pre)
+    }
+
+    /// Returns an iterator over the build identifiers.
+    pub fn build_identifiers(&self) -> Identifiers<'a> {
+        Identifiers::new(self.build)
+    }
+
+    /// Checks if the version number satisfies a constraint, or a set of them.
+    ///
+    /// Since the constraint only accepts a `Version`, the version number is
+    /// copied once before it is checked. A single `Operation` can check a
+    /// `VersionRef` without copying it using `Operation::allows_ref`.
+    pub fn satisfies<C: Constraint + ?Sized>(&self, constraint: &C) -> bool {
+        constraint.allows(&self.to_owned())
+    }
+
+    /// Creates an owned copy of the version number.
+    pub fn to_owned(&self) -> Version {
+        Version {
+            major: self.major,
+            minor: self.minor,
+            patch: self.patch,
+            pre: self.pre_identifiers().map(Identifier::from).collect(),
+            build: self.build_identifiers().map(Identifier::from).collect(),
+        }
+    }
+}
+
+impl<'a> Display for VersionRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+
+        for (prefix, mut identifiers) in [("-", self.pre_identifiers()),
+                                          ("+", self.build_identifiers())] {
+            if let Some(first) = identifiers.next() {
+                write!(f, "{}{}", prefix, first)?;
+
+                for identifier in identifiers {
+                    write!(f, ".{}", identifier)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Compares two version numbers given as their numbers and pre-release
+/// identifiers, using the same rules as `Version`.
+fn compare<'a, 'b, A, B>(a: (u64, u64, u64), mut a_pre: A, b: (u64, u64, u64), mut b_pre: B)
+                         -> Ordering
+    where A: Iterator<Item = IdentifierRef<'a>>,
+          B: Iterator<Item = IdentifierRef<'b>>
+{
+    if a != b {
+        return a.cmp(&b);
+    }
+
+    match (a_pre.next(), b_pre.next()) {
+        (None, None) => return Ordering::Equal,
+        (None, Some(_)) => return Ordering::Greater,
+        (Some(_), None) => return Ordering::Less,
+        (Some(a_first), Some(b_first)) if a_first != b_first => return a_first.cmp(&b_first),
+        _ => {}
+    }
+
+    loop {
+        match (a_pre.next(), b_pre.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a_next), Some(b_next)) if a_next != b_next => return a_next.cmp(&b_next),
+            _ => {}
+        }
+    }
+}
+
+/// Build identifiers are ignored, and a pre-release comes before its release.
+impl<'a> Ord for VersionRef<'a> {
+    fn cmp(&self, other: &VersionRef<'a>) -> Ordering {
+        compare((self.major, self.minor, self.patch),
+                self.pre_identifiers(),
+                (other.major, other.minor, other.patch),
+                other.pre_identifiers())
+    }
+}
+
+impl<'a> PartialEq for VersionRef<'a> {
+    fn eq(&self, other: &VersionRef<'a>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> PartialOrd for VersionRef<'a> {
+    fn partial_cmp(&self, other: &VersionRef<'a>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq<Version> for VersionRef<'a> {
+    fn eq(&self, other: &Version) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<'a> PartialOrd<Version> for VersionRef<'a> {
+    fn partial_cmp(&self, other: &Version) -> Option<Ordering> {
+        Some(compare((self.major, self.minor, self.patch),
+                     self.pre_identifiers(),
+                     (other.major, other.minor, other.patch),
+                     other.pre.iter().map(IdentifierRef::from)))
+    }
+}
+
+impl<'a> PartialEq<VersionRef<'a>> for Version {
+    fn eq(&self, other: &VersionRef<'a>) -> bool {
+        *other == *self
+    }
+}
+
+impl<'a> PartialOrd<VersionRef<'a>> for Version {
+    fn partial_cmp(&self, other: &VersionRef<'a>) -> Option<Ordering> {
+        other.partial_cmp(self).map(Ordering::reverse)
+    }
+}
+
+impl<'a> From<VersionRef<'a>> for Version {
+    fn from(version: VersionRef<'a>) -> Version {
+        version.to_owned()
+    }
+}
+
+impl Operation<Version> {
+    /// Checks if a borrowed version number satisfies the operation.
+    ///
+    /// Unlike `VersionRef::satisfies`, the version number is not copied.
+    pub fn allows_ref(&self, version: &VersionRef) -> bool {
+        match *self {
+            Operation::Exactly(ref base) => *base == *version,
+            Operation::ExactlyNot(ref base) => *base != *version,
+            Operation::GreaterThan(ref base) => *base < *version,
+            Operation::GreaterThanOrEqualTo(ref base) => *base <= *version,
+            Operation::LessThan(ref base) => *base > *version,
+            Operation::LessThanOrEqualTo(ref base) => *base >= *version,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use super::{IdentifierRef, VersionRef};
+    use super::super::parser::parse_prefix;
+    use super::super::resolve::Operation::*;
+    use super::super::version::Version;
+
+    #[test]
+    fn test_parse() {
+        let version = VersionRef::parse("1.2.3-alpha.1+build.7").unwrap();
+
+        assert_eq!((1, 2, 3), (version.major, version.minor, version.patch));
+        assert_eq!(vec![IdentifierRef::Alpha("alpha"), IdentifierRef::Number(1)],
+                   version.pre_identifiers().collect::<Vec<_>>());
+        assert_eq!("build.7", version.build);
+        assert_eq!("1.2.3-alpha.1+build.7", version.to_string());
+        assert_eq!(0, VersionRef::parse("1.2.3").unwrap().pre_identifiers().count());
+    }
+
+    #[test]
+    fn test_same_as_owned() {
+        let inputs = ["0.0.0",
+                      "1.2.3",
+                      "01.2.3",
+                      "1.2.3-rc.1",
+                      "1.2.3-rc.01+build",
+                      "1.2.3+build.1.x-y",
+                      "1.2.3-18446744073709551616",
+                      "1.2.3-",
+                      "1.2.3-a..b",
+                      "1.2.3-a.",
+                      "1.2.3+",
+                      "1.2.3-+b",
+                      "1.2",
+                      "1.2.3.4",
+                      "1.2.3-a_b",
+                      "v1.2.3",
+                      "99999999999999999999.0.0",
+                      ""];
+
+        // `parse_prefix` uses the `nom` grammar, which accepts the same strings,
+        // but also accepts leading zeros and numeric identifiers that are too
+        // large.
+        for input in &inputs {
+            let owned = parse_prefix(input.as_bytes())
+                .filter(|&(_, length)| length == input.len())
+                .map(|(version, _)| version);
+            let borrowed = VersionRef::parse(input);
+
+            assert!(owned.is_some() || borrowed.is_err(), "{}", input);
+            assert_eq!(borrowed.is_ok(), input.parse::<Version>().is_ok(), "{}", input);
+
+            if let (Some(owned), Ok(borrowed)) = (owned, borrowed) {
+                assert_eq!(owned.to_string(), borrowed.to_string());
+                assert_eq!(owned.build, borrowed.to_owned().build);
+                assert_eq!(owned, borrowed.to_owned());
+            }
+        }
+    }
+
+    #[test]
+    fn test_ordering() {
+        let ordered = ["1.0.0-1",
+                       "1.0.0-2",
+                       "1.0.0-10",
+                       "1.0.0-alpha",
+                       "1.0.0-alpha.1",
+                       "1.0.0-alpha.beta",
+                       "1.0.0-beta",
+                       "1.0.0-beta.2",
+                       "1.0.0-beta.11",
+                       "1.0.0-rc.1",
+                       "1.0.0",
+                       "1.0.1",
+                       "1.1.0",
+                       "2.0.0"];
+
+        for a in &ordered {
+            for b in &ordered {
+                let (owned_a, owned_b): (Version, Version) = (a.parse().unwrap(),
+                                                              b.parse().unwrap());
+                let (ref_a, ref_b) = (VersionRef::parse(a).unwrap(), VersionRef::parse(b).unwrap());
+                let owned = owned_a.cmp(&owned_b);
+
+                assert_eq!(owned, ref_a.cmp(&ref_b), "{} <=> {}", a, b);
+                assert_eq!(Some(owned), ref_a.partial_cmp(&owned_b));
+                assert_eq!(Some(owned), owned_a.partial_cmp(&ref_b));
+            }
+        }
+
+        assert_eq!(VersionRef::parse("1.0.0-rc.1+a").unwrap(),
+                   VersionRef::parse("1.0.0-rc.1+b").unwrap());
+        assert_eq!(VersionRef::parse("1.0.0+a").unwrap(), version!(1, 0, 0));
+    }
+
+    #[test]
+    fn test_constraints() {
+        let constraints = constraints!(And,
+                                       GreaterThanOrEqualTo(version!(1, 0, 0)),
+                                       LessThan(version!(2, 0, 0)),
+                                       ExactlyNot(version!(1, 4, 5)));
+        let release = VersionRef::parse("1.2.3").unwrap();
+        let build = VersionRef::parse("1.2.3+build").unwrap();
+        let rc = VersionRef::parse("1.2.3-rc.1").unwrap();
+
+        assert!(VersionRef::parse("1.0.0").unwrap().satisfies(&constraints));
+        assert!(VersionRef::parse("1.9.9-rc.1").unwrap().satisfies(&constraints));
+        assert!(!VersionRef::parse("1.4.5+build").unwrap().satisfies(&constraints));
+        assert!(!VersionRef::parse("1.0.0-rc.1").unwrap().satisfies(&constraints));
+        assert!(!VersionRef::parse("2.0.0").unwrap().satisfies(&constraints));
+        assert!(Exactly(version!(1, 2, 3)).allows_ref(&build));
+        assert!(!ExactlyNot(version!(1, 2, 3)).allows_ref(&build));
+        assert!(LessThanOrEqualTo(version!(1, 2, 3)).allows_ref(&rc));
+        assert!(!LessThan(version!(1, 2, 3)).allows_ref(&release));
+        assert!(GreaterThan(version!(1, 2, 3, vec![id!("rc")])).allows_ref(&rc));
+        assert!(GreaterThanOrEqualTo(version!(1, 2, 3)).allows_ref(&release));
+    }
+
+}
@@ -0,0 +1,499 @@
+//! Creates semantic version numbers one part at a time.
+//!
+//! The **builder** module provides you with a `VersionBuilder`, which puts
+//! together a version number from its numbers and identifiers, and checks
+//! every identifier as it is added.
+//!
+//! ```
+//! use recital::builder::VersionBuilder;
+//!
+//! let version = VersionBuilder::new()
+//!     .major(1)
+//!     .minor(2)
+//!     .pre("rc")
+//!     .pre(2)
+//!     .git_sha("abc123")
+//!     .build_meta("ci", 4567)
+//!     .dirty(true)
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!("1.2.0-rc.2+git.abc123.ci.4567.dirty", version.to_string());
+//! ```
+//!
+//! The first identifier that is not valid is returned as an error when the
+//! version number is built, with where it was added and why it is not valid.
+//!
+//! ```
+//! use recital::builder::{BuilderError, Field, VersionBuilder};
+//!
+//! let error = VersionBuilder::new().pre("rc").pre("01").build().unwrap_err();
+//!
+//! assert_eq!(BuilderError::LeadingZeros { index: 1, identifier: "01".to_string() }, error);
+//! assert_eq!("pre-release identifier 1 (01) has leading zeros", error.to_string());
+//!
+//! let error = VersionBuilder::new().build_meta("sha", "a b").build().unwrap_err();
+//!
+//! assert_eq!(Field::Build, error.field());
+//! ```
+use std::fmt::{self, Display};
+use super::version::{check, Identifier, Invalid, Version};
+
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+
+/// Represents the list of identifiers that an identifier was added to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Field {
+    /// The pre-release identifiers.
+    Pre,
+
+    /// The build identifiers.
+    Build,
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Field::Pre => write!(f, "pre-release"),
+            Field::Build => write!(f, "build"),
+        }
+    }
+}
+
+/// Represents an identifier that could not be added to a version number.
+///
+/// Each variant has the position of the identifier in its list.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BuilderError {
+    /// The identifier is empty.
+    Empty {
+        /// The list the identifier was added to.
+        field: Field,
+
+        /// The position of the identifier in the list.
+        index: usize,
+    },
+
+    /// The identifier has a character that is not a letter (a-z, A-Z),
+    /// number (0-9), or hyphen.
+    InvalidCharacter {
+        /// The list the identifier was added to.
+        field: Field,
+
+        /// The position of the identifier in the list.
+        index: usize,
+
+        /// The identifier as it was given.
+        identifier: String,
+
+        /// The first character that is not allowed.
+        character: char,
+    },
+
+    /// A numeric pre-release identifier has leading zeros.
+    LeadingZeros {
+        /// The position of the identifier in the list.
+        index: usize,
+
+        /// The identifier as it was given.
+        identifier: String,
+    },
+
+    /// A numeric identifier is negative.
+    Negative {
+        /// The list the identifier was added to.
+        field: Field,
+
+        /// The position of the identifier in the list.
+        index: usize,
+
+        /// The number as it was given.
+        number: i64,
+    },
+
+    /// A numeric pre-release identifier is too large to be compared.
+    TooLarge {
+        /// The position of the identifier in the list.
+        index: usize,
+
+        /// The identifier as it was given.
+        identifier: String,
+    },
+}
+
+impl BuilderError {
+    /// Returns the list of identifiers that the identifier was added to.
+    pub fn field(&self) -> Field {
+        match *self {
+            BuilderError::Empty { field, .. } |
+            BuilderError::InvalidCharacter { field, .. } |
+            BuilderError::Negative { field, .. } => field,
+            BuilderError::LeadingZeros { .. } |
+            BuilderError::TooLarge { .. } => Field::Pre,
+        }
+    }
+
+    /// Returns the position of the identifier in its list.
+    pub fn index(&self) -> usize {
+        match *self {
+            BuilderError::Empty { index, .. } |
+            BuilderError::InvalidCharacter { index, .. } |
+            BuilderError::LeadingZeros { index, .. } |
+            BuilderError::Negative { index, .. } |
+            BuilderError::TooLarge { index, .. } => index,
+        }
+    }
+}
+
+impl Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} identifier {} ", self.field(), self.index())?;
+
+        match *self {
+            BuilderError::Empty { .. } => write!(f, "is empty"),
+            BuilderError::InvalidCharacter { ref identifier, character, .. } => {
+                write!(f, "({}) has an invalid character: {:?}", identifier, character)
+            }
+            BuilderError::LeadingZeros { ref identifier, .. } => {
+                write!(f, "({}) has leading zeros", identifier)
+            }
+            BuilderError::Negative { number, .. } => write!(f, "({}) is negative", number),
+            BuilderError::TooLarge { ref identifier, .. } => {
+                write!(f, "({}) is too large", identifier)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for BuilderError {}
+
+/// Represents a value that can be added to a version number as an identifier.
+///
+/// Strings and integers are converted into a `Value`, so that they can be
+/// given to a `VersionBuilder` directly.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Value {
+    /// A string, which may be numeric.
+    Text(String),
+
+    /// A number that is not negative.
+    Number(u64),
+
+    /// A number that is negative.
+    Negative(i64),
+}
+
+impl<'a> From<&'a str> for Value {
+    fn from(s: &'a str) -> Value {
+        Value::Text(s.to_string())
+    }
+}
+
+impl<'a> From<&'a String> for Value {
+    fn from(s: &'a String) -> Value {
+        Value::Text(s.clone())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Value {
+        Value::Text(s)
+    }
+}
+
+// Add From trait for the unsigned number types.
+macro_rules! from_unsigned {
+    ($x:ty) => {
+        impl From<$x> for Value {
+            fn from(n: $x) -> Value {
+                Value::Number(n as u64)
+            }
+        }
+    }
+}
+
+from_unsigned!(u8);
+from_unsigned!(u16);
+from_unsigned!(u32);
+from_unsigned!(u64);
+from_unsigned!(usize);
+
+// Add From trait for the signed number types, keeping negative numbers so
+// that they can be rejected.
+macro_rules! from_signed {
+    ($x:ty) => {
+        impl From<$x> for Value {
+            fn from(n: $x) -> Value {
+                if n < 0 {
+                    Value::Negative(n as i64)
+                } else {
+                    Value::Number(n as u64)
+                }
+            }
+        }
+    }
+}
+
+from_signed!(i8);
+from_signed!(i16);
+from_signed!(i32);
+from_signed!(i64);
+from_signed!(isize);
+
+/// Converts a value into an identifier for the given list.
+///
+/// Numeric build identifiers with leading zeros are kept as they are, since
+/// the specification allows them, and build identifiers are never compared.
+fn identifier(value: Value, field: Field, index: usize) -> Result<Identifier, BuilderError> {
+    let s = match value {
+        Value::Number(n) => return Ok(Identifier::Number(n)),
+        Value::Negative(number) => {
+            return Err(BuilderError::Negative {
+                field,
+                index,
+                number,
+            })
+        }
+        Value::Text(s) => s,
+    };
+
+    if let Err(invalid) = check(&s, field == Field::Build) {
+        return Err(match invalid {
+            Invalid::Empty => BuilderError::Empty { field, index },
+            Invalid::Character(character) => {
+                BuilderError::InvalidCharacter {
+                    field,
+                    index,
+                    identifier: s,
+                    character,
+                }
+            }
+            Invalid::LeadingZeros => BuilderError::LeadingZeros { index, identifier: s },
+            Invalid::TooLarge => BuilderError::TooLarge { index, identifier: s },
+        });
+    }
+
+    match s.parse() {
+        Ok(n) if s.len() == 1 || !s.starts_with('0') => Ok(Identifier::Number(n)),
+        _ => Ok(Identifier::Alpha(s)),
+    }
+}
+
+/// Creates a version number one part at a time.
+///
+/// Each method takes and returns the builder, so that calls can be chained.
+/// Once an identifier is found to be invalid, everything else that is added
+/// is ignored, and the error is returned by `build`.
+#[derive(Clone, Debug, Default)]
+pub struct VersionBuilder {
+    version: Version,
+    error: Option<BuilderError>,
+}
+
+impl VersionBuilder {
+    /// Creates a builder for the version number `0.0.0`.
+    pub fn new() -> VersionBuilder {
+        Default::default()
+    }
+
+    /// Sets the major version number.
+    pub fn major(mut self, major: u64) -> VersionBuilder {
+        self.version.major = major;
+        self
+    }
+
+    /// Sets the minor version number.
+    pub fn minor(mut self, minor: u64) -> VersionBuilder {
+        self.version.minor = minor;
+        self
+    }
+
+    /// Sets the patch version number.
+    pub fn patch(mut self, patch: u64) -> VersionBuilder {
+        self.version.patch = patch;
+        self
+    }
+
+    /// Adds a pre-release identifier.
+    pub fn pre<T: Into<Value>>(self, identifier: T) -> VersionBuilder {
+        self.push(Field::Pre, identifier.into())
+    }
+
+    /// Adds a build identifier.
+    pub fn build_identifier<T: Into<Value>>(self, identifier: T) -> VersionBuilder {
+        self.push(Field::Build, identifier.into())
+    }
+
+    /// Adds a pair of build identifiers, such as `sha.5114f85`.
+    pub fn build_meta<K, V>(self, key: K, value: V) -> VersionBuilder
+        where K: Into<Value>,
+              V: Into<Value>
+    {
+        self.build_identifier(key).build_identifier(value)
+    }
+
+    /// Adds a build number as `build.<number>`.
+    pub fn build_number(self, number: u64) -> VersionBuilder {
+        self.build_meta("build", number)
+    }
+
+    /// Adds a timestamp, such as the number of seconds since the Unix epoch,
+    /// as a numeric build identifier.
+    pub fn build_timestamp(self, timestamp: u64) -> VersionBuilder {
+        self.build_identifier(timestamp)
+    }
+
+    /// Adds the commit that was built as `git.<sha>`.
+    pub fn git_sha<T: Into<Value>>(self, sha: T) -> VersionBuilder {
+        self.build_meta("git", sha)
+    }
+
+    /// Adds `dirty` if the working tree had changes when it was built.
+    pub fn dirty(self, dirty: bool) -> VersionBuilder {
+        if dirty {
+            self.build_identifier("dirty")
+        } else {
+            self
+        }
+    }
+
+    /// Creates the version number, or returns the first invalid identifier.
+    pub fn build(self) -> Result<Version, BuilderError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.version),
+        }
+    }
+
+    /// Adds an identifier to a list, unless an error was already found.
+    fn push(mut self, field: Field, value: Value) -> VersionBuilder {
+        if self.error.is_some() {
+            return self;
+        }
+
+        let identifiers = match field {
+            Field::Pre => &mut self.version.pre,
+            Field::Build => &mut self.version.build,
+        };
+
+        match identifier(value, field, identifiers.len()) {
+            Ok(identifier) => identifiers.push(identifier),
+            Err(error) => self.error = Some(error),
+        }
+
+        self
+    }
+}
+
+/// Starts from an existing version number, such as to add build metadata.
+///
+/// The identifiers that the version number already has are kept as they are.
+impl From<Version> for VersionBuilder {
+    fn from(version: Version) -> VersionBuilder {
+        VersionBuilder {
+            version,
+            error: None,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use super::{BuilderError, Field, VersionBuilder};
+    use super::super::version::Identifier::{Alpha, Number};
+
+    #[test]
+    fn test_build() {
+        let version = VersionBuilder::new()
+            .major(1)
+            .minor(2)
+            .patch(3)
+            .pre("rc")
+            .pre(2u8)
+            .pre("0a")
+            .build_meta("sha", "5114f85".to_string())
+            .build_timestamp(1700000000)
+            .build_identifier("007")
+            .build()
+            .unwrap();
+
+        assert_eq!("1.2.3-rc.2.0a+sha.5114f85.1700000000.007", version.to_string());
+        assert_eq!(vec![Alpha("rc".to_string()), Number(2), Alpha("0a".to_string())],
+                   version.pre);
+        assert_eq!(Number(1700000000), version.build[2]);
+        assert_eq!(Alpha("007".to_string()), version.build[3]);
+        assert_eq!(Ok(()), version.validate());
+    }
+
+    #[test]
+    fn test_conventions() {
+        let build = |dirty| {
+            VersionBuilder::from(version!(1, 0, 0, vec![id!("beta")]))
+                .git_sha("abc123")
+                .build_number(4567)
+                .dirty(dirty)
+                .build()
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!("1.0.0-beta+git.abc123.build.4567.dirty", build(true));
+        assert_eq!("1.0.0-beta+git.abc123.build.4567", build(false));
+    }
+
+    #[test]
+    fn test_errors() {
+        let pre = |s: &str| VersionBuilder::new().pre("rc").pre(s).build().unwrap_err();
+
+        assert_eq!(BuilderError::Empty {
+                       field: Field::Pre,
+                       index: 1,
+                   },
+                   pre(""));
+        assert_eq!(BuilderError::InvalidCharacter {
+                       field: Field::Pre,
+                       index: 1,
+                       identifier: "r.c".to_string(),
+                       character: '.',
+                   },
+                   pre("r.c"));
+        assert_eq!(BuilderError::LeadingZeros {
+                       index: 1,
+                       identifier: "01".to_string(),
+                   },
+                   pre("01"));
+        assert_eq!(BuilderError::TooLarge {
+                       index: 1,
+                       identifier: "18446744073709551616".to_string(),
+                   },
+                   pre("18446744073709551616"));
+
+        let error = VersionBuilder::new().build_meta("ci", -4).build().unwrap_err();
+
+        assert_eq!(BuilderError::Negative {
+                       field: Field::Build,
+                       index: 1,
+                       number: -4,
+                   },
+                   error);
+        assert_eq!("build identifier 1 (-4) is negative", error.to_string());
+        assert_eq!((Field::Build, 1), (error.field(), error.index()));
+    }
+
+    #[test]
+    fn test_first_error() {
+        let error = VersionBuilder::new()
+            .pre("a b")
+            .pre("")
+            .build_identifier("é")
+            .build()
+            .unwrap_err();
+
+        assert_eq!("pre-release identifier 0 (a b) has an invalid character: ' '",
+                   error.to_string());
+    }
+
+}
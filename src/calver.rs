@@ -0,0 +1,723 @@
+//! Creates, parses, and compares calendar version numbers.
+//!
+//! The **calver** module provides you with a `Version` whose format is given
+//! by a [CalVer][] template, such as `YYYY.0M.MICRO` or `YY.MM.DD_MODIFIER`.
+//! The template is attached to a type that implements `Template`, so that
+//! every version number of that type follows the same format.
+//!
+//! [CalVer]: https://calver.org/
+//!
+//! ```
+//! use recital::calver::{Template, Version};
+//!
+//! struct Monthly;
+//!
+//! impl Template for Monthly {
+//!     const FORMAT: &'static str = "YYYY.0M.MICRO";
+//! }
+//!
+//! let a: Version<Monthly> = "2024.01.3".parse().unwrap();
+//! let b: Version<Monthly> = "2024.02.0".parse().unwrap();
+//!
+//! assert!(a < b);
+//! assert!("2024.1.3".parse::<Version<Monthly>>().is_err());
+//! ```
+//!
+//! The templates support the following tokens, which must be separated by a
+//! period (`.`), hyphen (`-`), or underscore (`_`):
+//!
+//! - `YYYY` - Full year (`2006`, `2016`, `2106`)
+//! - `YY` - Short year (`6`, `16`, `106`)
+//! - `0Y` - Zero-padded year (`06`, `16`, `106`)
+//! - `MM` - Short month (`1`, `2` ... `11`, `12`)
+//! - `0M` - Zero-padded month (`01`, `02` ... `11`, `12`)
+//! - `WW` - Short week of the year (`1`, `2` ... `52`, `53`)
+//! - `0W` - Zero-padded week of the year (`01`, `02` ... `52`, `53`)
+//! - `DD` - Short day (`1`, `2` ... `30`, `31`)
+//! - `0D` - Zero-padded day (`01`, `02` ... `30`, `31`)
+//! - `MAJOR`, `MINOR`, and `MICRO` - Counters (`0`, `1`, `2` ...)
+//! - `MODIFIER` - An optional tag, such as `dev` or `rc1`, at the end
+//!
+//! A version number is bumped to the current date with `bump_with`, using a
+//! `Clock`. The counters are reset when the date moves forward, so the date
+//! can be fixed when testing.
+//!
+//! ```
+//! use recital::calver::{Date, Template, Version};
+//! use recital::scheme::Bump;
+//!
+//! struct Monthly;
+//!
+//! impl Template for Monthly {
+//!     const FORMAT: &'static str = "YYYY.0M.MICRO";
+//! }
+//!
+//! let mut version: Version<Monthly> = "2024.01.3".parse().unwrap();
+//!
+//! version.bump_with(Bump::Patch, &Date::new(2024, 1, 20));
+//!
+//! assert_eq!("2024.01.4", version.to_string());
+//!
+//! version.bump_with(Bump::Patch, &Date::new(2024, 3, 1));
+//!
+//! assert_eq!("2024.03.0", version.to_string());
+//! ```
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display};
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use super::scheme::{Bump, VersionScheme};
+
+/// Defines the format of a calendar version number.
+pub trait Template {
+    /// The template (e.g. `YYYY.0M.MICRO`).
+    const FORMAT: &'static str;
+}
+
+/// Represents a calendar date.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Date {
+    /// The year (e.g. `2024`).
+    pub year: u64,
+
+    /// The month, from one (`1`) to twelve (`12`).
+    pub month: u64,
+
+    /// The day of the month, from one (`1`) to thirty-one (`31`).
+    pub day: u64,
+}
+
+impl Date {
+    /// Creates a new date.
+    pub fn new(year: u64, month: u64, day: u64) -> Date {
+        Date { year, month, day }
+    }
+
+    /// Returns the week of the year, counting from the first day of the year.
+    ///
+    /// The first week is days one to seven, the second is days eight to
+    /// fourteen, and so on, so the last days of the year are in week `53`.
+    pub fn week(&self) -> u64 {
+        let leap = (self.year.is_multiple_of(4) && !self.year.is_multiple_of(100)) ||
+                   self.year.is_multiple_of(400);
+        let before = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+        let index = (self.month.clamp(1, 12) - 1) as usize;
+        let extra = if leap && self.month > 2 { 1 } else { 0 };
+        let ordinal = before[index] + extra + self.day;
+
+        (ordinal.max(1) - 1) / 7 + 1
+    }
+
+    /// Creates a date from the number of days since 1970-01-01.
+    fn from_days(days: u64) -> Date {
+        // Converts from days to a civil date, starting from 0000-03-01.
+        let z = days + 719_468;
+        let era = z / 146_097;
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Date { year, month, day }
+    }
+}
+
+/// Provides the current date when bumping a version number.
+pub trait Clock {
+    /// Returns the current date.
+    fn today(&self) -> Date;
+}
+
+/// A date is a clock that is stopped on that date.
+impl Clock for Date {
+    fn today(&self) -> Date {
+        *self
+    }
+}
+
+/// A clock that uses the system time, in UTC.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn today(&self) -> Date {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        Date::from_days(seconds / 86_400)
+    }
+}
+
+/// Represents a token in a template.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Token {
+    FullYear,
+    ShortYear,
+    PaddedYear,
+    Month,
+    PaddedMonth,
+    Week,
+    PaddedWeek,
+    Day,
+    PaddedDay,
+    Major,
+    Minor,
+    Micro,
+    Modifier,
+}
+
+/// The tokens by name, with longer names first when one is a prefix.
+const TOKENS: [(&str, Token); 13] = [("YYYY", Token::FullYear),
+                                     ("YY", Token::ShortYear),
+                                     ("0Y", Token::PaddedYear),
+                                     ("MM", Token::Month),
+                                     ("0M", Token::PaddedMonth),
+                                     ("WW", Token::Week),
+                                     ("0W", Token::PaddedWeek),
+                                     ("DD", Token::Day),
+                                     ("0D", Token::PaddedDay),
+                                     ("MAJOR", Token::Major),
+                                     ("MINOR", Token::Minor),
+                                     ("MICRO", Token::Micro),
+                                     ("MODIFIER", Token::Modifier)];
+
+impl Token {
+    /// Checks if the token is zero-padded to two digits.
+    fn is_padded(self) -> bool {
+        matches!(self,
+                 Token::PaddedYear | Token::PaddedMonth | Token::PaddedWeek | Token::PaddedDay)
+    }
+
+    /// Checks if the token is a counter.
+    fn is_counter(self) -> bool {
+        matches!(self, Token::Major | Token::Minor | Token::Micro)
+    }
+
+    /// Returns the range of values allowed for the token.
+    fn range(self) -> (u64, u64) {
+        match self {
+            Token::Month | Token::PaddedMonth => (1, 12),
+            Token::Week | Token::PaddedWeek => (1, 53),
+            Token::Day | Token::PaddedDay => (1, 31),
+            _ => (0, u64::MAX),
+        }
+    }
+
+    /// Returns the value of a date token for the given date.
+    fn of(self, date: &Date) -> Option<u64> {
+        match self {
+            Token::FullYear => Some(date.year),
+            Token::ShortYear | Token::PaddedYear => Some(date.year.saturating_sub(2000)),
+            Token::Month | Token::PaddedMonth => Some(date.month),
+            Token::Week | Token::PaddedWeek => Some(date.week()),
+            Token::Day | Token::PaddedDay => Some(date.day),
+            _ => None,
+        }
+    }
+}
+
+/// Represents a token or a separator in a template.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Segment {
+    Token(Token),
+    Separator(char),
+}
+
+/// Parses a template into its segments.
+///
+/// Tokens must be separated by exactly one separator, each token can only be
+/// used once, and `MODIFIER` can only be at the end.
+fn segments(template: &str) -> Result<Vec<Segment>, String> {
+    let invalid = |reason: &str| format!("Invalid CalVer template ({}): {}", reason, template);
+    let mut segments = Vec::new();
+    let mut rest = template;
+
+    while !rest.is_empty() {
+        let expect_token = match segments.last() {
+            None | Some(&Segment::Separator(_)) => true,
+            Some(&Segment::Token(_)) => false,
+        };
+
+        if expect_token {
+            let &(name, token) = TOKENS.iter()
+                .find(|&&(name, _)| rest.starts_with(name))
+                .ok_or_else(|| invalid("expected a token"))?;
+
+            if segments.contains(&Segment::Token(token)) {
+                return Err(invalid("repeated token"));
+            }
+
+            if segments.contains(&Segment::Token(Token::Modifier)) {
+                return Err(invalid("MODIFIER must be last"));
+            }
+
+            segments.push(Segment::Token(token));
+            rest = &rest[name.len()..];
+        } else {
+            match rest.chars().next() {
+                Some(c) if c == '.' || c == '-' || c == '_' => {
+                    segments.push(Segment::Separator(c));
+                    rest = &rest[1..];
+                }
+
+                _ => return Err(invalid("expected a separator")),
+            }
+        }
+    }
+
+    match segments.last() {
+        Some(&Segment::Token(_)) => Ok(segments),
+        _ => Err(invalid("expected a token")),
+    }
+}
+
+/// Returns the tokens of a template, which must be valid.
+fn tokens<T: Template>() -> Vec<Token> {
+    segments(T::FORMAT)
+        .expect("The template was validated when the version number was created.")
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Token(token) if token != Token::Modifier => Some(token),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Represents a calendar version number in the format of a template.
+pub struct Version<T: Template> {
+    /// The values of the tokens, in order, except for the modifier.
+    values: Vec<u64>,
+
+    /// The modifier, if any.
+    modifier: Option<String>,
+
+    template: PhantomData<T>,
+}
+
+impl<T: Template> Version<T> {
+    /// Creates the first version number for the current date.
+    ///
+    /// Every counter is set to zero (`0`), and there is no modifier.
+    pub fn today<C: Clock>(clock: &C) -> Result<Version<T>, String> {
+        segments(T::FORMAT)?;
+
+        let date = clock.today();
+
+        Ok(Version {
+            values: tokens::<T>().iter().map(|t| t.of(&date).unwrap_or(0)).collect(),
+            modifier: None,
+            template: PhantomData,
+        })
+    }
+
+    /// Increments the version number using the date from a clock.
+    ///
+    /// If the date is later than the one in the version number, the date is
+    /// updated and every counter is reset to zero (`0`). Otherwise, the date
+    /// is kept and the counter for the level (`MAJOR`, `MINOR`, or `MICRO`) is
+    /// incremented, or the last counter if the template does not have that
+    /// one, and the counters after it are reset. The modifier is always
+    /// removed, so the version number never becomes lower.
+    ///
+    /// A version number without counters is only changed by a later date, and
+    /// one whose counter is already at the largest value is left unchanged.
+    pub fn bump_with<C: Clock>(&mut self, level: Bump, clock: &C) {
+        let date = clock.today();
+        let tokens = tokens::<T>();
+        let current: Vec<u64> = self.values
+            .iter()
+            .zip(tokens.iter())
+            .filter(|&(_, token)| token.of(&date).is_some())
+            .map(|(&value, _)| value)
+            .collect();
+        let today: Vec<u64> = tokens.iter().filter_map(|token| token.of(&date)).collect();
+        let counters: Vec<usize> = (0..tokens.len()).filter(|&i| tokens[i].is_counter()).collect();
+
+        if today > current {
+            for (value, token) in self.values.iter_mut().zip(tokens.iter()) {
+                if let Some(today) = token.of(&date) {
+                    *value = today;
+                }
+            }
+
+            for &i in &counters {
+                self.values[i] = 0;
+            }
+        } else {
+            let wanted = match level {
+                Bump::Major => Token::Major,
+                Bump::Minor => Token::Minor,
+                Bump::Patch => Token::Micro,
+            };
+
+            let index = counters.iter()
+                .cloned()
+                .find(|&i| tokens[i] == wanted)
+                .or_else(|| counters.last().cloned());
+
+            if let Some(index) = index {
+                match self.values[index].checked_add(1) {
+                    Some(value) => self.values[index] = value,
+                    None => return,
+                }
+            }
+
+            for &i in counters.iter().filter(|&&i| Some(i) > index) {
+                self.values[i] = 0;
+            }
+        }
+
+        self.modifier = None;
+    }
+
+    /// Returns the modifier, if any.
+    pub fn modifier(&self) -> Option<&str> {
+        self.modifier.as_deref()
+    }
+}
+
+impl<T: Template> Clone for Version<T> {
+    fn clone(&self) -> Version<T> {
+        Version {
+            values: self.values.clone(),
+            modifier: self.modifier.clone(),
+            template: PhantomData,
+        }
+    }
+}
+
+impl<T: Template> Debug for Version<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Version({})", self)
+    }
+}
+
+impl<T: Template> Display for Version<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let segments = segments(T::FORMAT).map_err(|_| fmt::Error)?;
+        let mut values = self.values.iter();
+
+        for (i, segment) in segments.iter().enumerate() {
+            match *segment {
+                Segment::Separator(c) => {
+                    let modifier = segments.get(i + 1) == Some(&Segment::Token(Token::Modifier));
+
+                    if !modifier || self.modifier.is_some() {
+                        write!(f, "{}", c)?;
+                    }
+                }
+
+                Segment::Token(Token::Modifier) => {
+                    if let Some(ref modifier) = self.modifier {
+                        write!(f, "{}", modifier)?;
+                    }
+                }
+
+                Segment::Token(token) => {
+                    let value = values.next().ok_or(fmt::Error)?;
+
+                    if token.is_padded() {
+                        write!(f, "{:02}", value)?;
+                    } else {
+                        write!(f, "{}", value)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Enables parsing of strings as calendar version numbers.
+///
+/// The string must match the template exactly, including the padding of the
+/// tokens. The modifier, and the separator before it, may be left out.
+impl<T: Template> FromStr for Version<T> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Version<T>, String> {
+        let invalid = || format!("Invalid version number for {}: {}", T::FORMAT, s);
+        let segments = segments(T::FORMAT)?;
+        let mut version = Version {
+            values: Vec::new(),
+            modifier: None,
+            template: PhantomData,
+        };
+        let mut rest = s.trim();
+
+        for (i, segment) in segments.iter().enumerate() {
+            match *segment {
+                Segment::Separator(c) => {
+                    let modifier = segments.get(i + 1) == Some(&Segment::Token(Token::Modifier));
+
+                    if modifier && rest.is_empty() {
+                        break;
+                    }
+
+                    if !rest.starts_with(c) {
+                        return Err(invalid());
+                    }
+
+                    rest = &rest[1..];
+                }
+
+                Segment::Token(Token::Modifier) => {
+                    if rest.is_empty() || !rest.chars().all(|c| c.is_ascii_alphanumeric()) {
+                        return Err(invalid());
+                    }
+
+                    version.modifier = Some(rest.to_string());
+                    rest = "";
+                }
+
+                Segment::Token(token) => {
+                    let end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+                    let digits = &rest[..end];
+                    let value: u64 = digits.parse().map_err(|_| invalid())?;
+                    let (min, max) = token.range();
+                    let expected = if token.is_padded() {
+                        format!("{:02}", value)
+                    } else {
+                        value.to_string()
+                    };
+
+                    if digits != expected || value < min || value > max {
+                        return Err(invalid());
+                    }
+
+                    version.values.push(value);
+                    rest = &rest[end..];
+                }
+            }
+        }
+
+        if !rest.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(version)
+    }
+}
+
+impl<T: Template> Eq for Version<T> {}
+
+/// A version number with a modifier sorts before the same one without.
+impl<T: Template> Ord for Version<T> {
+    fn cmp(&self, other: &Version<T>) -> Ordering {
+        self.values.cmp(&other.values).then_with(|| match (&self.modifier, &other.modifier) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (a, b) => a.is_none().cmp(&b.is_none()),
+        })
+    }
+}
+
+impl<T: Template> PartialEq for Version<T> {
+    fn eq(&self, other: &Version<T>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Template> PartialOrd for Version<T> {
+    fn partial_cmp(&self, other: &Version<T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Template> VersionScheme for Version<T> {
+    /// Increments the version number using the system clock.
+    fn bump(&mut self, level: Bump) {
+        self.bump_with(level, &SystemClock);
+    }
+
+    /// A version number is stable if it does not have a modifier.
+    fn is_stable(&self) -> bool {
+        self.modifier.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{segments, Clock, Date, SystemClock, Template, Version};
+    use super::super::resolve::{resolve, Constraint};
+    use super::super::resolve::Operation::*;
+    use super::super::scheme::{Bump, VersionScheme};
+
+    struct Monthly;
+
+    impl Template for Monthly {
+        const FORMAT: &'static str = "YYYY.0M.MICRO";
+    }
+
+    struct Daily;
+
+    impl Template for Daily {
+        const FORMAT: &'static str = "YY.MM.DD_MODIFIER";
+    }
+
+    struct Weekly;
+
+    impl Template for Weekly {
+        const FORMAT: &'static str = "0Y.0W-MAJOR.MINOR";
+    }
+
+    struct Broken;
+
+    impl Template for Broken {
+        const FORMAT: &'static str = "YYYY.BUILD";
+    }
+
+    #[test]
+    fn test_segments() {
+        assert!(segments("YYYY.0M.MICRO").is_ok());
+        assert!(segments("YY.MM.DD_MODIFIER").is_ok());
+
+        for template in &["", "YYYY.", ".YYYY", "YYYY0M", "YYYY..MM", "YYYY.YYYY",
+                          "MODIFIER.YYYY", "YYYY.BUILD"] {
+            assert!(segments(template).is_err(), "{} should be invalid", template);
+        }
+
+        assert!("2024".parse::<Version<Broken>>().is_err());
+        assert!(Version::<Broken>::today(&Date::new(2024, 1, 1)).is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("2024.01.3", "2024.01.3".parse::<Version<Monthly>>().unwrap().to_string());
+        assert_eq!("24.1.9", "24.1.9".parse::<Version<Daily>>().unwrap().to_string());
+        assert_eq!("24.1.9_dev", "24.1.9_dev".parse::<Version<Daily>>().unwrap().to_string());
+        assert_eq!(Some("dev"), "24.1.9_dev".parse::<Version<Daily>>().unwrap().modifier());
+        assert_eq!(None, "24.1.9".parse::<Version<Daily>>().unwrap().modifier());
+        assert_eq!("06.52-1.0", "06.52-1.0".parse::<Version<Weekly>>().unwrap().to_string());
+
+        for s in &["2024.1.3", "2024.13.0", "2024.00.0", "2024.01.03", "2024.01", "2024.01.3.4",
+                   "02024.01.3", "2024-01-3", "2024.01.x"] {
+            assert!(s.parse::<Version<Monthly>>().is_err(), "{} should be invalid", s);
+        }
+
+        for s in &["24.01.9", "24.1.32", "24.1.9_", "24.1.9-dev", "24.1.9_dev.1"] {
+            assert!(s.parse::<Version<Daily>>().is_err(), "{} should be invalid", s);
+        }
+    }
+
+    #[test]
+    fn test_ordering() {
+        for pair in &[("2023.12.9", "2024.01.0"), ("2024.01.9", "2024.01.10")] {
+            let a: Version<Monthly> = pair.0.parse().unwrap();
+            let b: Version<Monthly> = pair.1.parse().unwrap();
+
+            assert!(a < b, "{} < {}", a, b);
+        }
+
+        for pair in &[("24.1.9_dev", "24.1.9"), ("24.1.9_alpha", "24.1.9_beta"),
+                      ("24.1.9", "24.1.10_dev")] {
+            let a: Version<Daily> = pair.0.parse().unwrap();
+            let b: Version<Daily> = pair.1.parse().unwrap();
+
+            assert!(a < b, "{} < {}", a, b);
+        }
+
+        assert_eq!("2024.01.3".parse::<Version<Monthly>>().unwrap(),
+                   "2024.01.3".parse::<Version<Monthly>>().unwrap());
+    }
+
+    #[test]
+    fn test_today() {
+        let version = Version::<Weekly>::today(&Date::new(2024, 12, 31)).unwrap();
+
+        assert_eq!("24.53-0.0", version.to_string());
+        assert_eq!(1, Date::new(2024, 1, 7).week());
+        assert_eq!(2, Date::new(2024, 1, 8).week());
+        assert_eq!(9, Date::new(2024, 3, 1).week());
+        assert_eq!(9, Date::new(2023, 3, 1).week());
+    }
+
+    #[test]
+    fn test_bump_with() {
+        let mut version: Version<Monthly> = "2024.01.3".parse().unwrap();
+
+        version.bump_with(Bump::Patch, &Date::new(2024, 1, 31));
+
+        assert_eq!("2024.01.4", version.to_string());
+
+        version.bump_with(Bump::Major, &Date::new(2024, 1, 31));
+
+        assert_eq!("2024.01.5", version.to_string());
+
+        version.bump_with(Bump::Patch, &Date::new(2025, 1, 1));
+
+        assert_eq!("2025.01.0", version.to_string());
+
+        let mut version: Version<Weekly> = "24.02-3.4".parse().unwrap();
+
+        version.bump_with(Bump::Major, &Date::new(2024, 1, 10));
+
+        assert_eq!("24.02-4.0", version.to_string());
+
+        version.bump_with(Bump::Minor, &Date::new(2024, 1, 10));
+
+        assert_eq!("24.02-4.1", version.to_string());
+
+        let mut version: Version<Daily> = "24.1.9_rc1".parse().unwrap();
+
+        version.bump_with(Bump::Patch, &Date::new(2024, 1, 9));
+
+        assert_eq!("24.1.9", version.to_string());
+        assert!(version.is_stable());
+
+        version.bump_with(Bump::Patch, &Date::new(2024, 1, 10));
+
+        assert_eq!("24.1.10", version.to_string());
+
+        let before: Version<Monthly> = "2024.03.5".parse().unwrap();
+        let mut version = before.clone();
+
+        version.bump_with(Bump::Patch, &Date::new(2024, 1, 1));
+
+        assert_eq!("2024.03.6", version.to_string());
+        assert!(version > before);
+
+        let mut version: Version<Daily> = "24.1.9_rc1".parse().unwrap();
+
+        version.bump_with(Bump::Patch, &Date::new(2023, 12, 31));
+
+        assert_eq!("24.1.9", version.to_string());
+
+        let mut version: Version<Monthly> = "2024.01.18446744073709551615".parse().unwrap();
+
+        version.bump_with(Bump::Patch, &Date::new(2024, 1, 31));
+
+        assert_eq!("2024.01.18446744073709551615", version.to_string());
+    }
+
+    #[test]
+    fn test_system_clock() {
+        assert_eq!(Date::new(1970, 1, 1), Date::from_days(0));
+        assert_eq!(Date::new(2000, 2, 29), Date::from_days(11_016));
+        assert_eq!(Date::new(2024, 1, 1), Date::from_days(19_723));
+        assert!(SystemClock.today().year >= 2024);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let versions: Vec<Version<Monthly>> = ["2023.11.0", "2024.01.0", "2024.01.1", "2024.02.0"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+
+        let c = constraints!(And,
+                             GreaterThanOrEqualTo("2024.01.0".parse().unwrap()),
+                             LessThan("2024.02.0".parse().unwrap()));
+
+        assert!(c.allows(&"2024.01.7".parse().unwrap()));
+        assert_eq!(resolve(&versions, &c), &versions[1..3]);
+    }
+
+}
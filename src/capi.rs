@@ -0,0 +1,673 @@
+//! Exposes version numbers and constraints through a C ABI.
+//!
+//! The **capi** module provides you with `extern "C"` functions that work on
+//! opaque `RecitalVersion` and `RecitalConstraints` pointers and report errors
+//! with a `RecitalStatus`, rather than unwinding. The functions are declared in
+//! `include/recital.h`.
+//!
+//! ```c
+//! #include "recital.h"
+//!
+//! RecitalVersion *installed = NULL;
+//! RecitalConstraints *requirement = NULL;
+//! char *error = NULL;
+//!
+//! if (recital_version_parse("1.2.3", &installed, &error) != RECITAL_STATUS_OK) {
+//!     fprintf(stderr, "%s\n", error);
+//!     recital_string_free(error);
+//! }
+//!
+//! recital_requirement_parse("^1.2", &requirement, NULL);
+//!
+//! if (recital_constraints_allows(requirement, installed)) {
+//!     /* ... */
+//! }
+//!
+//! recital_constraints_free(requirement);
+//! recital_version_free(installed);
+//! ```
+//!
+//! Version numbers and constraints are opaque handles, which are created by
+//! this library and have to be freed with their `_free` function. Strings that
+//! are returned have to be freed with `recital_string_free`.
+//!
+//! The library can be built for linking with a C program using:
+//!
+//! ```sh
+//! cargo rustc --release --lib --features capi --crate-type staticlib
+//! ```
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+use super::cargo::Requirement;
+use super::resolve::{Constraint, Constraints, Operation};
+use super::scheme::{Bump, VersionScheme};
+use super::version::Version;
+
+/// Represents the outcome of a function call.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecitalStatus {
+    /// The call succeeded.
+    Ok = 0,
+
+    /// A required pointer was null.
+    NullPointer = 1,
+
+    /// A string was not valid UTF-8.
+    InvalidUtf8 = 2,
+
+    /// A string was not a valid version number.
+    InvalidVersion = 3,
+
+    /// A string was not a valid version requirement.
+    InvalidRequirement = 4,
+
+    /// A number was not one of the values of its enum.
+    InvalidEnum = 5,
+
+    /// A version number was too large to be incremented.
+    Overflow = 6,
+}
+
+/// Represents how much a version number is incremented.
+///
+/// Functions take it as an `int`, since C does not stop other numbers from
+/// being passed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecitalBump {
+    /// Increment the patch version number.
+    Patch = 0,
+
+    /// Increment the minor version number.
+    Minor = 1,
+
+    /// Increment the major version number.
+    Major = 2,
+}
+
+/// Represents how the constraints in a group are combined.
+///
+/// Functions take it as an `int`, since C does not stop other numbers from
+/// being passed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecitalJoin {
+    /// All constraints must be satisfied.
+    And = 0,
+
+    /// At least one constraint must be satisfied.
+    Or = 1,
+}
+
+/// Represents a comparison with a version number.
+///
+/// Functions take it as an `int`, since C does not stop other numbers from
+/// being passed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecitalOperator {
+    /// Match the exact version number. (=)
+    Exactly = 0,
+
+    /// Exclude the exact version number. (!=)
+    ExactlyNot = 1,
+
+    /// Exclusively match any greater version number. (>)
+    GreaterThan = 2,
+
+    /// Inclusively match any greater version number. (>=)
+    GreaterThanOrEqualTo = 3,
+
+    /// Exclusively match any lesser version number. (<)
+    LessThan = 4,
+
+    /// Inclusively match any lesser version number. (<=)
+    LessThanOrEqualTo = 5,
+}
+
+impl TryFrom<c_int> for RecitalBump {
+    type Error = RecitalStatus;
+
+    fn try_from(n: c_int) -> Result<RecitalBump, RecitalStatus> {
+        match n {
+            0 => Ok(RecitalBump::Patch),
+            1 => Ok(RecitalBump::Minor),
+            2 => Ok(RecitalBump::Major),
+            _ => Err(RecitalStatus::InvalidEnum),
+        }
+    }
+}
+
+impl TryFrom<c_int> for RecitalJoin {
+    type Error = RecitalStatus;
+
+    fn try_from(n: c_int) -> Result<RecitalJoin, RecitalStatus> {
+        match n {
+            0 => Ok(RecitalJoin::And),
+            1 => Ok(RecitalJoin::Or),
+            _ => Err(RecitalStatus::InvalidEnum),
+        }
+    }
+}
+
+impl TryFrom<c_int> for RecitalOperator {
+    type Error = RecitalStatus;
+
+    fn try_from(n: c_int) -> Result<RecitalOperator, RecitalStatus> {
+        match n {
+            0 => Ok(RecitalOperator::Exactly),
+            1 => Ok(RecitalOperator::ExactlyNot),
+            2 => Ok(RecitalOperator::GreaterThan),
+            3 => Ok(RecitalOperator::GreaterThanOrEqualTo),
+            4 => Ok(RecitalOperator::LessThan),
+            5 => Ok(RecitalOperator::LessThanOrEqualTo),
+            _ => Err(RecitalStatus::InvalidEnum),
+        }
+    }
+}
+
+/// Represents a semantic version number.
+pub struct RecitalVersion {
+    version: Version,
+}
+
+/// Represents a group of constraints.
+pub struct RecitalConstraints {
+    constraints: Constraints,
+}
+
+/// Copies a message into a string owned by the caller, if one was requested.
+unsafe fn set_error(error: *mut *mut c_char, message: &str) {
+    if !error.is_null() {
+        *error = CString::new(message.replace('\0', ""))
+            .map(CString::into_raw)
+            .unwrap_or(ptr::null_mut());
+    }
+}
+
+/// Parses a C string, reporting any error.
+unsafe fn parse<T, F>(input: *const c_char, error: *mut *mut c_char, invalid: RecitalStatus, f: F)
+                      -> Result<T, RecitalStatus>
+    where F: FnOnce(&str) -> Result<T, String>
+{
+    if !error.is_null() {
+        *error = ptr::null_mut();
+    }
+
+    if input.is_null() {
+        set_error(error, "The string is null.");
+
+        return Err(RecitalStatus::NullPointer);
+    }
+
+    let input = match CStr::from_ptr(input).to_str() {
+        Ok(input) => input,
+        Err(e) => {
+            set_error(error, &e.to_string());
+
+            return Err(RecitalStatus::InvalidUtf8);
+        }
+    };
+
+    f(input).map_err(|message| {
+        set_error(error, &message);
+
+        invalid
+    })
+}
+
+/// Parses a string as a version number.
+///
+/// On success, `out` is set to a new version number. On failure, `error` is
+/// set to a message that describes the problem, unless it is null.
+///
+/// # Safety
+///
+/// `input` must be a null terminated string. `out` must be valid for writes,
+/// and `error` must be valid for writes or null.
+#[no_mangle]
+pub unsafe extern "C" fn recital_version_parse(input: *const c_char,
+                                               out: *mut *mut RecitalVersion,
+                                               error: *mut *mut c_char)
+                                               -> RecitalStatus {
+    if out.is_null() {
+        return RecitalStatus::NullPointer;
+    }
+
+    match parse(input, error, RecitalStatus::InvalidVersion, |s| s.parse::<Version>()) {
+        Ok(version) => {
+            *out = Box::into_raw(Box::new(RecitalVersion { version }));
+
+            RecitalStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// Creates a version number without any identifiers.
+#[no_mangle]
+pub extern "C" fn recital_version_new(major: u64, minor: u64, patch: u64) -> *mut RecitalVersion {
+    Box::into_raw(Box::new(RecitalVersion { version: version!(major, minor, patch) }))
+}
+
+/// Creates a copy of a version number, or returns null if it is null.
+///
+/// # Safety
+///
+/// `version` must be a version number created by this library, or null.
+#[no_mangle]
+pub unsafe extern "C" fn recital_version_clone(version: *const RecitalVersion)
+                                               -> *mut RecitalVersion {
+    match version.as_ref() {
+        Some(version) => {
+            Box::into_raw(Box::new(RecitalVersion { version: version.version.clone() }))
+        }
+        None => ptr::null_mut(),
+    }
+}
+
+/// Frees a version number. Nothing is done if it is null.
+///
+/// # Safety
+///
+/// `version` must be a version number created by this library, or null, and
+/// must not be used again.
+#[no_mangle]
+pub unsafe extern "C" fn recital_version_free(version: *mut RecitalVersion) {
+    if !version.is_null() {
+        drop(Box::from_raw(version));
+    }
+}
+
+/// Returns the major version number, or zero (`0`) if it is null.
+///
+/// # Safety
+///
+/// `version` must be a version number created by this library, or null.
+#[no_mangle]
+pub unsafe extern "C" fn recital_version_major(version: *const RecitalVersion) -> u64 {
+    version.as_ref().map(|v| v.version.major).unwrap_or(0)
+}
+
+/// Returns the minor version number, or zero (`0`) if it is null.
+///
+/// # Safety
+///
+/// `version` must be a version number created by this library, or null.
+#[no_mangle]
+pub unsafe extern "C" fn recital_version_minor(version: *const RecitalVersion) -> u64 {
+    version.as_ref().map(|v| v.version.minor).unwrap_or(0)
+}
+
+/// Returns the patch version number, or zero (`0`) if it is null.
+///
+/// # Safety
+///
+/// `version` must be a version number created by this library, or null.
+#[no_mangle]
+pub unsafe extern "C" fn recital_version_patch(version: *const RecitalVersion) -> u64 {
+    version.as_ref().map(|v| v.version.patch).unwrap_or(0)
+}
+
+/// Compares two version numbers.
+///
+/// Returns a negative number if `a` is less than `b`, zero (`0`) if they are
+/// equal, and a positive number if `a` is greater than `b`. A null version
+/// number is less than any other.
+///
+/// # Safety
+///
+/// `a` and `b` must be version numbers created by this library, or null.
+#[no_mangle]
+pub unsafe extern "C" fn recital_version_compare(a: *const RecitalVersion,
+                                                 b: *const RecitalVersion)
+                                                 -> c_int {
+    let a = a.as_ref().map(|v| &v.version);
+    let b = b.as_ref().map(|v| &v.version);
+
+    match a.cmp(&b) {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+/// Increments a version number, removing its identifiers.
+///
+/// `level` is a `RecitalBump`. The version number is not changed if the
+/// number that would be incremented is already the largest `uint64_t`.
+///
+/// # Safety
+///
+/// `version` must be a version number created by this library, or null.
+#[no_mangle]
+pub unsafe extern "C" fn recital_version_increment(version: *mut RecitalVersion,
+                                                   level: c_int)
+                                                   -> RecitalStatus {
+    let version = match version.as_mut() {
+        Some(version) => &mut version.version,
+        None => return RecitalStatus::NullPointer,
+    };
+
+    let (level, number) = match RecitalBump::try_from(level) {
+        Ok(RecitalBump::Patch) => (Bump::Patch, version.patch),
+        Ok(RecitalBump::Minor) => (Bump::Minor, version.minor),
+        Ok(RecitalBump::Major) => (Bump::Major, version.major),
+        Err(status) => return status,
+    };
+
+    if number == u64::MAX {
+        return RecitalStatus::Overflow;
+    }
+
+    version.bump(level);
+
+    RecitalStatus::Ok
+}
+
+/// Displays a version number as a new string, or returns null if it is null.
+///
+/// The string must be freed with `recital_string_free`.
+///
+/// # Safety
+///
+/// `version` must be a version number created by this library, or null.
+#[no_mangle]
+pub unsafe extern "C" fn recital_version_to_string(version: *const RecitalVersion)
+                                                   -> *mut c_char {
+    match version.as_ref() {
+        Some(version) => {
+            CString::new(version.version.to_string())
+                .map(CString::into_raw)
+                .unwrap_or(ptr::null_mut())
+        }
+        None => ptr::null_mut(),
+    }
+}
+
+/// Frees a string returned by this library. Nothing is done if it is null.
+///
+/// # Safety
+///
+/// `s` must be a string returned by this library, or null, and must not be
+/// used again.
+#[no_mangle]
+pub unsafe extern "C" fn recital_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Creates an empty group of constraints, or returns null if `join` is not a
+/// `RecitalJoin`.
+///
+/// An empty `RECITAL_JOIN_AND` group allows every version number, and an empty
+/// `RECITAL_JOIN_OR` group allows none.
+#[no_mangle]
+pub extern "C" fn recital_constraints_new(join: c_int) -> *mut RecitalConstraints {
+    let constraints = match RecitalJoin::try_from(join) {
+        Ok(RecitalJoin::And) => Constraints::And(Vec::new()),
+        Ok(RecitalJoin::Or) => Constraints::Or(Vec::new()),
+        Err(_) => return ptr::null_mut(),
+    };
+
+    Box::into_raw(Box::new(RecitalConstraints { constraints }))
+}
+
+/// Parses a version requirement, using the rules of Cargo (`^1.2, <1.5`).
+///
+/// On success, `out` is set to a new group of constraints with the
+/// requirement in it. On failure, `error` is set to a message that describes
+/// the problem, unless it is null.
+///
+/// # Safety
+///
+/// `input` must be a null terminated string. `out` must be valid for writes,
+/// and `error` must be valid for writes or null.
+#[no_mangle]
+pub unsafe extern "C" fn recital_requirement_parse(input: *const c_char,
+                                                   out: *mut *mut RecitalConstraints,
+                                                   error: *mut *mut c_char)
+                                                   -> RecitalStatus {
+    if out.is_null() {
+        return RecitalStatus::NullPointer;
+    }
+
+    match parse(input, error, RecitalStatus::InvalidRequirement, |s| s.parse::<Requirement>()) {
+        Ok(requirement) => {
+            let requirement: Box<dyn Constraint> = Box::new(requirement);
+
+            *out = Box::into_raw(Box::new(RecitalConstraints {
+                constraints: Constraints::And(vec![requirement]),
+            }));
+
+            RecitalStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// Adds a comparison with a copy of a version number to a group.
+///
+/// `op` is a `RecitalOperator`.
+///
+/// # Safety
+///
+/// `constraints` and `version` must have been created by this library, or
+/// be null.
+#[no_mangle]
+pub unsafe extern "C" fn recital_constraints_add_operation(constraints: *mut RecitalConstraints,
+                                                           op: c_int,
+                                                           version: *const RecitalVersion)
+                                                           -> RecitalStatus {
+    let (constraints, version) = match (constraints.as_mut(), version.as_ref()) {
+        (Some(constraints), Some(version)) => (constraints, version.version.clone()),
+        _ => return RecitalStatus::NullPointer,
+    };
+
+    let operation = match RecitalOperator::try_from(op) {
+        Ok(RecitalOperator::Exactly) => Operation::Exactly(version),
+        Ok(RecitalOperator::ExactlyNot) => Operation::ExactlyNot(version),
+        Ok(RecitalOperator::GreaterThan) => Operation::GreaterThan(version),
+        Ok(RecitalOperator::GreaterThanOrEqualTo) => Operation::GreaterThanOrEqualTo(version),
+        Ok(RecitalOperator::LessThan) => Operation::LessThan(version),
+        Ok(RecitalOperator::LessThanOrEqualTo) => Operation::LessThanOrEqualTo(version),
+        Err(status) => return status,
+    };
+
+    push(constraints, Box::new(operation));
+
+    RecitalStatus::Ok
+}
+
+/// Adds a group of constraints to another group, to build a tree.
+///
+/// The added group is owned by the other group from then on, and must not be
+/// used or freed. If `constraints` is null, the added group is freed.
+///
+/// # Safety
+///
+/// `constraints` and `group` must have been created by this library, or be
+/// null.
+#[no_mangle]
+pub unsafe extern "C" fn recital_constraints_add_group(constraints: *mut RecitalConstraints,
+                                                       group: *mut RecitalConstraints)
+                                                       -> RecitalStatus {
+    if group.is_null() {
+        return RecitalStatus::NullPointer;
+    }
+
+    let group = Box::from_raw(group);
+
+    match constraints.as_mut() {
+        Some(constraints) => {
+            push(constraints, Box::new(group.constraints));
+
+            RecitalStatus::Ok
+        }
+        None => RecitalStatus::NullPointer,
+    }
+}
+
+/// Adds a constraint to a group.
+fn push(constraints: &mut RecitalConstraints, constraint: Box<dyn Constraint>) {
+    match constraints.constraints {
+        Constraints::And(ref mut list) | Constraints::Or(ref mut list) => list.push(constraint),
+    }
+}
+
+/// Checks if a version number satisfies a group of constraints.
+///
+/// Returns `false` if either of them is null.
+///
+/// # Safety
+///
+/// `constraints` and `version` must have been created by this library, or
+/// be null.
+#[no_mangle]
+pub unsafe extern "C" fn recital_constraints_allows(constraints: *const RecitalConstraints,
+                                                    version: *const RecitalVersion)
+                                                    -> bool {
+    match (constraints.as_ref(), version.as_ref()) {
+        (Some(constraints), Some(version)) => constraints.constraints.allows(&version.version),
+        _ => false,
+    }
+}
+
+/// Frees a group of constraints, and every group in it. Nothing is done if
+/// it is null.
+///
+/// # Safety
+///
+/// `constraints` must have been created by this library, or be null, and must
+/// not be used again.
+#[no_mangle]
+pub unsafe extern "C" fn recital_constraints_free(constraints: *mut RecitalConstraints) {
+    if !constraints.is_null() {
+        drop(Box::from_raw(constraints));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::ffi::{CStr, CString};
+    use std::ptr;
+    use super::*;
+
+    unsafe fn version(s: &str) -> *mut RecitalVersion {
+        let input = CString::new(s).unwrap();
+        let mut version = ptr::null_mut();
+
+        assert_eq!(RecitalStatus::Ok,
+                   recital_version_parse(input.as_ptr(), &mut version, ptr::null_mut()));
+
+        version
+    }
+
+    #[test]
+    fn test_parse_error() {
+        unsafe {
+            let input = CString::new("1.2").unwrap();
+            let mut version = ptr::null_mut();
+            let mut error = ptr::null_mut();
+
+            assert_eq!(RecitalStatus::InvalidVersion,
+                       recital_version_parse(input.as_ptr(), &mut version, &mut error));
+            assert!(version.is_null());
+            assert_eq!("Invalid version number: 1.2", CStr::from_ptr(error).to_str().unwrap());
+
+            recital_string_free(error);
+
+            assert_eq!(RecitalStatus::NullPointer,
+                       recital_version_parse(ptr::null(), &mut version, &mut error));
+
+            recital_string_free(error);
+        }
+    }
+
+    #[test]
+    fn test_version() {
+        unsafe {
+            let a = version("1.2.3-rc.1");
+            let b = recital_version_clone(a);
+
+            assert_eq!(0, recital_version_compare(a, b));
+            assert_eq!(RecitalStatus::Ok,
+                       recital_version_increment(b, RecitalBump::Minor as c_int));
+            assert_eq!(-1, recital_version_compare(a, b));
+            assert_eq!(1, recital_version_compare(b, ptr::null()));
+            assert_eq!(1, recital_version_major(b));
+            assert_eq!(3, recital_version_minor(b));
+            assert_eq!(0, recital_version_patch(b));
+
+            let s = recital_version_to_string(b);
+
+            assert_eq!("1.3.0", CStr::from_ptr(s).to_str().unwrap());
+
+            recital_string_free(s);
+            recital_version_free(a);
+            recital_version_free(b);
+        }
+    }
+
+    #[test]
+    fn test_increment_errors() {
+        unsafe {
+            let largest = recital_version_new(1, u64::MAX, 3);
+
+            assert_eq!(RecitalStatus::InvalidEnum, recital_version_increment(largest, 3));
+            assert_eq!(RecitalStatus::InvalidEnum, recital_version_increment(largest, -1));
+            assert_eq!(RecitalStatus::Overflow,
+                       recital_version_increment(largest, RecitalBump::Minor as c_int));
+            assert_eq!(u64::MAX, recital_version_minor(largest));
+            assert_eq!(RecitalStatus::Ok,
+                       recital_version_increment(largest, RecitalBump::Major as c_int));
+            assert_eq!(2, recital_version_major(largest));
+
+            recital_version_free(largest);
+        }
+    }
+
+    #[test]
+    fn test_constraints() {
+        unsafe {
+            let tree = recital_constraints_new(RecitalJoin::Or as c_int);
+            let group = recital_constraints_new(RecitalJoin::And as c_int);
+            let low = recital_version_new(1, 0, 0);
+            let high = recital_version_new(2, 0, 0);
+            let input = CString::new("^3.1").unwrap();
+            let mut requirement = ptr::null_mut();
+
+            recital_constraints_add_operation(group,
+                                              RecitalOperator::GreaterThanOrEqualTo as c_int,
+                                              low);
+            recital_constraints_add_operation(group, RecitalOperator::LessThan as c_int, high);
+            recital_constraints_add_group(tree, group);
+            recital_requirement_parse(input.as_ptr(), &mut requirement, ptr::null_mut());
+            recital_constraints_add_group(tree, requirement);
+
+            for &(s, allowed) in &[("1.5.0", true), ("2.0.0", false), ("3.2.0", true)] {
+                let v = version(s);
+
+                assert_eq!(allowed, recital_constraints_allows(tree, v), "{}", s);
+
+                recital_version_free(v);
+            }
+
+            assert!(!recital_constraints_allows(ptr::null(), low));
+            assert_eq!(RecitalStatus::InvalidEnum,
+                       recital_constraints_add_operation(tree, 6, low));
+            assert!(recital_constraints_new(-1).is_null());
+
+            recital_version_free(low);
+            recital_version_free(high);
+            recital_constraints_free(tree);
+        }
+    }
+
+}
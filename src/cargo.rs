@@ -0,0 +1,294 @@
+//! Parses and evaluates version requirements the way Cargo does.
+//!
+//! The **cargo** module provides you with a `Requirement`, which is created
+//! from the requirement strings found in `Cargo.toml` files. The strings are
+//! converted into a set of `Operation` constraints with the same meaning that
+//! Cargo gives them.
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate recital;
+//! use recital::cargo::Requirement;
+//! use recital::resolve::Constraint;
+//!
+//! # fn main() {
+//! let requirement: Requirement = "1.2".parse().unwrap();
+//!
+//! assert!(requirement.allows(&version!(1, 9, 0)));
+//! assert!(!requirement.allows(&version!(2, 0, 0)));
+//! # }
+//! ```
+//!
+//! The following operators are supported:
+//!
+//! | Requirement      | Equivalent           |
+//! |------------------|----------------------|
+//! | `1.2.3`, `^1.2.3`| `>=1.2.3, <2.0.0`    |
+//! | `^0.2.3`         | `>=0.2.3, <0.3.0`    |
+//! | `^0.0.3`         | `>=0.0.3, <0.0.4`    |
+//! | `~1.2.3`         | `>=1.2.3, <1.3.0`    |
+//! | `~1`             | `>=1.0.0, <2.0.0`    |
+//! | `1.2.*`          | `>=1.2.0, <1.3.0`    |
+//! | `*`              | any version number   |
+//! | `=1.2`           | `>=1.2.0, <1.3.0`    |
+//! | `>1.2`           | `>=1.3.0`            |
+//! | `<=1.2`          | `<1.3.0`             |
+//!
+//! Pre-release version numbers are only allowed if at least one of the
+//! comparators in the requirement is a pre-release of the same major, minor,
+//! and patch version numbers. For example, `>=1.2.3-alpha` allows
+//! `1.2.3-beta`, but not `1.2.4-beta`.
+use std::str::FromStr;
+use super::parser::{parse_partial, Partial};
+use super::resolve::{Constraint, Constraints, Operation};
+use super::version::Version;
+
+/// Represents a Cargo version requirement.
+pub struct Requirement {
+    /// The constraints that all must be satisfied.
+    pub constraints: Constraints,
+
+    /// The version numbers whose pre-releases are allowed.
+    ///
+    /// These are the comparators that had pre-release identifiers. Only the
+    /// major, minor, and patch version numbers are relevant.
+    pub prerelease: Vec<Version>,
+}
+
+impl Constraint for Requirement {
+    fn allows(&self, version: &Version) -> bool {
+        if !self.constraints.allows(version) {
+            return false;
+        }
+
+        version.pre.is_empty() ||
+        self.prerelease.iter().any(|p| {
+            p.major == version.major && p.minor == version.minor && p.patch == version.patch
+        })
+    }
+}
+
+/// Enables parsing of strings as Cargo version requirements.
+///
+/// ```
+/// # use recital::cargo::Requirement;
+/// let requirement: Requirement = ">= 1.2.0, < 1.5".parse().unwrap();
+/// ```
+impl FromStr for Requirement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Requirement, String> {
+        let mut operations = Vec::new();
+        let mut prerelease = Vec::new();
+
+        for comparator in s.split(',') {
+            let comparator = comparator.trim();
+
+            if comparator.is_empty() {
+                return Err(format!("Invalid requirement: {}", s));
+            }
+
+            let (op, rest) = split_op(comparator);
+            let partial = parse_partial(rest.trim_start())?;
+
+            if !partial.build.is_empty() || (op.is_some() && partial.wildcard) {
+                return Err(format!("Invalid requirement: {}", s));
+            }
+
+            if !partial.pre.is_empty() {
+                prerelease.push(partial.to_version());
+            }
+
+            let op = match op {
+                Some(op) => op,
+                None if partial.wildcard => "=",
+                None => "^",
+            };
+
+            expand(op, &partial, &mut operations);
+        }
+
+        let mut constraints: Vec<Box<dyn Constraint>> = Vec::new();
+
+        for operation in operations {
+            constraints.push(Box::new(operation));
+        }
+
+        Ok(Requirement {
+            constraints: Constraints::And(constraints),
+            prerelease,
+        })
+    }
+}
+
+/// Adds the operations for a single comparator.
+///
+/// A comparator that is only a wildcard (`*`) adds no operations, since it
+/// allows any version number.
+fn expand(op: &str, partial: &Partial, operations: &mut Vec<Operation>) {
+    use super::resolve::Operation::*;
+
+    if partial.major.is_none() {
+        return;
+    }
+
+    let version = partial.to_version();
+    let complete = partial.patch.is_some();
+    let next = partial.next_release();
+
+    match (op, next) {
+        ("=", _) if complete => operations.push(Exactly(version)),
+        ("=", next) => {
+            operations.push(GreaterThanOrEqualTo(version));
+            operations.extend(next.map(LessThan));
+        }
+
+        (">", _) if complete => operations.push(GreaterThan(version)),
+        (">", Some(next)) => operations.push(GreaterThanOrEqualTo(next)),
+        (">", None) => operations.push(GreaterThan(version!(u64::MAX, u64::MAX, u64::MAX))),
+        (">=", _) => operations.push(GreaterThanOrEqualTo(version)),
+        ("<", _) => operations.push(LessThan(version)),
+        ("<=", _) if complete => operations.push(LessThanOrEqualTo(version)),
+        ("<=", next) => operations.extend(next.map(LessThan)),
+
+        ("~", _) => {
+            let upper = match (partial.major, partial.minor) {
+                (Some(major), None) => version!(major, u64::MAX, u64::MAX).next_release(),
+                (Some(major), Some(minor)) => version!(major, minor, u64::MAX).next_release(),
+                _ => return,
+            };
+
+            operations.push(GreaterThanOrEqualTo(version));
+            operations.extend(upper.map(LessThan));
+        }
+
+        ("^", _) => {
+            let upper = match (partial.major, partial.minor, partial.patch) {
+                (Some(0), Some(0), Some(patch)) => version!(0, 0, patch).next_release(),
+                (Some(0), Some(minor), _) => version!(0, minor, u64::MAX).next_release(),
+                (Some(major), _, _) => version!(major, u64::MAX, u64::MAX).next_release(),
+                _ => return,
+            };
+
+            operations.push(GreaterThanOrEqualTo(version));
+            operations.extend(upper.map(LessThan));
+        }
+
+        _ => {}
+    }
+}
+
+/// Splits the operator from the beginning of a comparator.
+fn split_op(comparator: &str) -> (Option<&str>, &str) {
+    for op in &[">=", "<=", "=", ">", "<", "~", "^"] {
+        if let Some(rest) = comparator.strip_prefix(op) {
+            return (Some(op), rest);
+        }
+    }
+
+    (None, comparator)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+    use super::Requirement;
+    use super::super::resolve::Constraint;
+
+    #[test]
+    fn test_caret() {
+        check_allows!(Requirement::from_str("1.2.3"),
+                      ["1.2.3", "1.9.0", "1.2.3+build"],
+                      ["1.2.2", "2.0.0"]);
+        check_allows!(Requirement::from_str("^1.2.3"), ["1.2.3", "1.9.0"], ["1.2.2", "2.0.0"]);
+        check_allows!(Requirement::from_str("^1.2"), ["1.2.0", "1.9.9"], ["1.1.9", "2.0.0"]);
+        check_allows!(Requirement::from_str("^1"), ["1.0.0", "1.9.9"], ["0.9.9", "2.0.0"]);
+        check_allows!(Requirement::from_str("^0.2.3"), ["0.2.3", "0.2.9"], ["0.2.2", "0.3.0"]);
+        check_allows!(Requirement::from_str("^0.2"), ["0.2.0", "0.2.9"], ["0.1.9", "0.3.0"]);
+        check_allows!(Requirement::from_str("^0.0.3"), ["0.0.3"], ["0.0.2", "0.0.4"]);
+        check_allows!(Requirement::from_str("^0.0"), ["0.0.0", "0.0.9"], ["0.1.0"]);
+        check_allows!(Requirement::from_str("^0"), ["0.0.0", "0.9.9"], ["1.0.0"]);
+    }
+
+    #[test]
+    fn test_tilde() {
+        check_allows!(Requirement::from_str("~1.2.3"), ["1.2.3", "1.2.9"], ["1.2.2", "1.3.0"]);
+        check_allows!(Requirement::from_str("~1.2"), ["1.2.0", "1.2.9"], ["1.1.9", "1.3.0"]);
+        check_allows!(Requirement::from_str("~1"), ["1.0.0", "1.9.9"], ["0.9.9", "2.0.0"]);
+    }
+
+    #[test]
+    fn test_wildcard() {
+        check_allows!(Requirement::from_str("*"), ["0.0.0", "9.9.9"], ["1.0.0-alpha"]);
+        check_allows!(Requirement::from_str("1.*"), ["1.0.0", "1.9.9"], ["0.9.9", "2.0.0"]);
+        check_allows!(Requirement::from_str("1.2.*"), ["1.2.0", "1.2.9"], ["1.1.9", "1.3.0"]);
+        check_allows!(Requirement::from_str("1.2.x"), ["1.2.0", "1.2.9"], ["1.1.9", "1.3.0"]);
+    }
+
+    #[test]
+    fn test_comparison() {
+        check_allows!(Requirement::from_str("=1.2.3"), ["1.2.3"], ["1.2.4"]);
+        check_allows!(Requirement::from_str("=1.2"), ["1.2.0", "1.2.9"], ["1.3.0"]);
+        check_allows!(Requirement::from_str(">1.2.3"), ["1.2.4"], ["1.2.3"]);
+        check_allows!(Requirement::from_str(">1.2"), ["1.3.0"], ["1.2.9"]);
+        check_allows!(Requirement::from_str(">1"), ["2.0.0"], ["1.9.9"]);
+        check_allows!(Requirement::from_str(">=1.2"), ["1.2.0"], ["1.1.9"]);
+        check_allows!(Requirement::from_str("<1.2"), ["1.1.9"], ["1.2.0"]);
+        check_allows!(Requirement::from_str("<=1.2"), ["1.2.9"], ["1.3.0"]);
+        check_allows!(Requirement::from_str("<=1.2.3"), ["1.2.3"], ["1.2.4"]);
+    }
+
+    #[test]
+    fn test_multiple() {
+        check_allows!(Requirement::from_str(">= 1.2.0, < 1.5"),
+                      ["1.2.0", "1.4.9"],
+                      ["1.1.9", "1.5.0"]);
+    }
+
+    #[test]
+    fn test_prerelease() {
+        check_allows!(Requirement::from_str(">=1.2.3-alpha"),
+                      ["1.2.3-beta", "1.2.3", "1.3.0"],
+                      ["1.2.4-beta"]);
+        check_allows!(Requirement::from_str("^1.2.3-alpha.1"),
+                      ["1.2.3-alpha.2", "1.2.5"],
+                      ["1.2.3-alpha.0", "1.3.0-beta"]);
+        check_allows!(Requirement::from_str("^1.2.3"), [], ["1.2.4-alpha", "2.0.0-alpha"]);
+        check_allows!(Requirement::from_str("<2.0.0"), ["1.9.9"], ["2.0.0-alpha"]);
+        check_allows!(Requirement::from_str("=1.2.3-rc.1"), ["1.2.3-rc.1"], ["1.2.3-rc.2"]);
+    }
+
+    #[test]
+    fn test_largest() {
+        let max = "18446744073709551615";
+
+        check_allows!(Requirement::from_str(&format!("^{}", max)),
+                      [format!("{}.0.0", max), format!("{0}.{0}.{0}", max)],
+                      []);
+        check_allows!(Requirement::from_str(&format!("~1.{}", max)),
+                      [format!("1.{}.0", max)],
+                      ["2.0.0"]);
+        check_allows!(Requirement::from_str(&format!("^0.0.{}", max)),
+                      [format!("0.0.{}", max)],
+                      ["0.1.0"]);
+        check_allows!(Requirement::from_str(&format!("={}", max)),
+                      [format!("{}.1.0", max)],
+                      ["1.0.0"]);
+        check_allows!(Requirement::from_str(&format!(">{}", max)),
+                      [],
+                      [format!("{0}.{0}.{0}", max)]);
+        check_allows!(Requirement::from_str(&format!("<={}", max)),
+                      ["0.0.0", format!("{0}.{0}.{0}", max)],
+                      []);
+    }
+
+    #[test]
+    fn test_invalid() {
+        for req in &["", "1.2.3,", ">=1.*", "1.2.3+build", "!=1.2.3", "1.*.3", ">= 1.2 1.3",
+                     "01.2.3", "^1.2.3-rc.01"] {
+            assert!(req.parse::<Requirement>().is_err(), "{} should be invalid", req);
+        }
+    }
+
+}
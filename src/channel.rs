@@ -0,0 +1,396 @@
+//! Reads, compares, and advances the pre-release channels of version numbers.
+//!
+//! The **channel** module provides you with `Channels`, an ordered list of
+//! pre-release channels (such as `alpha`, `beta`, and `rc`), which is used to
+//! find out which channel a version number was released on, and how far along
+//! that channel it is.
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate recital;
+//! use recital::channel::Channels;
+//! use recital::resolve::Constraint;
+//!
+//! # fn main() {
+//! let channels = Channels::default();
+//! let version = "1.0.0-preview.3".parse().unwrap();
+//! let channel = channels.channel(&version).unwrap();
+//!
+//! assert_eq!(("preview", Some(3)), (&channel.name[..], channel.iteration));
+//!
+//! let next = channels.advance(&version).unwrap();
+//!
+//! assert_eq!("1.0.0-rc.1", next.to_string());
+//! assert_eq!("1.0.0", channels.advance(&next).unwrap().to_string());
+//!
+//! let beta_or_later = channels.at_least("beta").unwrap();
+//!
+//! assert!(beta_or_later.allows(&version));
+//! assert!(!beta_or_later.allows(&"1.0.0-alpha.9".parse().unwrap()));
+//! # }
+//! ```
+//!
+//! Since channels are compared by their names, `1.0.0-dev` comes after
+//! `1.0.0-alpha` when version numbers are compared. `Channels::compare` orders
+//! them by their channels instead.
+use std::cmp::Ordering;
+use super::resolve::Constraint;
+use super::version::{Identifier, Version};
+
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+
+/// Represents the channel that a pre-release was released on.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Channel {
+    /// The name of the channel, as it was configured.
+    pub name: String,
+
+    /// The position of the channel, from the least to the most stable.
+    pub rank: usize,
+
+    /// The iteration of the pre-release on the channel (the `3` in `beta.3`
+    /// or `beta3`), if there is one.
+    pub iteration: Option<u64>,
+}
+
+/// Represents the pre-release channels, from the least to the most stable.
+///
+/// The names of channels are matched against the first pre-release
+/// identifier of a version number, ignoring case. The iteration on the
+/// channel is either the identifier that follows (`rc.2`), or the digits
+/// that the first identifier ends with (`rc2`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Channels {
+    names: Vec<String>,
+}
+
+/// The default channels are `dev`, `alpha`, `beta`, `preview`, and `rc`.
+impl Default for Channels {
+    fn default() -> Channels {
+        Channels::new(vec!["dev", "alpha", "beta", "preview", "rc"])
+    }
+}
+
+impl Channels {
+    /// Creates a list of channels, from the least to the most stable.
+    pub fn new<I, S>(names: I) -> Channels
+        where I: IntoIterator<Item = S>,
+              S: Into<String>
+    {
+        Channels { names: names.into_iter().map(|name| name.into()).collect() }
+    }
+
+    /// Returns the names of the channels, from the least to the most stable.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns the position of a channel by its name, ignoring case.
+    pub fn rank(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the channel that a version number was released on.
+    ///
+    /// There is no channel for a release, or for a pre-release whose first
+    /// identifier is not the name of a channel.
+    pub fn channel(&self, version: &Version) -> Option<Channel> {
+        let label = match version.pre.first() {
+            Some(Identifier::Alpha(ref label)) => label,
+            _ => return None,
+        };
+
+        let (rank, iteration) = match self.rank(label) {
+            Some(rank) => {
+                let iteration = match version.pre.get(1) {
+                    Some(Identifier::Number(n)) => Some(*n),
+                    _ => None,
+                };
+
+                (rank, iteration)
+            }
+            None => {
+                let name = label.trim_end_matches(|c: char| c.is_ascii_digit());
+
+                (self.rank(name)?, label[name.len()..].parse().ok())
+            }
+        };
+
+        Some(Channel {
+            name: self.names[rank].clone(),
+            rank,
+            iteration,
+        })
+    }
+
+    /// Compares two version numbers by their channels.
+    ///
+    /// Version numbers are compared as usual, except that pre-releases of the
+    /// same release are ordered by their channel, and then by their iteration
+    /// on it. Pre-releases that are not on a channel come before those that
+    /// are, and are compared as usual among themselves.
+    ///
+    /// ```
+    /// use recital::channel::Channels;
+    /// use recital::version::Version;
+    ///
+    /// let channels = Channels::default();
+    /// let mut versions: Vec<Version> = ["1.0.0-rc.1", "1.0.0-alpha.2", "1.0.0-dev.7"]
+    ///     .iter()
+    ///     .map(|v| v.parse().unwrap())
+    ///     .collect();
+    ///
+    /// versions.sort_by(|a, b| channels.compare(a, b));
+    ///
+    /// assert_eq!("1.0.0-dev.7", versions[0].to_string());
+    /// ```
+    pub fn compare(&self, a: &Version, b: &Version) -> Ordering {
+        let numbers = (a.major, a.minor, a.patch).cmp(&(b.major, b.minor, b.patch));
+
+        if numbers != Ordering::Equal || a.pre.is_empty() || b.pre.is_empty() {
+            return a.cmp(b);
+        }
+
+        let key = |version: &Version| {
+            self.channel(version).map(|channel| (channel.rank, channel.iteration))
+        };
+
+        key(a).cmp(&key(b)).then_with(|| a.cmp(b))
+    }
+
+    /// Returns the first pre-release on the next channel, or the release once
+    /// the last channel has been reached.
+    ///
+    /// There is nothing to advance to for a release, or for a pre-release that
+    /// is not on a channel. Neither is there when the next channel would come
+    /// first when version numbers are compared, such as `1.0.0-alpha.1` after
+    /// `1.0.0-dev.5`, since it would not be picked as the latest version.
+    pub fn advance(&self, version: &Version) -> Option<Version> {
+        let channel = self.channel(version)?;
+        let mut next = Version {
+            major: version.major,
+            minor: version.minor,
+            patch: version.patch,
+            ..Default::default()
+        };
+
+        if let Some(name) = self.names.get(channel.rank + 1) {
+            next.pre = vec![Identifier::Alpha(name.clone()), Identifier::Number(1)];
+        }
+
+        Some(next).filter(|next| next > version)
+    }
+
+    /// Returns the next pre-release on the same channel.
+    ///
+    /// The iteration is written the same way as in the version number, so
+    /// `1.0.0-rc2` is followed by `1.0.0-rc3`, and `1.0.0-rc.2` by
+    /// `1.0.0-rc.3`. A pre-release without an iteration is the same as its
+    /// first iteration, so `1.0.0-beta` is followed by `1.0.0-beta.2`.
+    ///
+    /// There is no next pre-release for a release, or for a pre-release that
+    /// is not on a channel. Neither is there when it would come first when
+    /// version numbers are compared, such as `1.0.0-rc10` after `1.0.0-rc9`.
+    pub fn next_iteration(&self, version: &Version) -> Option<Version> {
+        let channel = self.channel(version)?;
+        let iteration = channel.iteration.unwrap_or(1).checked_add(1)?;
+        let label = match version.pre[0] {
+            Identifier::Alpha(ref label) => label,
+            Identifier::Number(_) => return None,
+        };
+
+        let pre = if self.rank(label).is_some() {
+            vec![Identifier::Alpha(label.clone()), Identifier::Number(iteration)]
+        } else {
+            let name = label.trim_end_matches(|c: char| c.is_ascii_digit());
+
+            vec![Identifier::Alpha(format!("{}{}", name, iteration))]
+        };
+
+        let next = Version {
+            major: version.major,
+            minor: version.minor,
+            patch: version.patch,
+            pre,
+            build: Vec::new(),
+        };
+
+        Some(next).filter(|next| next > version)
+    }
+
+    /// Creates a constraint that allows pre-releases on the given channel, or
+    /// on a more stable one, and every release.
+    pub fn at_least(&self, name: &str) -> Result<AtLeast, String> {
+        match self.rank(name) {
+            Some(minimum) => {
+                Ok(AtLeast {
+                    channels: self.clone(),
+                    minimum,
+                })
+            }
+            None => Err(format!("Unknown channel: {}", name)),
+        }
+    }
+}
+
+/// Represents a constraint on the channel of a version number.
+///
+/// Pre-releases that are not on a channel are never allowed.
+#[derive(Clone, Debug)]
+pub struct AtLeast {
+    channels: Channels,
+    minimum: usize,
+}
+
+impl Constraint for AtLeast {
+    fn allows(&self, version: &Version) -> bool {
+        if version.pre.is_empty() {
+            return true;
+        }
+
+        match self.channels.channel(version) {
+            Some(channel) => channel.rank >= self.minimum,
+            None => false,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use std::cmp::Ordering;
+    use super::{Channel, Channels};
+    use super::super::resolve::Constraint;
+    use super::super::resolve::Constraints::And;
+    use super::super::resolve::Operation::LessThan;
+    use super::super::version::Version;
+
+    #[test]
+    fn test_channel() {
+        let channels = Channels::default();
+        let channel = |s: &str| {
+            channels.channel(&s.parse().unwrap()).map(|c| (c.name, c.rank, c.iteration))
+        };
+
+        assert_eq!(Some(("dev".to_string(), 0, None)), channel("1.0.0-dev"));
+        assert_eq!(Some(("beta".to_string(), 2, Some(3))), channel("1.0.0-beta.3"));
+        assert_eq!(Some(("rc".to_string(), 4, Some(2))), channel("1.0.0-RC2"));
+        assert_eq!(Some(("preview".to_string(), 3, None)), channel("1.0.0-preview.x.1"));
+        assert_eq!(None, channel("1.0.0"));
+        assert_eq!(None, channel("1.0.0-nightly.1"));
+        assert_eq!(None, channel("1.0.0-1"));
+    }
+
+    #[test]
+    fn test_custom() {
+        let channels = Channels::new(vec!["canary", "stable"]);
+
+        assert_eq!(Some(Channel {
+                       name: "canary".to_string(),
+                       rank: 0,
+                       iteration: Some(4),
+                   }),
+                   channels.channel(&"2.0.0-Canary.4".parse().unwrap()));
+        assert_eq!(None, channels.channel(&"2.0.0-beta.1".parse().unwrap()));
+        assert_eq!(&["canary".to_string(), "stable".to_string()], channels.names());
+    }
+
+    #[test]
+    fn test_compare() {
+        let channels = Channels::default();
+        let ordered = ["0.9.0",
+                       "1.0.0-nightly",
+                       "1.0.0-dev",
+                       "1.0.0-dev.2",
+                       "1.0.0-alpha.1",
+                       "1.0.0-beta",
+                       "1.0.0-beta.2",
+                       "1.0.0-beta.10",
+                       "1.0.0-preview.1",
+                       "1.0.0-rc.1",
+                       "1.0.0",
+                       "1.0.1-dev"];
+
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(i.cmp(&j),
+                           channels.compare(&a.parse().unwrap(), &b.parse().unwrap()),
+                           "{} <=> {}",
+                           a,
+                           b);
+            }
+        }
+
+        assert_eq!(Ordering::Equal,
+                   channels.compare(&"1.0.0-rc.1".parse().unwrap(),
+                                    &"1.0.0-rc.1+b".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_advance() {
+        let channels = Channels::default();
+        let advance = |s: &str| channels.advance(&s.parse().unwrap()).map(|v| v.to_string());
+
+        assert_eq!(Some("1.0.0-beta.1".to_string()), advance("1.0.0-alpha.2+build"));
+        assert_eq!(Some("1.0.0-rc.1".to_string()), advance("1.0.0-preview3"));
+        assert_eq!(Some("1.0.0".to_string()), advance("1.0.0-rc.2"));
+        assert_eq!(None, advance("1.0.0-dev.5"));
+        assert_eq!(None, advance("1.0.0"));
+        assert_eq!(None, advance("1.0.0-nightly"));
+
+        for s in &["1.0.0-dev", "1.0.0-alpha", "1.0.0-beta.9", "1.0.0-preview3", "1.0.0-RC.1"] {
+            let version: Version = s.parse().unwrap();
+
+            if let Some(next) = channels.advance(&version) {
+                assert!(next > version, "{} > {}", next, version);
+            }
+        }
+    }
+
+    #[test]
+    fn test_next_iteration() {
+        let channels = Channels::default();
+        let next = |s: &str| {
+            channels.next_iteration(&s.parse().unwrap()).map(|v| v.to_string())
+        };
+
+        assert_eq!(Some("1.0.0-beta.4".to_string()), next("1.0.0-beta.3"));
+        assert_eq!(Some("1.0.0-beta.2".to_string()), next("1.0.0-beta"));
+        assert_eq!(Some("1.0.0-rc.3".to_string()), next("1.0.0-rc.2"));
+        assert_eq!(Some("1.0.0-rc3".to_string()), next("1.0.0-rc2"));
+        assert_eq!(Some("1.0.0-RC3".to_string()), next("1.0.0-RC2"));
+        assert_eq!(None, next("1.0.0-rc9"));
+        assert_eq!(None, next("1.0.0-beta.x"));
+        assert_eq!(None, next("1.0.0"));
+        assert_eq!(None, next("1.0.0-nightly.1"));
+
+        for s in &["1.0.0-dev", "1.0.0-alpha.1", "1.0.0-beta.3.x", "1.0.0-preview8",
+                   "1.0.0-RC.1"] {
+            let version: Version = s.parse().unwrap();
+            let next = channels.next_iteration(&version).unwrap();
+
+            assert!(next > version, "{} > {}", next, version);
+        }
+    }
+
+    #[test]
+    fn test_at_least() {
+        let channels = Channels::default();
+        let beta = channels.at_least("Beta").unwrap();
+
+        assert!(beta.allows(&"1.0.0-beta.1".parse().unwrap()));
+        assert!(beta.allows(&"1.0.0-rc.1".parse().unwrap()));
+        assert!(beta.allows(&version!(1, 0, 0)));
+        assert!(!beta.allows(&"1.0.0-alpha.3".parse().unwrap()));
+        assert!(!beta.allows(&"1.0.0-nightly".parse().unwrap()));
+        assert!(channels.at_least("gamma").is_err());
+
+        let constraints = And(vec![Box::new(beta),
+                                   Box::new(LessThan("2.0.0-0".parse().unwrap()))]);
+
+        assert!(constraints.allows(&"1.5.0-preview.1".parse().unwrap()));
+        assert!(!constraints.allows(&"2.0.0-rc.1".parse().unwrap()));
+    }
+
+}
@@ -0,0 +1,344 @@
+//! Creates, parses, and compares semantic version numbers in less memory.
+//!
+//! The **compact** module provides you with a `CompactVersion`, which holds
+//! the same version number as a `Version` in far less memory. It is meant for
+//! keeping long lists of version numbers around, such as every release of
+//! every package in a registry.
+//!
+//! ```
+//! use recital::compact::CompactVersion;
+//! use recital::version::Version;
+//!
+//! let a: CompactVersion = "1.2.3".parse().unwrap();
+//! let b: CompactVersion = "1.2.4-rc.1+build.7".parse().unwrap();
+//!
+//! assert!(a.is_packed());
+//! assert!(!b.is_packed());
+//! assert!(a < b);
+//! assert_eq!((1, 2, 4), (b.major(), b.minor(), b.patch()));
+//! assert_eq!("1.2.4-rc.1+build.7", b.to_string());
+//! assert_eq!("1.2.4-rc.1+build.7".parse::<Version>().unwrap(), b.to_version());
+//! ```
+//!
+//! Most version numbers are releases with small numbers and no identifiers.
+//! Those are packed into a single `u64`, and take no more memory than the
+//! `CompactVersion` itself, which is 16 bytes. Any other version number is
+//! kept in a single heap allocation, with all of its identifiers stored as
+//! one string, instead of a `Version`'s 72 bytes and one allocation for each
+//! list of identifiers and each alphanumeric identifier.
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use super::borrowed::VersionRef;
+use super::scheme::{Bump, VersionScheme};
+use super::version::{join, Version};
+
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+
+/// The number of bits used for each number in a packed version number.
+const BITS: u32 = 21;
+
+/// The largest number that can be packed.
+const MAX_PACKED: u64 = (1 << BITS) - 1;
+
+/// Represents a version number with pre-release or build identifiers, or
+/// with numbers that are too large to be packed.
+#[derive(Clone, Debug)]
+struct Extended {
+    major: u64,
+    minor: u64,
+    patch: u64,
+
+    /// The pre-release identifiers followed by the build identifiers.
+    identifiers: Box<str>,
+
+    /// The length of the pre-release identifiers in `identifiers`.
+    pre_len: usize,
+}
+
+#[derive(Clone, Debug)]
+enum Repr {
+    /// The major, minor, and patch version numbers, from the most to the
+    /// least significant bits, so that packed version numbers are ordered
+    /// the same as the numbers they hold.
+    Packed(u64),
+
+    /// The whole version number, boxed, which is used when any of the
+    /// numbers needs more than `BITS` (21) bits, or when there are any
+    /// pre-release or build identifiers.
+    Extended(Box<Extended>),
+}
+
+/// Represents a semantic version number in as little memory as possible.
+///
+/// A `CompactVersion` is ordered, displayed, and parsed in the same way as a
+/// `Version`. Since its numbers may be packed together, they are accessed
+/// using the `major`, `minor`, and `patch` methods.
+#[derive(Clone, Debug)]
+pub struct CompactVersion {
+    repr: Repr,
+}
+
+impl CompactVersion {
+    /// Creates a version number without any identifiers.
+    pub fn new(major: u64, minor: u64, patch: u64) -> CompactVersion {
+        CompactVersion::with_identifiers(major, minor, patch, "", "")
+    }
+
+    /// Creates a version number from its parts.
+    ///
+    /// The version number is packed if it has no identifiers and its numbers
+    /// are small enough.
+    fn with_identifiers(major: u64, minor: u64, patch: u64, pre: &str, build: &str)
+                        -> CompactVersion {
+        let repr = if pre.is_empty() && build.is_empty() && major <= MAX_PACKED &&
+                      minor <= MAX_PACKED && patch <= MAX_PACKED {
+            Repr::Packed(major << (2 * BITS) | minor << BITS | patch)
+        } else {
+            Repr::Extended(Box::new(Extended {
+                major,
+                minor,
+                patch,
+                identifiers: format!("{}{}", pre, build).into_boxed_str(),
+                pre_len: pre.len(),
+            }))
+        };
+
+        CompactVersion { repr }
+    }
+
+    /// Returns the major version number.
+    pub fn major(&self) -> u64 {
+        match self.repr {
+            Repr::Packed(packed) => packed >> (2 * BITS),
+            Repr::Extended(ref extended) => extended.major,
+        }
+    }
+
+    /// Returns the minor version number.
+    pub fn minor(&self) -> u64 {
+        match self.repr {
+            Repr::Packed(packed) => packed >> BITS & MAX_PACKED,
+            Repr::Extended(ref extended) => extended.minor,
+        }
+    }
+
+    /// Returns the patch version number.
+    pub fn patch(&self) -> u64 {
+        match self.repr {
+            Repr::Packed(packed) => packed & MAX_PACKED,
+            Repr::Extended(ref extended) => extended.patch,
+        }
+    }
+
+    /// Checks if the version number is packed into a single number.
+    pub fn is_packed(&self) -> bool {
+        matches!(self.repr, Repr::Packed(_))
+    }
+
+    /// Checks if the version number is stable, in the same way as `Version`.
+    pub fn is_stable(&self) -> bool {
+        self.major() != 0 && self.to_ref().pre.is_empty()
+    }
+
+    /// Borrows the version number as a `VersionRef`.
+    pub fn to_ref(&self) -> VersionRef<'_> {
+        match self.repr {
+            Repr::Packed(_) => VersionRef {
+                major: self.major(),
+                minor: self.minor(),
+                patch: self.patch(),
+                pre: "",
+                build: "",
+            },
+            Repr::Extended(ref extended) => VersionRef {
+                major: extended.major,
+                minor: extended.minor,
+                patch: extended.patch,
+                pre: &extended.identifiers[..extended.pre_len],
+                build: &extended.identifiers[extended.pre_len..],
+            },
+        }
+    }
+
+    /// Creates a `Version` with the same version number.
+    pub fn to_version(&self) -> Version {
+        self.to_ref().to_owned()
+    }
+}
+
+impl Display for CompactVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.to_ref().fmt(f)
+    }
+}
+
+impl FromStr for CompactVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<CompactVersion, String> {
+        VersionRef::parse(s).map(CompactVersion::from)
+    }
+}
+
+impl<'a> From<&'a Version> for CompactVersion {
+    fn from(version: &'a Version) -> CompactVersion {
+        CompactVersion::with_identifiers(version.major,
+                                         version.minor,
+                                         version.patch,
+                                         &join(&version.pre),
+                                         &join(&version.build))
+    }
+}
+
+impl From<Version> for CompactVersion {
+    fn from(version: Version) -> CompactVersion {
+        CompactVersion::from(&version)
+    }
+}
+
+impl<'a> From<VersionRef<'a>> for CompactVersion {
+    fn from(version: VersionRef<'a>) -> CompactVersion {
+        CompactVersion::with_identifiers(version.major,
+                                         version.minor,
+                                         version.patch,
+                                         version.pre,
+                                         version.build)
+    }
+}
+
+impl From<CompactVersion> for Version {
+    fn from(version: CompactVersion) -> Version {
+        version.to_version()
+    }
+}
+
+impl Eq for CompactVersion {}
+
+/// Build identifiers are ignored, and a pre-release comes before its release.
+impl Ord for CompactVersion {
+    fn cmp(&self, other: &CompactVersion) -> Ordering {
+        match (&self.repr, &other.repr) {
+            (Repr::Packed(a), Repr::Packed(b)) => a.cmp(b),
+            _ => self.to_ref().cmp(&other.to_ref()),
+        }
+    }
+}
+
+impl PartialEq for CompactVersion {
+    fn eq(&self, other: &CompactVersion) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for CompactVersion {
+    fn partial_cmp(&self, other: &CompactVersion) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl VersionScheme for CompactVersion {
+    fn bump(&mut self, level: Bump) {
+        let (major, minor, patch) = (self.major(), self.minor(), self.patch());
+
+        *self = match level {
+            Bump::Major => CompactVersion::new(major + 1, 0, 0),
+            Bump::Minor => CompactVersion::new(major, minor + 1, 0),
+            Bump::Patch => CompactVersion::new(major, minor, patch + 1),
+        };
+    }
+
+    fn is_stable(&self) -> bool {
+        CompactVersion::is_stable(self)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use std::mem::size_of;
+    use super::{CompactVersion, MAX_PACKED};
+    use super::super::scheme::{Bump, VersionScheme};
+    use super::super::version::Version;
+
+    #[test]
+    fn test_size() {
+        assert_eq!(16, size_of::<CompactVersion>());
+    }
+
+    #[test]
+    fn test_packed() {
+        let version = CompactVersion::new(MAX_PACKED, 1, MAX_PACKED);
+
+        assert!(version.is_packed());
+        assert_eq!((MAX_PACKED, 1, MAX_PACKED),
+                   (version.major(), version.minor(), version.patch()));
+        assert!(!CompactVersion::new(MAX_PACKED + 1, 0, 0).is_packed());
+        assert!(!"1.0.0+build".parse::<CompactVersion>().unwrap().is_packed());
+        assert_eq!("20240115.0.0".parse::<CompactVersion>().unwrap().major(), 20240115);
+    }
+
+    #[test]
+    fn test_same_as_version() {
+        let inputs = ["0.0.0",
+                      "1.2.3",
+                      "1.2.3-rc.1",
+                      "1.2.3+build.7",
+                      "1.2.3-alpha+build",
+                      "18446744073709551615.0.0"];
+
+        for input in &inputs {
+            let version: Version = input.parse().unwrap();
+            let compact = input.parse::<CompactVersion>().unwrap();
+
+            assert_eq!(version.to_string(), compact.to_string());
+            assert_eq!(version, compact.to_version());
+            assert_eq!(compact, CompactVersion::from(&version));
+            assert_eq!(version.build, compact.to_version().build);
+        }
+
+        assert!("1.2".parse::<CompactVersion>().is_err());
+        assert!("01.02.03".parse::<CompactVersion>().is_err());
+        assert!("1.2.3-rc.01".parse::<CompactVersion>().is_err());
+    }
+
+    #[test]
+    fn test_ordering() {
+        let ordered = ["0.0.1",
+                       "0.1.0",
+                       "1.0.0-alpha",
+                       "1.0.0-alpha.1",
+                       "1.0.0-beta.2",
+                       "1.0.0-beta.11",
+                       "1.0.0",
+                       "1.0.1",
+                       "1.2097151.0",
+                       "1.2097152.0",
+                       "2.0.0"];
+
+        let versions: Vec<CompactVersion> = ordered.iter().map(|s| s.parse().unwrap()).collect();
+
+        for pair in versions.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+
+        assert_eq!("1.0.0".parse::<CompactVersion>().unwrap(),
+                   "1.0.0+build".parse().unwrap());
+    }
+
+    #[test]
+    fn test_scheme() {
+        let mut version: CompactVersion = "1.2.3-rc.1+build".parse().unwrap();
+
+        assert!(!version.is_stable());
+
+        version.bump(Bump::Minor);
+
+        assert!(version.is_packed());
+        assert!(version.is_stable());
+        assert_eq!("1.3.0", version.to_string());
+        assert!(!"0.1.0".parse::<CompactVersion>().unwrap().is_stable());
+    }
+
+}
@@ -0,0 +1,474 @@
+//! Parses and evaluates version constraints the way Composer does.
+//!
+//! The **composer** module provides you with a `Requirement`, which is created
+//! from the constraint strings found in `composer.json` files. The strings are
+//! converted into a tree of `Operation` constraints with the same meaning that
+//! Composer gives them, while stability flags (`@dev`, `@beta`, etc.) are
+//! turned into a minimum `Stability` for pre-release version numbers.
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate recital;
+//! use recital::composer::Requirement;
+//! use recital::resolve::Constraint;
+//!
+//! # fn main() {
+//! let requirement: Requirement = "^1.2 || 2.0.*@beta".parse().unwrap();
+//!
+//! assert!(requirement.allows(&version!(1, 9, 0)));
+//! assert!(requirement.allows(&version!(2, 0, 1, vec![id!("beta1")])));
+//! assert!(!requirement.allows(&version!(2, 0, 1, vec![id!("alpha1")])));
+//! # }
+//! ```
+//!
+//! The following syntax is supported:
+//!
+//! | Constraint          | Equivalent                |
+//! |---------------------|---------------------------|
+//! | `1.0.2`, `1.0`      | `==1.0.2`, `==1.0.0`      |
+//! | `>=1.0 <2.0`        | `>=1.0.0-0, <2.0.0-0`     |
+//! | `>=1.0,<2.0`        | `>=1.0.0-0, <2.0.0-0`     |
+//! | `1.0 - 2.0`         | `>=1.0.0-0, <2.1.0-0`     |
+//! | `1.0.0 - 2.1.0`     | `>=1.0.0-0, <=2.1.0`      |
+//! | `1.0.*`             | `>=1.0.0-0, <1.1.0-0`     |
+//! | `~1.2`              | `>=1.2.0-0, <2.0.0-0`     |
+//! | `~1.2.3`            | `>=1.2.3-0, <1.3.0-0`     |
+//! | `^1.2.3`            | `>=1.2.3-0, <2.0.0-0`     |
+//! | `^0.3`              | `>=0.3.0-0, <0.4.0-0`     |
+//! | `1.0.x-dev`         | `1.0.*@dev`               |
+//! | `a \|\| b`, `a \| b`| either `a` or `b`         |
+//!
+//! Like Composer, the lower bounds include the pre-releases of the version
+//! number, and exclusive upper bounds exclude them. Whether a pre-release is
+//! actually allowed depends on its stability, which must be at least the
+//! minimum stability of the requirement. The minimum stability is the least
+//! stable of the one given to `Requirement::parse` (`stable` by default), any
+//! stability flags, and any pre-release version numbers in the constraint.
+use std::cmp;
+use std::str::FromStr;
+use super::parser::{parse_partial, Partial};
+use super::resolve::{Constraint, Constraints, Operation};
+use super::resolve::Operation::*;
+use super::version::{next_floor, Identifier, Version};
+
+/// Represents the stability of a version number.
+///
+/// The levels are ordered from the least to the most stable.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Stability {
+    /// A development version (`-dev`).
+    Dev,
+
+    /// An alpha release (`-alpha`, `-a`).
+    Alpha,
+
+    /// A beta release (`-beta`, `-b`).
+    Beta,
+
+    /// A release candidate (`-RC`).
+    RC,
+
+    /// A stable release, which includes patch releases (`-patch`, `-pl`).
+    Stable,
+}
+
+impl Stability {
+    /// Returns the stability of a version number.
+    ///
+    /// The stability is taken from the first pre-release identifier, ignoring
+    /// case and any trailing digits (`beta2`). A version number without any
+    /// pre-release identifiers is stable. Unrecognized identifiers are treated
+    /// as development versions.
+    pub fn of(version: &Version) -> Stability {
+        let label = match version.pre.first() {
+            None => return Stability::Stable,
+            Some(Identifier::Number(_)) => return Stability::Dev,
+            Some(Identifier::Alpha(label)) => label.to_lowercase(),
+        };
+
+        match label.trim_end_matches(|c: char| c.is_ascii_digit()) {
+            "alpha" | "a" => Stability::Alpha,
+            "beta" | "b" => Stability::Beta,
+            "rc" => Stability::RC,
+            "patch" | "pl" | "p" => Stability::Stable,
+            _ => Stability::Dev,
+        }
+    }
+}
+
+/// Enables parsing of stability flags (`dev`, `alpha`, `beta`, `RC`, `stable`).
+impl FromStr for Stability {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Stability, String> {
+        match s.to_lowercase().as_str() {
+            "dev" => Ok(Stability::Dev),
+            "alpha" => Ok(Stability::Alpha),
+            "beta" => Ok(Stability::Beta),
+            "rc" => Ok(Stability::RC),
+            "stable" => Ok(Stability::Stable),
+            _ => Err(format!("Invalid stability: {}", s)),
+        }
+    }
+}
+
+/// Represents a Composer version constraint.
+pub struct Requirement {
+    /// The constraints that must be satisfied.
+    pub constraints: Constraints,
+
+    /// The minimum stability of the allowed version numbers.
+    pub stability: Stability,
+}
+
+impl Constraint for Requirement {
+    fn allows(&self, version: &Version) -> bool {
+        Stability::of(version) >= self.stability && self.constraints.allows(version)
+    }
+}
+
+/// Enables parsing of strings as Composer version constraints.
+///
+/// This is the same as calling `Requirement::parse` with a minimum stability
+/// of `Stability::Stable`.
+///
+/// ```
+/// # use recital::composer::Requirement;
+/// let requirement: Requirement = ">=1.0 <1.1 || >=1.2".parse().unwrap();
+/// ```
+impl FromStr for Requirement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Requirement, String> {
+        Requirement::parse(s, Stability::Stable)
+    }
+}
+
+impl Requirement {
+    /// Parses a string as a Composer version constraint.
+    ///
+    /// The `minimum` stability is the one that applies when the constraint
+    /// does not ask for a less stable one, like the `minimum-stability`
+    /// setting of a `composer.json` file.
+    pub fn parse(s: &str, minimum: Stability) -> Result<Requirement, String> {
+        let mut stability = minimum;
+        let mut sets: Vec<Box<dyn Constraint>> = Vec::new();
+        let normalized = s.replace("||", "|");
+
+        for set in normalized.split('|') {
+            let operations = parse_set(set, &mut stability)
+                .map_err(|_| format!("Invalid constraint: {}", s))?;
+
+            let mut constraints: Vec<Box<dyn Constraint>> = Vec::new();
+
+            for operation in operations {
+                constraints.push(Box::new(operation));
+            }
+
+            sets.push(Box::new(Constraints::And(constraints)));
+        }
+
+        Ok(Requirement {
+            constraints: Constraints::Or(sets),
+            stability,
+        })
+    }
+}
+
+/// Creates a lower bound that includes the pre-releases of a version number.
+fn lower(mut version: Version) -> Version {
+    version.build.clear();
+
+    if version.pre.is_empty() {
+        version.pre.push(id!(0));
+    }
+
+    version
+}
+
+/// Parses a version number, returning it and the stability it implies.
+///
+/// An optional `v` prefix and stability flag (`@beta`) are supported, as are
+/// branch aliases such as `1.0.x-dev`.
+fn parse_version(s: &str, stability: &mut Stability) -> Result<Partial, String> {
+    let mut s = s.trim_start_matches('v');
+
+    if let Some(at) = s.find('@') {
+        *stability = cmp::min(*stability, s[at + 1..].parse()?);
+        s = &s[..at];
+    }
+
+    if s.is_empty() {
+        return parse_partial("*");
+    }
+
+    if s.starts_with("dev-") {
+        return Err(format!("Branch constraints are not supported: {}", s));
+    }
+
+    let partial = match s.strip_suffix("-dev") {
+        Some(branch) if branch.ends_with(".x") || branch.ends_with(".*") => {
+            *stability = Stability::Dev;
+
+            parse_partial(branch)?
+        }
+
+        _ => parse_partial(s)?,
+    };
+
+    if !partial.pre.is_empty() {
+        *stability = cmp::min(*stability, Stability::of(&partial.to_version()));
+    }
+
+    Ok(partial)
+}
+
+/// Parses one side of a `||` as a list of operations.
+fn parse_set(s: &str, stability: &mut Stability) -> Result<Vec<Operation>, String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut pending = String::new();
+
+    for token in s.split(|c: char| c == ',' || c.is_whitespace()).filter(|t| !t.is_empty()) {
+        pending.push_str(token);
+
+        if !["<", ">", "<=", ">=", "=", "==", "!=", "<>", "~", "^"].contains(&token) {
+            tokens.push(pending.clone());
+            pending.clear();
+        }
+    }
+
+    if !pending.is_empty() || tokens.is_empty() {
+        return Err(format!("Invalid constraint: {}", s));
+    }
+
+    let mut operations = Vec::new();
+
+    if tokens.len() == 3 && tokens[1] == "-" {
+        let from = parse_version(&tokens[0], stability)?;
+        let to = parse_version(&tokens[2], stability)?;
+
+        operations.push(GreaterThanOrEqualTo(lower(from.to_version())));
+
+        match to.next_floor() {
+            Some(upper) => operations.push(LessThan(upper)),
+            None if to.major.is_some() && to.patch.is_none() => {}
+            None => operations.push(LessThanOrEqualTo(to.to_version())),
+        }
+
+        return Ok(operations);
+    }
+
+    for token in &tokens {
+        let (op, rest) = split_op(token);
+        let partial = parse_version(rest, stability)?;
+
+        if partial.wildcard && !op.is_empty() {
+            return Err(format!("Invalid constraint: {}", s));
+        }
+
+        expand(op, &partial, &mut operations);
+    }
+
+    Ok(operations)
+}
+
+/// Adds the operations for a single constraint.
+fn expand(op: &str, partial: &Partial, operations: &mut Vec<Operation>) {
+    let (major, minor, patch) = match partial.major {
+        Some(major) => (major, partial.minor, partial.patch),
+        None => return,
+    };
+
+    let version = partial.to_version();
+
+    match op {
+        "" if partial.wildcard => {
+            operations.push(GreaterThanOrEqualTo(lower(version)));
+
+            if let Some(upper) = partial.next_floor() {
+                operations.push(LessThan(upper));
+            }
+        }
+
+        "" | "=" | "==" => operations.push(Exactly(version)),
+        "!=" | "<>" => operations.push(ExactlyNot(version)),
+        ">" => operations.push(GreaterThan(version)),
+        ">=" => operations.push(GreaterThanOrEqualTo(lower(version))),
+        "<" => operations.push(LessThan(lower(version))),
+        "<=" => operations.push(LessThanOrEqualTo(version)),
+
+        "~" => {
+            let upper = match (minor, patch) {
+                (Some(minor), Some(_)) => next_floor(major, minor, u64::MAX),
+                _ => next_floor(major, u64::MAX, u64::MAX),
+            };
+
+            operations.push(GreaterThanOrEqualTo(lower(version)));
+            operations.extend(upper.map(LessThan));
+        }
+
+        _ => {
+            let upper = match (major, minor, patch) {
+                (0, Some(0), Some(patch)) => next_floor(0, 0, patch),
+                (0, Some(minor), _) => next_floor(0, minor, u64::MAX),
+                (major, _, _) => next_floor(major, u64::MAX, u64::MAX),
+            };
+
+            operations.push(GreaterThanOrEqualTo(lower(version)));
+            operations.extend(upper.map(LessThan));
+        }
+    }
+}
+
+/// Splits the operator from the beginning of a constraint.
+fn split_op(constraint: &str) -> (&str, &str) {
+    for op in &[">=", "<=", "==", "!=", "<>", ">", "<", "=", "~", "^"] {
+        if let Some(rest) = constraint.strip_prefix(op) {
+            return (op, rest);
+        }
+    }
+
+    ("", constraint)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Requirement, Stability};
+    use super::super::resolve::Constraint;
+
+    #[test]
+    fn test_stability_of() {
+        assert_eq!(Stability::Stable, Stability::of(&version!(1, 0, 0)));
+        assert_eq!(Stability::Dev, Stability::of(&version!(1, 0, 0, vec![id!("dev")])));
+        assert_eq!(Stability::Alpha, Stability::of(&version!(1, 0, 0, vec![id!("alpha2")])));
+        assert_eq!(Stability::Beta, Stability::of(&version!(1, 0, 0, vec![id!("b"), id!(1)])));
+        assert_eq!(Stability::RC, Stability::of(&version!(1, 0, 0, vec![id!("RC1")])));
+        assert_eq!(Stability::Stable, Stability::of(&version!(1, 0, 0, vec![id!("pl1")])));
+        assert_eq!(Stability::Dev, Stability::of(&version!(1, 0, 0, vec![id!("nightly")])));
+    }
+
+    #[test]
+    fn test_exact() {
+        check_allows!(Requirement::parse("1.0.2", Stability::Stable), ["1.0.2"], ["1.0.3"]);
+        check_allows!(Requirement::parse("1.0", Stability::Stable), ["1.0.0"], ["1.0.1"]);
+        check_allows!(Requirement::parse("v1.0.2", Stability::Stable), ["1.0.2"], ["1.0.3"]);
+        check_allows!(Requirement::parse("!=1.0.2", Stability::Stable), ["1.0.3"], ["1.0.2"]);
+    }
+
+    #[test]
+    fn test_range() {
+        check_allows!(Requirement::parse(">=1.0 <2.0", Stability::Stable),
+                      ["1.0.0", "1.9.9"],
+                      ["0.9.9", "2.0.0"]);
+        check_allows!(Requirement::parse(">=1.0,<2.0", Stability::Stable),
+                      ["1.0.0", "1.9.9"],
+                      ["0.9.9", "2.0.0"]);
+        check_allows!(Requirement::parse(">= 1.0, < 2.0", Stability::Stable), ["1.5.0"], ["2.0.0"]);
+        check_allows!(Requirement::parse(">1.0", Stability::Stable), ["1.0.1"], ["1.0.0"]);
+        check_allows!(Requirement::parse("<=1.0", Stability::Stable), ["1.0.0"], ["1.0.1"]);
+        check_allows!(Requirement::parse("<2.0", Stability::Dev), ["1.9.9"], ["2.0.0-beta"]);
+        check_allows!(Requirement::parse(">=1.0", Stability::Dev), ["1.0.0-beta"], ["0.9.9"]);
+    }
+
+    #[test]
+    fn test_union() {
+        check_allows!(Requirement::parse(">=1.0 <1.1 || >=1.2", Stability::Stable),
+                      ["1.0.5", "1.2.0"],
+                      ["1.1.0"]);
+        check_allows!(Requirement::parse("1.0.* | 2.0.*", Stability::Stable),
+                      ["1.0.5", "2.0.1"],
+                      ["1.1.0"]);
+    }
+
+    #[test]
+    fn test_hyphen() {
+        check_allows!(Requirement::parse("1.0 - 2.0", Stability::Stable),
+                      ["1.0.0", "2.0.9"],
+                      ["0.9.9", "2.1.0"]);
+        check_allows!(Requirement::parse("1.0.0 - 2.1.0", Stability::Stable),
+                      ["1.0.0", "2.1.0"],
+                      ["2.1.1"]);
+    }
+
+    #[test]
+    fn test_wildcard() {
+        check_allows!(Requirement::parse("1.0.*", Stability::Stable),
+                      ["1.0.0", "1.0.9"],
+                      ["0.9.9", "1.1.0"]);
+        check_allows!(Requirement::parse("1.*", Stability::Stable), ["1.0.0", "1.9.9"], ["2.0.0"]);
+        check_allows!(Requirement::parse("*", Stability::Stable),
+                      ["0.0.0", "9.9.9"],
+                      ["1.0.0-beta"]);
+    }
+
+    #[test]
+    fn test_tilde() {
+        check_allows!(Requirement::parse("~1.2", Stability::Stable),
+                      ["1.2.0", "1.9.9"],
+                      ["1.1.9", "2.0.0"]);
+        check_allows!(Requirement::parse("~1.2.3", Stability::Stable),
+                      ["1.2.3", "1.2.9"],
+                      ["1.2.2", "1.3.0"]);
+        check_allows!(Requirement::parse("~1", Stability::Stable), ["1.0.0", "1.9.9"], ["2.0.0"]);
+    }
+
+    #[test]
+    fn test_caret() {
+        check_allows!(Requirement::parse("^1.2.3", Stability::Stable),
+                      ["1.2.3", "1.9.9"],
+                      ["1.2.2", "2.0.0"]);
+        check_allows!(Requirement::parse("^0.3", Stability::Stable), ["0.3.0", "0.3.9"], ["0.4.0"]);
+        check_allows!(Requirement::parse("^0.0.3", Stability::Stable), ["0.0.3"], ["0.0.4"]);
+    }
+
+    #[test]
+    fn test_stability() {
+        check_allows!(Requirement::parse("^1.0", Stability::Stable),
+                      ["1.1.0"],
+                      ["1.1.0-beta1", "1.1.0-RC1"]);
+        check_allows!(Requirement::parse("^1.0", Stability::Beta),
+                      ["1.1.0-beta1", "1.1.0-RC1"],
+                      ["1.1.0-alpha1"]);
+        check_allows!(Requirement::parse("^1.0@beta", Stability::Stable),
+                      ["1.1.0-beta1"],
+                      ["1.1.0-alpha1"]);
+        check_allows!(Requirement::parse("@dev", Stability::Stable), ["1.1.0-dev", "0.1.0"], []);
+        check_allows!(Requirement::parse(">=1.0.0-RC1", Stability::Stable),
+                      ["1.0.0-RC2", "1.0.0"],
+                      ["1.0.0-beta1"]);
+        check_allows!(Requirement::parse("1.0.x-dev", Stability::Stable),
+                      ["1.0.5-dev", "1.0.0"],
+                      ["1.1.0-dev"]);
+    }
+
+    #[test]
+    fn test_largest() {
+        let max = "18446744073709551615";
+
+        check_allows!(Requirement::parse(&format!("^{}", max), Stability::Stable),
+                      [format!("{0}.{0}.{0}", max)],
+                      []);
+        check_allows!(Requirement::parse(&format!("~1.{}", max), Stability::Stable),
+                      [format!("1.{}.9", max)],
+                      ["2.0.0"]);
+        check_allows!(Requirement::parse(&format!("^0.0.{}", max), Stability::Stable),
+                      [],
+                      ["0.1.0"]);
+        check_allows!(Requirement::parse(&format!("{}.*", max), Stability::Stable),
+                      [format!("{}.1.0", max)],
+                      ["1.0.0"]);
+        check_allows!(Requirement::parse(&format!("1.0 - {}", max), Stability::Stable),
+                      [format!("{}.1.0", max)],
+                      []);
+    }
+
+    #[test]
+    fn test_invalid() {
+        for req in &["", "dev-master", "^1.0@foo", ">=1.*", ">=", "1.*.3", "a || b", "01.2.3",
+                     "^1.2.3-rc.01", "1.02.x-dev"] {
+            assert!(req.parse::<Requirement>().is_err(), "{} should be invalid", req);
+        }
+    }
+
+}
@@ -0,0 +1,360 @@
+//! Converts semantic version numbers to and from other versioning schemes.
+//!
+//! The **convert** module lets you publish one semantic version number to
+//! several package registries. Each conversion returns a `Conversion`, which
+//! has the converted version number and the `Loss`es that happened along the
+//! way, such as build metadata that the other scheme cannot represent.
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate recital;
+//! use recital::convert::{to_debian, to_pep440, Loss};
+//!
+//! # fn main() {
+//! let version = version!(1, 2, 0, vec![id!("rc"), id!(1)], vec![id!("sha"), id!(5)]);
+//! let pep440 = to_pep440(&version).unwrap();
+//!
+//! assert_eq!("1.2.0rc1", pep440.value.to_string());
+//! assert_eq!(vec![Loss::Build("sha.5".to_string()),
+//!                 Loss::PreRelease {
+//!                     from: "-rc.1".to_string(),
+//!                     to: "rc1".to_string(),
+//!                 }],
+//!            pep440.losses);
+//!
+//! assert_eq!("1.2.0~rc~1.", to_debian(&version).unwrap().value.to_string());
+//! # }
+//! ```
+//!
+//! Every conversion from a semantic version number keeps the order: if `a`
+//! is less than `b`, and both can be converted, then the converted `a` is
+//! less than the converted `b`. A version number is rejected instead of being
+//! converted if keeping the order is not possible, such as a pre-release that
+//! another scheme would sort differently. Since build metadata does not
+//! affect the order of semantic version numbers, dropping it is safe.
+use std::fmt::{self, Display};
+use super::debian;
+use super::maven;
+use super::nuget;
+use super::pep440;
+use super::version::{join, Identifier, Version};
+
+/// Represents something that could not be represented by a conversion.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Loss {
+    /// The build metadata was dropped.
+    Build(String),
+
+    /// The pre-release was rewritten, from one form into another.
+    PreRelease {
+        /// The pre-release as it was, with its separator (e.g. `-rc.1`).
+        from: String,
+
+        /// The pre-release as it is now, with its separator (e.g. `rc1`).
+        to: String,
+    },
+}
+
+impl Display for Loss {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Loss::Build(ref build) => write!(f, "dropped the build metadata +{}", build),
+            Loss::PreRelease { ref from, ref to } => {
+                write!(f, "rewrote the pre-release {} as {}", from, to)
+            }
+        }
+    }
+}
+
+/// Represents the result of a conversion.
+#[derive(Clone, Debug)]
+pub struct Conversion<T> {
+    /// The converted version number.
+    pub value: T,
+
+    /// What could not be represented, in the order it happened.
+    pub losses: Vec<Loss>,
+}
+
+impl<T> Conversion<T> {
+    /// Checks if nothing was lost in the conversion.
+    pub fn is_lossless(&self) -> bool {
+        self.losses.is_empty()
+    }
+}
+
+/// Returns a copy of the version number without build metadata, and the loss.
+fn without_build(version: &Version) -> (Version, Vec<Loss>) {
+    let mut stripped = version.clone();
+    let mut losses = Vec::new();
+
+    if !stripped.build.is_empty() {
+        losses.push(Loss::Build(join(&stripped.build)));
+        stripped.build.clear();
+    }
+
+    (stripped, losses)
+}
+
+/// Records a rewritten pre-release, using the part after the release numbers.
+fn rewritten(version: &Version, converted: &str, losses: &mut Vec<Loss>) {
+    let release = format!("{}.{}.{}", version.major, version.minor, version.patch);
+    let from = format!("-{}", join(&version.pre));
+    let to = converted.strip_prefix(&release).unwrap_or(converted);
+
+    if !version.pre.is_empty() && from != to {
+        losses.push(Loss::PreRelease {
+            from,
+            to: to.to_string(),
+        });
+    }
+}
+
+/// Converts a semantic version number into a PEP 440 version number.
+///
+/// The pre-release must be `alpha`, `beta`, or `rc` followed by a number,
+/// which is rewritten (`-rc.1` is `rc1`). The build metadata is dropped, as
+/// a local version label would change the order.
+pub fn to_pep440(version: &Version) -> Result<Conversion<pep440::Version>, String> {
+    let (stripped, mut losses) = without_build(version);
+    let value = pep440::Version::from_semver(&stripped)?;
+
+    rewritten(&stripped, &value.to_string(), &mut losses);
+
+    Ok(Conversion { value, losses })
+}
+
+/// Converts a semantic version number into a Maven version number.
+///
+/// The pre-release must start with `alpha`, `beta`, `milestone`, `rc`, or
+/// `snapshot`, followed by numbers, so that Maven sorts it the same way. The
+/// build metadata is dropped.
+pub fn to_maven(version: &Version) -> Result<Conversion<maven::Version>, String> {
+    let (stripped, losses) = without_build(version);
+    let value = maven::Version::from_semver(&stripped)?;
+
+    Ok(Conversion { value, losses })
+}
+
+/// Converts a semantic version number into a Debian version number.
+///
+/// The pre-release follows a tilde (`~`) instead of a hyphen, and each of
+/// its identifiers ends with a tilde or a dot (`-rc.1` is `~rc~1.`), so that
+/// `dpkg` sorts them the same way. Any of its letters can only be in
+/// identifiers that are all letters. The build metadata is dropped.
+pub fn to_debian(version: &Version) -> Result<Conversion<debian::Version>, String> {
+    let (stripped, mut losses) = without_build(version);
+    let value = debian::Version::from_semver(&stripped)?;
+
+    rewritten(&stripped, &value.to_string(), &mut losses);
+
+    Ok(Conversion { value, losses })
+}
+
+/// Converts a semantic version number into one that npm accepts.
+///
+/// The numbers must be safe JavaScript integers (no greater than `2^53 - 1`).
+/// The build metadata is dropped, as it is when a package is published.
+pub fn to_npm(version: &Version) -> Result<Conversion<Version>, String> {
+    const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+    let numbers = [version.major, version.minor, version.patch];
+    let identifiers = version.pre.iter().filter_map(|i| match *i {
+        Identifier::Number(n) => Some(n),
+        Identifier::Alpha(_) => None,
+    });
+
+    if numbers.iter().cloned().chain(identifiers).any(|n| n > MAX_SAFE_INTEGER) {
+        return Err(format!("Number too large for npm: {}", version));
+    }
+
+    let (value, losses) = without_build(version);
+
+    Ok(Conversion { value, losses })
+}
+
+/// Converts a semantic version number into a NuGet version number.
+///
+/// The numbers must not be greater than `nuget::MAX_NUMBER`, and the letters
+/// in the pre-release must be lower case, since NuGet ignores their case.
+/// Nothing is lost, since NuGet keeps the build metadata.
+pub fn to_nuget(version: &Version) -> Result<Conversion<nuget::Version>, String> {
+    if [version.major, version.minor, version.patch].iter().any(|&n| n > nuget::MAX_NUMBER) {
+        return Err(format!("Number too large for NuGet: {}", version));
+    }
+
+    if join(&version.pre).chars().any(|c| c.is_ascii_uppercase()) {
+        return Err(format!("Upper case pre-release for NuGet: {}", version));
+    }
+
+    Ok(Conversion {
+        value: nuget::Version {
+            major: version.major,
+            minor: version.minor,
+            patch: version.patch,
+            revision: 0,
+            pre: version.pre.clone(),
+            build: version.build.clone(),
+        },
+        losses: Vec::new(),
+    })
+}
+
+/// Converts a NuGet version number into a semantic version number.
+///
+/// The pre-release is rewritten in lower case, since NuGet ignores its case.
+///
+/// A revision that is not zero (`0`) is rejected, since semantic versioning
+/// has no fourth number: `1.2.3.4` and `1.2.3.5` would be equal, and NuGet
+/// sorts `1.0.0.1-alpha` after `1.0.0-beta`, which semantic versioning could
+/// not keep.
+pub fn from_nuget(version: &nuget::Version) -> Result<Conversion<Version>, String> {
+    if version.revision != 0 {
+        return Err(format!("Revision for semver: {}", version));
+    }
+
+    let mut losses = Vec::new();
+    let pre: Vec<Identifier> = version.pre
+        .iter()
+        .map(|i| match *i {
+            Identifier::Alpha(ref a) => Identifier::Alpha(a.to_ascii_lowercase()),
+            Identifier::Number(n) => Identifier::Number(n),
+        })
+        .collect();
+
+    if pre != version.pre {
+        losses.push(Loss::PreRelease {
+            from: format!("-{}", join(&version.pre)),
+            to: format!("-{}", join(&pre)),
+        });
+    }
+
+    Ok(Conversion {
+        value: Version {
+            major: version.major,
+            minor: version.minor,
+            patch: version.patch,
+            pre,
+            build: version.build.clone(),
+        },
+        losses,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{from_nuget, to_debian, to_maven, to_npm, to_nuget, to_pep440, Loss};
+    use super::super::nuget;
+    use super::super::version::Version;
+
+    // Checks that the conversions of the version numbers are in order.
+    macro_rules! check_order {
+        ($convert:expr, $versions:expr) => {
+            let converted: Vec<_> = $versions.iter()
+                .map(|s| $convert(&s.parse::<Version>().unwrap()).expect(s).value)
+                .collect();
+
+            for (i, pair) in converted.windows(2).enumerate() {
+                assert!(pair[0] < pair[1], "{} < {}", $versions[i], $versions[i + 1]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_order() {
+        let common = ["0.9.9", "1.0.0-alpha.1", "1.0.0-beta.2", "1.0.0-rc.1", "1.0.0-rc.2",
+                      "1.0.0", "1.0.1", "1.10.0", "2.0.0"];
+
+        check_order!(to_pep440, common);
+        check_order!(to_maven, common);
+        check_order!(to_debian, common);
+        check_order!(to_npm, common);
+        check_order!(to_nuget, common);
+
+        let labels = ["1.0.0-1", "1.0.0-alpha", "1.0.0-alpha.1", "1.0.0-alpha.beta",
+                      "1.0.0-alphabet", "1.0.0-beta", "1.0.0-beta.11", "1.0.0-pre.1",
+                      "1.0.0-preview", "1.0.0-rc", "1.0.0-rc.1", "1.0.0"];
+
+        check_order!(to_debian, labels);
+        check_order!(to_npm, labels);
+        check_order!(to_nuget, labels);
+    }
+
+    #[test]
+    fn test_losses() {
+        let version: Version = "1.0.0-rc.1+sha.5".parse().unwrap();
+        let build = Loss::Build("sha.5".to_string());
+
+        assert_eq!(vec![build.clone(),
+                        Loss::PreRelease {
+                            from: "-rc.1".to_string(),
+                            to: "rc1".to_string(),
+                        }],
+                   to_pep440(&version).unwrap().losses);
+        assert_eq!(vec![build.clone()], to_maven(&version).unwrap().losses);
+        assert_eq!(vec![build.clone(),
+                        Loss::PreRelease {
+                            from: "-rc.1".to_string(),
+                            to: "~rc~1.".to_string(),
+                        }],
+                   to_debian(&version).unwrap().losses);
+        assert_eq!(vec![build], to_npm(&version).unwrap().losses);
+        assert!(to_nuget(&version).unwrap().is_lossless());
+        assert!(to_pep440(&version!(1, 0, 0)).unwrap().is_lossless());
+        assert_eq!("dropped the build metadata +sha.5",
+                   Loss::Build("sha.5".to_string()).to_string());
+    }
+
+    #[test]
+    fn test_rejected() {
+        assert!(to_pep440(&"1.0.0-dev.1".parse().unwrap()).is_err());
+        assert!(to_maven(&"1.0.0-1".parse().unwrap()).is_err());
+        assert!(to_debian(&"1.0.0-rc1".parse().unwrap()).is_err());
+        assert!(to_npm(&version!(9007199254740992, 0, 0)).is_err());
+        assert!(to_npm(&"1.0.0-9007199254740992".parse().unwrap()).is_err());
+        assert!(to_npm(&version!(9007199254740991, 0, 0)).is_ok());
+        assert!(to_nuget(&"1.0.0-RC.1".parse().unwrap()).is_err());
+        assert!(to_nuget(&version!(2147483648, 0, 0)).is_err());
+    }
+
+    #[test]
+    fn test_from_nuget() {
+        let conversion = from_nuget(&"1.2.3-Beta.2+sha".parse().unwrap()).unwrap();
+
+        assert_eq!("1.2.3-beta.2+sha", conversion.value.to_string());
+        assert_eq!(vec![Loss::PreRelease {
+                            from: "-Beta.2".to_string(),
+                            to: "-beta.2".to_string(),
+                        }],
+                   conversion.losses);
+
+        let first: nuget::Version = "1.2.3.4".parse().unwrap();
+        let second: nuget::Version = "1.2.3.5".parse().unwrap();
+
+        assert!(first < second);
+        assert!(from_nuget(&first).is_err());
+        assert!(from_nuget(&second).is_err());
+        assert!(from_nuget(&"1.2.3.0".parse().unwrap()).unwrap().is_lossless());
+        assert!(from_nuget(&"1.2.3-rc.1".parse().unwrap()).unwrap().is_lossless());
+
+        let beta: nuget::Version = "1.0.0-beta".parse().unwrap();
+        let alpha: nuget::Version = "1.0.0.1-alpha".parse().unwrap();
+
+        assert!(beta < alpha);
+        assert!(from_nuget(&beta).is_ok());
+        assert!(from_nuget(&alpha).is_err());
+
+        let ordered = ["1.0.0-ALPHA", "1.0.0-beta", "1.0.0", "1.0.1-rc.1", "1.0.1", "1.1.0"];
+
+        for (i, a) in ordered.iter().enumerate() {
+            for b in &ordered[i + 1..] {
+                let from = from_nuget(&a.parse().unwrap()).unwrap().value;
+                let to = from_nuget(&b.parse().unwrap()).unwrap().value;
+
+                assert!(from < to, "{} < {}", a, b);
+            }
+        }
+    }
+
+}
@@ -0,0 +1,437 @@
+//! Creates, parses, and compares Debian package version numbers.
+//!
+//! The **debian** module provides you with a `Version` that is ordered using
+//! the same algorithm as `dpkg`. A Debian version number has an optional
+//! epoch, an upstream version, and an optional Debian revision, in the form
+//! `[epoch:]upstream[-revision]`.
+//!
+//! ```
+//! use recital::debian::Version;
+//!
+//! let a: Version = "1.0~rc1-1".parse().unwrap();
+//! let b: Version = "1.0-1".parse().unwrap();
+//! let c: Version = "1:0.9-1".parse().unwrap();
+//!
+//! assert!(a < b && b < c);
+//! ```
+//!
+//! Version relations from dependency fields (`>= 1.2-3`, `<< 2.0`) can be
+//! parsed with `relation`, which creates an `Operation` constraint.
+//!
+//! ```
+//! use recital::debian::{relation, Version};
+//! use recital::resolve::Constraint;
+//!
+//! let operation = relation("(<< 2.0)").unwrap();
+//!
+//! assert!(operation.allows(&"2.0~beta1".parse::<Version>().unwrap()));
+//! assert!(!operation.allows(&"2.0".parse::<Version>().unwrap()));
+//! ```
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use super::resolve::Operation;
+use super::scheme::{bump_dotted, Bump, VersionScheme};
+use super::version::{self, Identifier};
+
+/// Represents a Debian package version number.
+#[derive(Clone, Debug, Default)]
+pub struct Version {
+    /// The epoch, which is zero (`0`) if not given.
+    pub epoch: u64,
+
+    /// The upstream version.
+    pub upstream: String,
+
+    /// The Debian revision, which is empty if not given.
+    pub revision: String,
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}:", self.epoch)?;
+        }
+
+        write!(f, "{}", self.upstream)?;
+
+        if !self.revision.is_empty() {
+            write!(f, "-{}", self.revision)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Enables parsing of strings as Debian version numbers.
+///
+/// The upstream version must start with a digit, and may only contain
+/// alphanumerics and `.`, `+`, `~`, and `-` (if there is a revision). The
+/// revision may only contain alphanumerics and `.`, `+`, and `~`.
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Version, String> {
+        let invalid = || format!("Invalid version number: {}", s);
+        let s = s.trim();
+
+        let (epoch, rest) = match s.find(':') {
+            Some(colon) => (s[..colon].parse().map_err(|_| invalid())?, &s[colon + 1..]),
+            None => (0, s),
+        };
+
+        let (upstream, revision) = match rest.rfind('-') {
+            Some(hyphen) => (&rest[..hyphen], &rest[hyphen + 1..]),
+            None => (rest, ""),
+        };
+
+        let allowed = |c: char, extra: &str| c.is_ascii_alphanumeric() || extra.contains(c);
+
+        if !upstream.starts_with(|c: char| c.is_ascii_digit()) ||
+           !upstream.chars().all(|c| allowed(c, ".+~-")) ||
+           (rest.contains('-') && revision.is_empty()) ||
+           !revision.chars().all(|c| allowed(c, ".+~")) {
+            return Err(invalid());
+        }
+
+        Ok(Version {
+            epoch,
+            upstream: upstream.to_string(),
+            revision: revision.to_string(),
+        })
+    }
+}
+
+impl Eq for Version {}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Version) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| compare(&self.upstream, &other.upstream))
+            .then_with(|| compare(&self.revision, &other.revision))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Version) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Version) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl VersionScheme for Version {
+    /// Increments the leading numeric components of the upstream version.
+    ///
+    /// Anything after the numeric components is removed, and the revision is
+    /// reset to `1` if there was one.
+    fn bump(&mut self, level: Bump) {
+        self.upstream = bump_dotted(&self.upstream, level);
+
+        if !self.revision.is_empty() {
+            self.revision = "1".to_string();
+        }
+    }
+
+    /// A version number is stable if its upstream version has no tilde (`~`).
+    fn is_stable(&self) -> bool {
+        !self.upstream.contains('~')
+    }
+}
+
+impl Version {
+    /// Converts a semantic version number into a Debian version number.
+    ///
+    /// The pre-release identifiers follow a tilde (`~`), so that they sort
+    /// before the release. Each alphanumeric identifier ends with a tilde, and
+    /// each numeric one with a dot (`.`), so `1.0.0-rc.1` is `1.0.0~rc~1.`.
+    /// Joining them with dots would not work, since `dpkg` sorts a dot after
+    /// letters, which puts `1.0.0~pre.1` after `1.0.0~preview`. Build
+    /// identifiers are dropped, since they do not affect ordering either.
+    ///
+    /// The order of the version numbers is kept for every conversion that
+    /// succeeds. This requires alphanumeric identifiers to only have letters,
+    /// since `dpkg` compares embedded digits and hyphens differently.
+    pub fn from_semver(semver: &version::Version) -> Result<Version, String> {
+        let mut upstream = format!("{}.{}.{}", semver.major, semver.minor, semver.patch);
+
+        if !semver.pre.is_empty() {
+            letters_only(semver)?;
+            upstream.push('~');
+
+            for identifier in &semver.pre {
+                upstream.push_str(&identifier.to_string());
+                upstream.push(match *identifier {
+                    Identifier::Alpha(_) => '~',
+                    Identifier::Number(_) => '.',
+                });
+            }
+        }
+
+        Ok(Version {
+            upstream,
+            ..Default::default()
+        })
+    }
+}
+
+/// Returns the pre-release identifiers, if every alphanumeric one is letters.
+///
+/// This is shared by the Debian and RPM conversions, which cannot keep the
+/// order of identifiers that mix letters with digits or hyphens.
+pub(crate) fn letters_only(semver: &version::Version) -> Result<Vec<String>, String> {
+    semver.pre
+        .iter()
+        .map(|identifier| {
+            match *identifier {
+                Identifier::Alpha(ref a) if !a.chars().all(|c| c.is_ascii_alphabetic()) => {
+                    Err(format!("Unsupported pre-release identifier in {}: {}", semver, a))
+                }
+
+                _ => Ok(identifier.to_string()),
+            }
+        })
+        .collect()
+}
+
+/// Parses a version relation (e.g. `>= 1.2-3`, `(<< 2.0)`) as an operation.
+///
+/// The relations are `<<`, `<=`, `=`, `>=`, and `>>`. The deprecated `<` and
+/// `>` relations mean `<=` and `>=`, as they do in `dpkg`.
+pub fn relation(s: &str) -> Result<Operation<Version>, String> {
+    let trimmed = s.trim();
+    let trimmed = match trimmed.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        Some(inner) => inner.trim(),
+        None => trimmed,
+    };
+
+    for op in &["<<", "<=", ">=", ">>", "=", "<", ">"] {
+        if let Some(rest) = trimmed.strip_prefix(op) {
+            let version: Version = rest.parse()?;
+
+            return Ok(match *op {
+                "<<" => Operation::LessThan(version),
+                "<=" | "<" => Operation::LessThanOrEqualTo(version),
+                ">=" | ">" => Operation::GreaterThanOrEqualTo(version),
+                ">>" => Operation::GreaterThan(version),
+                _ => Operation::Exactly(version),
+            });
+        }
+    }
+
+    Err(format!("Invalid relation: {}", s))
+}
+
+/// Returns the sort weight of a character outside of a number.
+///
+/// A tilde sorts before everything, even the end of the string, and letters
+/// sort before all other characters.
+fn order(c: Option<u8>) -> i32 {
+    match c {
+        None => 0,
+        Some(b'~') => -1,
+        Some(c) if c.is_ascii_digit() => 0,
+        Some(c) if c.is_ascii_alphabetic() => c as i32,
+        Some(c) => c as i32 + 256,
+    }
+}
+
+/// Compares two upstream versions or revisions the way `dpkg` does.
+///
+/// The strings are compared in alternating non-numeric and numeric parts.
+/// Non-numeric parts are compared by the weight of each character, and
+/// numeric parts are compared by their value.
+pub fn compare(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+    let digit = |s: &[u8], k: usize| s.get(k).map(|c| c.is_ascii_digit()).unwrap_or(false);
+
+    while i < a.len() || j < b.len() {
+        while (i < a.len() && !digit(a, i)) || (j < b.len() && !digit(b, j)) {
+            let (ac, bc) = (order(a.get(i).cloned()), order(b.get(j).cloned()));
+
+            if ac != bc {
+                return ac.cmp(&bc);
+            }
+
+            i += 1;
+            j += 1;
+        }
+
+        while a.get(i) == Some(&b'0') {
+            i += 1;
+        }
+
+        while b.get(j) == Some(&b'0') {
+            j += 1;
+        }
+
+        let mut first_diff = Ordering::Equal;
+
+        while digit(a, i) && digit(b, j) {
+            if first_diff == Ordering::Equal {
+                first_diff = a[i].cmp(&b[j]);
+            }
+
+            i += 1;
+            j += 1;
+        }
+
+        if digit(a, i) {
+            return Ordering::Greater;
+        }
+
+        if digit(b, j) {
+            return Ordering::Less;
+        }
+
+        if first_diff != Ordering::Equal {
+            return first_diff;
+        }
+    }
+
+    Ordering::Equal
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::cmp::Ordering;
+    use super::{compare, relation, Version};
+    use super::super::resolve::Constraint;
+    use super::super::scheme::{Bump, VersionScheme};
+    use super::super::version;
+
+    #[test]
+    fn test_parse() {
+        let version: Version = "2:1.0-beta+dfsg-3ubuntu1".parse().unwrap();
+
+        assert_eq!(2, version.epoch);
+        assert_eq!("1.0-beta+dfsg", version.upstream);
+        assert_eq!("3ubuntu1", version.revision);
+        assert_eq!("2:1.0-beta+dfsg-3ubuntu1", version.to_string());
+        assert_eq!("1.0", "0:1.0".parse::<Version>().unwrap().to_string());
+
+        for s in &["", "a1.0", "1.0-", "x:1.0", "1.0_1", "1.0-1:2"] {
+            assert!(s.parse::<Version>().is_err(), "{} should be invalid", s);
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(Ordering::Equal, compare("1.0", "1.0"));
+        assert_eq!(Ordering::Equal, compare("1.00", "1.0"));
+        assert_eq!(Ordering::Equal, compare("", "0"));
+        assert_eq!(Ordering::Less, compare("1.0~rc1", "1.0"));
+        assert_eq!(Ordering::Less, compare("1.0~~", "1.0~"));
+        assert_eq!(Ordering::Less, compare("1.0", "1.0a"));
+        assert_eq!(Ordering::Less, compare("1.0a", "1.0+"));
+        assert_eq!(Ordering::Less, compare("1.0", "1.0.1"));
+        assert_eq!(Ordering::Less, compare("1.9", "1.10"));
+        assert_eq!(Ordering::Less, compare("1.0-", "1.0."));
+    }
+
+    #[test]
+    fn test_ordering() {
+        let ordered = ["1.0~~",
+                       "1.0~~a",
+                       "1.0~",
+                       "1.0",
+                       "1.0-1",
+                       "1.0-1ubuntu1",
+                       "1.0a",
+                       "1.0+dfsg",
+                       "1.2",
+                       "1.10",
+                       "1:0.1"];
+
+        let versions: Vec<Version> = ordered.iter().map(|s| s.parse().unwrap()).collect();
+
+        for pair in versions.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+
+        assert_eq!("1.0".parse::<Version>().unwrap(), "0:1.0".parse::<Version>().unwrap());
+    }
+
+    #[test]
+    fn test_relation() {
+        assert!(relation(">= 1.2-3").unwrap().allows(&"1.2-3".parse().unwrap()));
+        assert!(!relation(">= 1.2-3").unwrap().allows(&"1.2-2".parse().unwrap()));
+        assert!(relation("(<< 2.0)").unwrap().allows(&"2.0~rc1".parse().unwrap()));
+        assert!(!relation("<< 2.0").unwrap().allows(&"2.0".parse().unwrap()));
+        assert!(relation(">> 2.0").unwrap().allows(&"2.0-1".parse().unwrap()));
+        assert!(relation("= 2.0-1").unwrap().allows(&"2.0-1".parse().unwrap()));
+        assert!(!relation("= 2.0").unwrap().allows(&"2.0-1".parse().unwrap()));
+        assert!(relation("< 2.0").unwrap().allows(&"2.0".parse().unwrap()));
+        assert!(relation("<= 2.0").unwrap().allows(&"2.0".parse().unwrap()));
+        assert!(relation("~ 2.0").is_err());
+        assert!(relation(">= x").is_err());
+    }
+
+    #[test]
+    fn test_bump() {
+        let mut version: Version = "1:2.4~rc1-3".parse().unwrap();
+
+        version.bump(Bump::Minor);
+
+        assert_eq!("1:2.5-1", version.to_string());
+        assert!(version.is_stable());
+        assert!(!"2.4~rc1".parse::<Version>().unwrap().is_stable());
+
+        let large: Version = "99999999999999999999999.1-3".parse().unwrap();
+        let mut version = large.clone();
+
+        version.bump(Bump::Major);
+
+        assert_eq!("100000000000000000000000.0-1", version.to_string());
+        assert!(version > large);
+    }
+
+    #[test]
+    fn test_from_semver() {
+        let ordered = ["1.0.0-0",
+                       "1.0.0-1",
+                       "1.0.0-1.0",
+                       "1.0.0-alpha",
+                       "1.0.0-alpha.1",
+                       "1.0.0-alpha.beta",
+                       "1.0.0-alphabet",
+                       "1.0.0-beta",
+                       "1.0.0-beta.2",
+                       "1.0.0-beta.11",
+                       "1.0.0-pre.1",
+                       "1.0.0-preview",
+                       "1.0.0-rc",
+                       "1.0.0-rc.0",
+                       "1.0.0-rc.1",
+                       "1.0.0",
+                       "1.0.1",
+                       "1.10.0"];
+
+        for pair in ordered.windows(2) {
+            let (a, b): (version::Version, version::Version) = (pair[0].parse().unwrap(),
+                                                                pair[1].parse().unwrap());
+
+            assert!(a < b, "{} < {}", a, b);
+
+            let (a, b) = (Version::from_semver(&a).unwrap(), Version::from_semver(&b).unwrap());
+
+            assert!(a < b, "{} < {}", a, b);
+        }
+
+        assert_eq!("1.0.0~rc~1.",
+                   Version::from_semver(&"1.0.0-rc.1+build.5".parse().unwrap())
+                       .unwrap()
+                       .to_string());
+
+        assert!(Version::from_semver(&"1.0.0-rc1".parse().unwrap()).is_err());
+        assert!(Version::from_semver(&"1.0.0-x-y".parse().unwrap()).is_err());
+    }
+
+}
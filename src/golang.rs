@@ -0,0 +1,451 @@
+//! Creates, parses, and compares Go module version numbers.
+//!
+//! The **golang** module provides you with a `Version` for Go modules, which
+//! is a semantic version number with a `v` prefix. The only build metadata
+//! that Go allows is `+incompatible`, which marks a `v2` or later version of
+//! a module that does not use a major version suffix in its path.
+//!
+//! ```
+//! use recital::golang::Version;
+//!
+//! let a: Version = "v1.2.3".parse().unwrap();
+//! let b: Version = "v2.0.0+incompatible".parse().unwrap();
+//!
+//! assert!(a < b);
+//! assert!(b.is_incompatible());
+//! assert!("1.2.3".parse::<Version>().is_err());
+//! ```
+//!
+//! Commits that are not tagged are referred to by pseudo-versions, which
+//! encode the time of the commit and its revision identifier in pre-release
+//! identifiers. A pseudo-version sorts after the tag it is based on, and
+//! before the next possible release.
+//!
+//! ```
+//! use recital::golang::{Timestamp, Version};
+//!
+//! let base: Version = "v1.2.3".parse().unwrap();
+//! let time: Timestamp = "20191109021931".parse().unwrap();
+//! let pseudo = Version::pseudo(&base, &time, "daa7c04131f5a1b2").unwrap();
+//!
+//! assert_eq!("v1.2.4-0.20191109021931-daa7c04131f5", pseudo.to_string());
+//! assert!(base < pseudo && pseudo < "v1.2.4".parse().unwrap());
+//!
+//! let parts = pseudo.pseudo_parts().unwrap();
+//!
+//! assert_eq!(Some(base), parts.base);
+//! assert_eq!(time, parts.timestamp);
+//! assert_eq!("daa7c04131f5", parts.revision);
+//! ```
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use super::scheme::{Bump, VersionScheme};
+use super::version::{self, Identifier};
+
+/// The build metadata of an incompatible version number.
+const INCOMPATIBLE: &str = "incompatible";
+
+/// Represents the time of a commit in a pseudo-version, in UTC.
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Timestamp {
+    /// The year.
+    pub year: u64,
+
+    /// The month, from one (`1`) to twelve (`12`).
+    pub month: u64,
+
+    /// The day of the month, from one (`1`) to thirty-one (`31`).
+    pub day: u64,
+
+    /// The hour, from zero (`0`) to twenty-three (`23`).
+    pub hour: u64,
+
+    /// The minute, from zero (`0`) to fifty-nine (`59`).
+    pub minute: u64,
+
+    /// The second, from zero (`0`) to fifty-nine (`59`).
+    pub second: u64,
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "{:04}{:02}{:02}{:02}{:02}{:02}",
+               self.year,
+               self.month,
+               self.day,
+               self.hour,
+               self.minute,
+               self.second)
+    }
+}
+
+/// Enables parsing of strings as timestamps, in the `yyyymmddhhmmss` format.
+impl FromStr for Timestamp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Timestamp, String> {
+        let invalid = || format!("Invalid timestamp: {}", s);
+
+        if s.len() != 14 || !s.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let field = |start: usize, end: usize| s[start..end].parse::<u64>().unwrap();
+        let timestamp = Timestamp {
+            year: field(0, 4),
+            month: field(4, 6),
+            day: field(6, 8),
+            hour: field(8, 10),
+            minute: field(10, 12),
+            second: field(12, 14),
+        };
+
+        if timestamp.month < 1 || timestamp.month > 12 || timestamp.day < 1 ||
+           timestamp.day > 31 || timestamp.hour > 23 || timestamp.minute > 59 ||
+           timestamp.second > 59 {
+            return Err(invalid());
+        }
+
+        Ok(timestamp)
+    }
+}
+
+/// Represents the parts of a pseudo-version.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pseudo {
+    /// The tagged version number that the pseudo-version is based on, if any.
+    pub base: Option<Version>,
+
+    /// The time of the commit.
+    pub timestamp: Timestamp,
+
+    /// The revision identifier of the commit, such as a shortened hash.
+    pub revision: String,
+}
+
+/// Represents a Go module version number.
+///
+/// The semantic version number is ordered as usual, and `+incompatible` is
+/// ignored when comparing version numbers, as Go does.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Version {
+    /// The semantic version number, without the `v` prefix.
+    pub semver: version::Version,
+}
+
+impl Version {
+    /// Checks if the version number is marked `+incompatible`.
+    pub fn is_incompatible(&self) -> bool {
+        !self.semver.build.is_empty()
+    }
+
+    /// Checks if the version number is a pseudo-version.
+    pub fn is_pseudo(&self) -> bool {
+        self.pseudo_parts().is_some()
+    }
+
+    /// Returns the parts of a pseudo-version.
+    ///
+    /// There are three forms of pseudo-versions, depending on the tag that
+    /// they are based on:
+    ///
+    /// - `vX.0.0-yyyymmddhhmmss-abcdef123456` has no tag.
+    /// - `vX.Y.Z-pre.0.yyyymmddhhmmss-abcdef123456` is based on `vX.Y.Z-pre`.
+    /// - `vX.Y.(Z+1)-0.yyyymmddhhmmss-abcdef123456` is based on `vX.Y.Z`.
+    ///
+    /// `None` is returned if the version number is not a pseudo-version.
+    pub fn pseudo_parts(&self) -> Option<Pseudo> {
+        let pre = &self.semver.pre;
+        let (timestamp, revision) = match pre.last() {
+            Some(Identifier::Alpha(last)) => {
+                let mut parts = last.splitn(2, '-');
+                let timestamp = parts.next()?.parse().ok()?;
+                let revision = parts.next()?;
+
+                if revision.is_empty() || !revision.chars().all(|c| c.is_ascii_alphanumeric()) {
+                    return None;
+                }
+
+                (timestamp, revision.to_string())
+            }
+
+            _ => return None,
+        };
+
+        let mut base = self.clone();
+
+        base.semver.pre.truncate(pre.len() - 1);
+
+        let base = match base.semver.pre.pop() {
+            None if self.semver.minor == 0 && self.semver.patch == 0 => None,
+            Some(Identifier::Number(0)) if base.semver.pre.is_empty() => {
+                if self.semver.patch == 0 {
+                    return None;
+                }
+
+                base.semver.patch -= 1;
+
+                Some(base)
+            }
+            Some(Identifier::Number(0)) => Some(base),
+            _ => return None,
+        };
+
+        Some(Pseudo {
+            base,
+            timestamp,
+            revision,
+        })
+    }
+
+    /// Creates a pseudo-version for a commit after a tagged version number.
+    ///
+    /// The revision identifier is shortened to twelve characters, and the
+    /// pseudo-version is `+incompatible` if the tagged one is. A release
+    /// whose patch version number cannot be incremented is an error.
+    pub fn pseudo(base: &Version,
+                  timestamp: &Timestamp,
+                  revision: &str)
+                  -> Result<Version, String> {
+        let mut pseudo = base.clone();
+
+        if pseudo.semver.pre.is_empty() {
+            pseudo.semver.patch = pseudo.semver
+                .patch
+                .checked_add(1)
+                .ok_or_else(|| format!("The patch version number is too large: {}", base))?;
+        }
+
+        pseudo.semver.pre.push(Identifier::Number(0));
+        pseudo.semver.pre.push(commit(timestamp, revision)?);
+
+        Ok(pseudo)
+    }
+
+    /// Creates a pseudo-version for a commit when there are no tags.
+    ///
+    /// The pseudo-version is `vX.0.0-yyyymmddhhmmss-abcdef123456`, where `X`
+    /// is the major version number of the module.
+    pub fn pseudo_untagged(major: u64,
+                           timestamp: &Timestamp,
+                           revision: &str)
+                           -> Result<Version, String> {
+        let mut semver = version::Version::new();
+
+        semver.major = major;
+        semver.pre.push(commit(timestamp, revision)?);
+
+        Ok(Version { semver })
+    }
+}
+
+/// Creates the identifier for a commit in a pseudo-version.
+fn commit(timestamp: &Timestamp, revision: &str) -> Result<Identifier, String> {
+    if revision.is_empty() || !revision.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("Invalid revision identifier: {}", revision));
+    }
+
+    let revision: String = revision.chars().take(12).collect();
+
+    Ok(Identifier::Alpha(format!("{}-{}", timestamp, revision)))
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "v{}", self.semver)
+    }
+}
+
+/// Enables parsing of strings as Go module version numbers.
+///
+/// The version number must be canonical, so the `v` prefix and all three
+/// version numbers are required. The only build metadata allowed is
+/// `+incompatible`, and only for major version numbers of two (`2`) or more.
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Version, String> {
+        let invalid = || format!("Invalid Go module version: {}", s);
+        let rest = s.strip_prefix('v').ok_or_else(invalid)?;
+        let semver: version::Version = rest.parse().map_err(|_| invalid())?;
+
+        if semver.to_string() != rest {
+            return Err(invalid());
+        }
+
+        match semver.build.as_slice() {
+            [] => {}
+            [Identifier::Alpha(build)] if build == INCOMPATIBLE && semver.major >= 2 => {}
+            _ => return Err(invalid()),
+        }
+
+        Ok(Version { semver })
+    }
+}
+
+impl VersionScheme for Version {
+    /// Increments the semantic version number, keeping `+incompatible`.
+    fn bump(&mut self, level: Bump) {
+        let incompatible = self.is_incompatible();
+
+        self.semver.bump(level);
+
+        if incompatible {
+            self.semver.build.push(Identifier::Alpha(INCOMPATIBLE.to_string()));
+        }
+    }
+
+    fn is_stable(&self) -> bool {
+        self.semver.is_stable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Pseudo, Timestamp, Version};
+    use super::super::resolve::{resolve, Constraint};
+    use super::super::resolve::Operation::*;
+    use super::super::scheme::{Bump, VersionScheme};
+
+    #[test]
+    fn test_parse() {
+        let version: Version = "v1.2.3".parse().unwrap();
+        let incompatible: Version = "v2.0.0+incompatible".parse().unwrap();
+
+        assert_eq!(version!(1, 2, 3), version.semver);
+        assert_eq!("v2.0.0+incompatible", incompatible.to_string());
+        assert!(incompatible.is_incompatible());
+        assert!(!version.is_incompatible());
+
+        for s in &["1.2.3", "v1.2", "v1", "v01.2.3", "v1.2.3+build", "v1.0.0+incompatible",
+                   "v1.2.3-01", "V1.2.3", "v1.2.3 "] {
+            assert!(s.parse::<Version>().is_err(), "{} should be invalid", s);
+        }
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let timestamp: Timestamp = "20191109021931".parse().unwrap();
+
+        assert_eq!(2019, timestamp.year);
+        assert_eq!(11, timestamp.month);
+        assert_eq!(31, timestamp.second);
+        assert_eq!("20191109021931", timestamp.to_string());
+
+        for s in &["2019110902193", "2019110902193x", "20191309021931", "20191109241931"] {
+            assert!(s.parse::<Timestamp>().is_err(), "{} should be invalid", s);
+        }
+    }
+
+    #[test]
+    fn test_pseudo_parts() {
+        let timestamp: Timestamp = "20191109021931".parse().unwrap();
+        let parts = |base: Option<&str>| {
+            Some(Pseudo {
+                base: base.map(|s| s.parse().unwrap()),
+                timestamp,
+                revision: "daa7c04131f5".to_string(),
+            })
+        };
+
+        let pseudo_parts = |s: &str| s.parse::<Version>().unwrap().pseudo_parts();
+
+        assert_eq!(parts(None), pseudo_parts("v0.0.0-20191109021931-daa7c04131f5"));
+        assert_eq!(parts(Some("v1.2.3")),
+                   pseudo_parts("v1.2.4-0.20191109021931-daa7c04131f5"));
+        assert_eq!(parts(Some("v1.2.4-pre.2")),
+                   pseudo_parts("v1.2.4-pre.2.0.20191109021931-daa7c04131f5"));
+        assert_eq!(parts(Some("v2.1.0+incompatible")),
+                   pseudo_parts("v2.1.1-0.20191109021931-daa7c04131f5+incompatible"));
+
+        for s in &["v1.2.3", "v1.2.3-rc.1", "v1.2.0-0.20191109021931-daa7c04131f5",
+                   "v1.2.3-20191109021931-daa7c04131f5", "v1.2.4-1.20191109021931-daa7c04131f5",
+                   "v1.2.4-0.2019110902193-daa7c04131f5", "v0.0.0-20191109021931-"] {
+            assert!(!s.parse::<Version>().unwrap().is_pseudo(),
+                    "{} should not be a pseudo-version",
+                    s);
+        }
+    }
+
+    #[test]
+    fn test_pseudo() {
+        let timestamp: Timestamp = "20191109021931".parse().unwrap();
+        let hash = "daa7c04131f5a1b2c3d4";
+        let tagged: Version = "v1.2.3".parse().unwrap();
+        let pre: Version = "v1.2.4-pre".parse().unwrap();
+        let incompatible: Version = "v2.0.0+incompatible".parse().unwrap();
+        let largest: Version = "v1.2.18446744073709551615".parse().unwrap();
+
+        assert_eq!("v1.2.4-0.20191109021931-daa7c04131f5",
+                   Version::pseudo(&tagged, &timestamp, hash).unwrap().to_string());
+        assert_eq!("v1.2.4-pre.0.20191109021931-daa7c04131f5",
+                   Version::pseudo(&pre, &timestamp, hash).unwrap().to_string());
+        assert_eq!("v2.0.1-0.20191109021931-daa7c04131f5+incompatible",
+                   Version::pseudo(&incompatible, &timestamp, hash).unwrap().to_string());
+        assert_eq!("v2.0.0-20191109021931-daa7c04131f5",
+                   Version::pseudo_untagged(2, &timestamp, hash).unwrap().to_string());
+        assert!(Version::pseudo(&tagged, &timestamp, "").is_err());
+        assert!(Version::pseudo(&tagged, &timestamp, "not-a-hash").is_err());
+        assert!(Version::pseudo(&largest, &timestamp, hash).is_err());
+
+        let pseudo = Version::pseudo(&pre, &timestamp, hash).unwrap();
+
+        assert_eq!(Some(pre), pseudo.pseudo_parts().unwrap().base);
+    }
+
+    #[test]
+    fn test_ordering() {
+        let ordered = ["v0.0.0-20191109021931-daa7c04131f5",
+                       "v0.0.0-20200101000000-0123456789ab",
+                       "v0.1.0",
+                       "v1.2.3",
+                       "v1.2.4-0.20191109021931-daa7c04131f5",
+                       "v1.2.4-0.20200101000000-0123456789ab",
+                       "v1.2.4-pre",
+                       "v1.2.4-pre.0.20191109021931-daa7c04131f5",
+                       "v1.2.4-pre.1",
+                       "v1.2.4",
+                       "v2.0.0+incompatible",
+                       "v2.0.1-0.20191109021931-daa7c04131f5+incompatible"];
+
+        let versions: Vec<Version> = ordered.iter().map(|s| s.parse().unwrap()).collect();
+
+        for pair in versions.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+
+        assert_eq!("v2.0.0".parse::<Version>().unwrap(),
+                   "v2.0.0+incompatible".parse().unwrap());
+    }
+
+    #[test]
+    fn test_scheme() {
+        let mut version: Version = "v2.3.1-0.20191109021931-daa7c04131f5+incompatible"
+            .parse()
+            .unwrap();
+
+        assert!(!version.is_stable());
+
+        version.bump(Bump::Minor);
+
+        assert_eq!("v2.4.0+incompatible", version.to_string());
+        assert!(version.is_stable());
+
+        let versions: Vec<Version> = ["v1.2.3",
+                                      "v1.2.4-0.20191109021931-daa7c04131f5",
+                                      "v1.2.4",
+                                      "v1.3.0"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let c = constraints!(And,
+                             GreaterThan(versions[0].clone()),
+                             LessThan(versions[3].clone()));
+
+        assert!(c.allows(&versions[1]));
+        assert_eq!(resolve(&versions, &c), versions[1..3].to_vec());
+    }
+
+}
@@ -0,0 +1,306 @@
+//! Filters, deduplicates, and sorts streams of semantic version numbers.
+//!
+//! The **iter** module provides you with the `VersionIterator` trait, which
+//! adds adapters to any iterator of `Version`s or `&Version`s. Since they work
+//! on iterators, version numbers can be filtered as they are read, without
+//! collecting them first.
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate recital;
+//! use recital::iter::VersionIterator;
+//! use recital::resolve::Operation::*;
+//! use recital::version::Version;
+//!
+//! # fn main() {
+//! let registry = vec!["0.9.0", "1.0.0", "1.0.1", "1.1.0-rc.1", "1.1.0", "1.1.2", "2.0.0"];
+//! let constraint = constraints!(And,
+//!                               GreaterThanOrEqualTo(version!(1, 0, 0)),
+//!                               LessThan(version!(2, 0, 0)));
+//!
+//! let latest: Vec<String> = registry.iter()
+//!     .map(|v| v.parse::<Version>().unwrap())
+//!     .satisfying(&constraint)
+//!     .stable_only()
+//!     .latest_per_minor()
+//!     .map(|v| v.to_string())
+//!     .collect();
+//!
+//! assert_eq!(vec!["1.0.1", "1.1.2"], latest);
+//! # }
+//! ```
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::{btree_map, BTreeMap};
+use std::vec;
+use super::resolve::Constraint;
+use super::version::Version;
+
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+
+/// Adds version number adapters to iterators of `Version`s or `&Version`s.
+///
+/// The trait is implemented for every such iterator, so it only needs to be
+/// imported.
+pub trait VersionIterator: Iterator + Sized
+    where Self::Item: Borrow<Version>
+{
+    /// Keeps the version numbers that satisfy a constraint.
+    fn satisfying<C>(self, constraint: &C) -> Satisfying<'_, Self, C>
+        where C: Constraint + ?Sized
+    {
+        Satisfying {
+            iter: self,
+            constraint,
+        }
+    }
+
+    /// Keeps the version numbers that are stable.
+    ///
+    /// A version number is stable when its major version number is not zero
+    /// (`0`) and it has no pre-release identifiers.
+    fn stable_only(self) -> StableOnly<Self> {
+        StableOnly { iter: self }
+    }
+
+    /// Keeps the greatest version number of each minor version, from the
+    /// lowest minor version to the highest.
+    ///
+    /// Only one version number is kept for each minor version while the
+    /// iterator is read, but nothing is returned until it has been read
+    /// completely. When version numbers are equal, the first one is kept.
+    fn latest_per_minor(self) -> LatestPerMinor<Self::Item> {
+        let mut latest: BTreeMap<(u64, u64), Self::Item> = BTreeMap::new();
+
+        for item in self {
+            let key = (item.borrow().major, item.borrow().minor);
+
+            match latest.entry(key) {
+                btree_map::Entry::Vacant(entry) => {
+                    entry.insert(item);
+                }
+                btree_map::Entry::Occupied(mut entry) => {
+                    if item.borrow() > entry.get().borrow() {
+                        entry.insert(item);
+                    }
+                }
+            }
+        }
+
+        LatestPerMinor { iter: latest.into_iter() }
+    }
+
+    /// Skips version numbers that are equal to the one before them, which
+    /// means they only differ in their build identifiers.
+    ///
+    /// Like `Vec::dedup`, only consecutive version numbers are compared, so
+    /// the iterator has to be sorted to remove every duplicate.
+    fn dedupe_by_precedence(self) -> DedupeByPrecedence<Self> {
+        DedupeByPrecedence {
+            iter: self,
+            last: None,
+        }
+    }
+
+    /// Sorts the version numbers from the lowest to the highest.
+    ///
+    /// The version numbers have to be collected to be sorted. The sort is
+    /// stable, so version numbers that are equal stay in the same order.
+    fn sorted_semver(self) -> vec::IntoIter<Self::Item> {
+        let mut versions: Vec<Self::Item> = self.collect();
+
+        versions.sort_by(|a, b| a.borrow().cmp(b.borrow()));
+        versions.into_iter()
+    }
+}
+
+impl<I> VersionIterator for I
+    where I: Iterator,
+          I::Item: Borrow<Version>
+{
+}
+
+/// An iterator of the version numbers that satisfy a constraint.
+///
+/// This is created by `VersionIterator::satisfying`.
+pub struct Satisfying<'a, I, C: ?Sized + 'a> {
+    iter: I,
+    constraint: &'a C,
+}
+
+impl<'a, I, C> Iterator for Satisfying<'a, I, C>
+    where I: Iterator,
+          I::Item: Borrow<Version>,
+          C: Constraint + ?Sized
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let constraint = self.constraint;
+
+        self.iter.find(|v| constraint.allows(v.borrow()))
+    }
+}
+
+/// An iterator of the version numbers that are stable.
+///
+/// This is created by `VersionIterator::stable_only`.
+#[derive(Clone, Debug)]
+pub struct StableOnly<I> {
+    iter: I,
+}
+
+impl<I> Iterator for StableOnly<I>
+    where I: Iterator,
+          I::Item: Borrow<Version>
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.iter.find(|v| v.borrow().is_stable())
+    }
+}
+
+/// An iterator of the greatest version number of each minor version.
+///
+/// This is created by `VersionIterator::latest_per_minor`.
+#[derive(Debug)]
+pub struct LatestPerMinor<T> {
+    iter: btree_map::IntoIter<(u64, u64), T>,
+}
+
+impl<T> Iterator for LatestPerMinor<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.iter.next().map(|(_, version)| version)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// An iterator that skips version numbers equal to the one before them.
+///
+/// This is created by `VersionIterator::dedupe_by_precedence`.
+pub struct DedupeByPrecedence<I: Iterator> {
+    iter: I,
+    last: Option<Version>,
+}
+
+impl<I> Iterator for DedupeByPrecedence<I>
+    where I: Iterator,
+          I::Item: Borrow<Version>
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        for item in &mut self.iter {
+            let duplicate = match self.last {
+                Some(ref last) => last.cmp(item.borrow()) == Ordering::Equal,
+                None => false,
+            };
+
+            if !duplicate {
+                self.last = Some(item.borrow().clone());
+
+                return Some(item);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use super::VersionIterator;
+    use super::super::resolve::Operation::{GreaterThan, LessThan};
+    use super::super::version::Version;
+
+    #[test]
+    fn test_satisfying() {
+        let pool: Vec<Version> =
+            ["1.0.0", "1.2.0", "1.5.0", "2.0.0"].iter().map(|v| v.parse().unwrap()).collect();
+        let constraint = GreaterThan(version!(1, 0, 0));
+        let allowed: Vec<&Version> = pool.iter().satisfying(&constraint).collect();
+
+        assert_eq!(vec![&pool[1], &pool[2], &pool[3]], allowed);
+
+        let boxed: Box<dyn super::Constraint> = Box::new(LessThan(version!(1, 5, 0)));
+        let allowed: Vec<String> =
+            pool.into_iter().satisfying(&*boxed).map(|v| v.to_string()).collect();
+
+        assert_eq!(vec!["1.0.0", "1.2.0"], allowed);
+    }
+
+    #[test]
+    fn test_stable_only() {
+        let pool: Vec<Version> = ["0.9.0", "1.0.0-rc.1", "1.0.0", "1.0.1+build"]
+            .iter()
+            .map(|v| v.parse().unwrap())
+            .collect();
+        let stable: Vec<String> = pool.into_iter().stable_only().map(|v| v.to_string()).collect();
+
+        assert_eq!(vec!["1.0.0", "1.0.1+build"], stable);
+    }
+
+    #[test]
+    fn test_latest_per_minor() {
+        let pool: Vec<Version> = ["1.1.2",
+                                  "2.0.0",
+                                  "1.0.1",
+                                  "1.1.0",
+                                  "1.1.3-rc.1",
+                                  "1.0.0",
+                                  "1.1.2+second"]
+            .iter()
+            .map(|v| v.parse().unwrap())
+            .collect();
+        let latest: Vec<String> = pool.iter().latest_per_minor().map(|v| v.to_string()).collect();
+
+        assert_eq!(vec!["1.0.1", "1.1.3-rc.1", "2.0.0"], latest);
+
+        let pool: Vec<Version> =
+            ["1.1.2", "1.1.2+second"].iter().map(|v| v.parse().unwrap()).collect();
+        let latest: Vec<String> =
+            pool.into_iter().latest_per_minor().map(|v| v.to_string()).collect();
+
+        assert_eq!(vec!["1.1.2"], latest);
+    }
+
+    #[test]
+    fn test_dedupe_by_precedence() {
+        let pool: Vec<Version> = ["1.0.0+a", "1.0.0+b", "1.0.1", "1.0.0", "1.0.1", "1.0.1+c"]
+            .iter()
+            .map(|v| v.parse().unwrap())
+            .collect();
+        let deduped: Vec<String> =
+            pool.iter().dedupe_by_precedence().map(|v| v.to_string()).collect();
+
+        assert_eq!(vec!["1.0.0+a", "1.0.1", "1.0.0", "1.0.1"], deduped);
+
+        let deduped: Vec<String> = pool.into_iter()
+            .sorted_semver()
+            .dedupe_by_precedence()
+            .map(|v| v.to_string())
+            .collect();
+
+        assert_eq!(vec!["1.0.0+a", "1.0.1"], deduped);
+    }
+
+    #[test]
+    fn test_sorted_semver() {
+        let pool: Vec<Version> = ["1.0.0", "1.0.0-rc.1", "0.1.0", "1.0.0-beta.11", "1.0.0-beta.2"]
+            .iter()
+            .map(|v| v.parse().unwrap())
+            .collect();
+        let sorted: Vec<String> = pool.iter().sorted_semver().map(|v| v.to_string()).collect();
+
+        assert_eq!(vec!["0.1.0", "1.0.0-beta.2", "1.0.0-beta.11", "1.0.0-rc.1", "1.0.0"], sorted);
+    }
+
+}
@@ -91,10 +91,72 @@
 //! assert!(!(a > b));
 //! assert!(a != b);
 //! ```
+//!
+//! Without `std`
+//! -------------
+//!
+//! The `std` feature is enabled by default. Without it, the crate only needs
+//! `core` and `alloc`, and provides the `version`, `resolve`, `scheme`,
+//! `borrowed`, and `compact` modules. Version numbers can still be created,
+//! parsed, compared, and checked against constraints.
+//!
+//! ```toml
+//! "recital" = { version = "0.3.0", default-features = false }
+//! ```
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+
+#[cfg(feature = "std")]
 #[macro_use]
 extern crate nom;
 
+// The Python and WebAssembly bindings are generated with paths from the
+// crate root.
+#[cfg(any(feature = "python", feature = "wasm"))]
+extern crate core;
+
+#[cfg(all(feature = "std", any(test, feature = "proptest")))]
+extern crate proptest;
+
+#[cfg(feature = "python")]
+extern crate pyo3;
+
+#[cfg(feature = "advisory")]
+extern crate serde_json;
+
+#[cfg(feature = "wasm")]
+extern crate wasm_bindgen;
+
+/// Provides the parts of the standard library used by the crate.
+///
+/// Without `std`, they are taken from `core` and `alloc` instead, so that the
+/// same paths can be used either way.
+#[cfg(not(feature = "std"))]
+mod std {
+    pub use core::*;
+    pub use alloc::{boxed, collections, vec};
+
+    /// Provides what is missing from the `core` prelude.
+    pub mod prelude {
+        pub use alloc::borrow::ToOwned;
+        pub use alloc::boxed::Box;
+        pub use alloc::string::{String, ToString};
+        pub use alloc::vec::Vec;
+    }
+}
+
+/// Re-exports what the macros need, so that they also work without `std`.
+#[doc(hidden)]
+pub mod __private {
+    pub use std::boxed::Box;
+    pub use std::vec;
+    pub use std::vec::Vec;
+    pub use version::{IntoIdentifier, Literal};
+}
+
 // Version number management.
 #[macro_use]
 pub mod version;
@@ -105,9 +167,100 @@ mod parser;
 /// Re-exports submodules for glob imports.
 pub mod prelude {
     pub use resolve::{Constraint, Constraints, Operation, resolve};
+    pub use scheme::{Bump, VersionScheme};
     pub use version::{Identifier, Version};
 }
 
 // Version constraints management.
 #[macro_use]
 pub mod resolve;
+
+// Security advisory matching.
+#[cfg(feature = "advisory")]
+pub mod advisory;
+
+// Arbitrary version numbers for property testing.
+#[cfg(all(feature = "std", any(test, feature = "proptest")))]
+pub mod arbitrary;
+
+// Borrowed version numbers.
+pub mod borrowed;
+
+// Version number builder.
+pub mod builder;
+
+// Calendar version numbers.
+#[cfg(feature = "std")]
+pub mod calver;
+
+// C ABI for other languages.
+#[cfg(feature = "capi")]
+pub mod capi;
+
+// Cargo version requirements.
+#[cfg(feature = "std")]
+pub mod cargo;
+
+// Pre-release channels.
+pub mod channel;
+
+// Compact version numbers.
+pub mod compact;
+
+// Composer version constraints.
+#[cfg(feature = "std")]
+pub mod composer;
+
+// Conversion between versioning schemes.
+#[cfg(feature = "std")]
+pub mod convert;
+
+// Debian version numbers.
+#[cfg(feature = "std")]
+pub mod debian;
+
+// Go module version numbers.
+#[cfg(feature = "std")]
+pub mod golang;
+
+// Version number iterator adapters.
+pub mod iter;
+
+// Maven version numbers and ranges.
+#[cfg(feature = "std")]
+pub mod maven;
+
+// npm version ranges.
+#[cfg(feature = "std")]
+pub mod npm;
+
+// NuGet version numbers.
+#[cfg(feature = "std")]
+pub mod nuget;
+
+// Python (PEP 440) version numbers.
+#[cfg(feature = "std")]
+pub mod pep440;
+
+// Python bindings.
+#[cfg(feature = "python")]
+pub mod python;
+
+// RPM version numbers.
+#[cfg(feature = "std")]
+pub mod rpm;
+
+// Version number scanning.
+#[cfg(feature = "std")]
+pub mod scan;
+
+// Versioning scheme abstraction.
+pub mod scheme;
+
+// WebAssembly bindings.
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+// Workspace release planning.
+#[cfg(feature = "std")]
+pub mod workspace;
@@ -0,0 +1,635 @@
+//! Creates, parses, and compares Maven version numbers and ranges.
+//!
+//! The **maven** module provides you with a `Version` that is ordered the
+//! same way as Maven's `ComparableVersion`, which is also used by Gradle. A
+//! version number is split into numbers and qualifiers, and the well known
+//! qualifiers are ordered like this:
+//!
+//! `alpha` < `beta` < `milestone` < `rc` < `snapshot` < `""` < `sp`
+//!
+//! The release qualifiers `ga`, `final`, and `release` are the same as no
+//! qualifier (`""`), and `cr` is the same as `rc`. Any other qualifier comes
+//! after `sp`, in alphabetical order.
+//!
+//! ```
+//! use recital::maven::Version;
+//!
+//! let a: Version = "1.0-alpha-1".parse().unwrap();
+//! let b: Version = "1.0-SNAPSHOT".parse().unwrap();
+//! let c: Version = "1.0.0.Final".parse().unwrap();
+//! let d: Version = "1.0-sp1".parse().unwrap();
+//!
+//! assert!(a < b && b < c && c < d);
+//! assert_eq!(c, "1".parse().unwrap());
+//! ```
+//!
+//! Version ranges are parsed into a `Range`, which can be used like any
+//! other constraint.
+//!
+//! ```
+//! use recital::maven::{Range, Version};
+//! use recital::resolve::Constraint;
+//!
+//! let range: Range = "(,1.5],[2.0,)".parse().unwrap();
+//!
+//! assert!(range.allows(&"1.5".parse::<Version>().unwrap()));
+//! assert!(!range.allows(&"1.6".parse::<Version>().unwrap()));
+//! assert!(range.allows(&"2.0-sp1".parse::<Version>().unwrap()));
+//! ```
+//!
+//! | Range           | Meaning                                        |
+//! |-----------------|------------------------------------------------|
+//! | `1.0`           | any version number, but `1.0` is recommended   |
+//! | `[1.0]`         | exactly `1.0`                                  |
+//! | `[1.0,2.0)`     | `>=1.0 <2.0`                                   |
+//! | `(1.0,2.0]`     | `>1.0 <=2.0`                                   |
+//! | `(,1.0]`        | `<=1.0`                                        |
+//! | `[1.5,)`        | `>=1.5`                                        |
+//! | `(,1.0],[1.2,)` | `<=1.0` or `>=1.2`                             |
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use super::resolve::{Constraint, Constraints, Operation};
+use super::scheme::{bump_dotted, Bump, VersionScheme};
+use super::version::{self, Identifier};
+
+/// The well known qualifiers, in order.
+const QUALIFIERS: [&str; 7] = ["alpha", "beta", "milestone", "rc", "snapshot", "", "sp"];
+
+/// The sort key of the release qualifier (`""`).
+const RELEASE: &str = "5";
+
+/// Represents an item in a parsed version number.
+#[derive(Clone, Debug)]
+enum Item {
+    /// A number, without leading zeros (so zero is empty).
+    Int(String),
+
+    /// The sort key of a qualifier.
+    Str(String),
+
+    /// The items after a hyphen, or after a change between letters and digits.
+    List(Vec<Item>),
+}
+
+impl Item {
+    /// Creates a qualifier item, which is an alias if it is followed by a digit.
+    fn qualifier(value: &str, followed_by_digit: bool) -> Item {
+        let value = match value {
+            "a" if followed_by_digit => "alpha",
+            "b" if followed_by_digit => "beta",
+            "m" if followed_by_digit => "milestone",
+            "ga" | "final" | "release" => "",
+            "cr" => "rc",
+            _ => value,
+        };
+
+        Item::Str(match QUALIFIERS.iter().position(|q| *q == value) {
+            Some(index) => index.to_string(),
+            None => format!("{}-{}", QUALIFIERS.len(), value),
+        })
+    }
+
+    /// Creates a number or qualifier item.
+    fn parse(value: &str, digit: bool) -> Item {
+        if digit {
+            Item::Int(value.trim_start_matches('0').to_string())
+        } else {
+            Item::qualifier(value, false)
+        }
+    }
+
+    /// Checks if the item is the same as having nothing at all.
+    fn is_null(&self) -> bool {
+        match *self {
+            Item::Int(ref n) => n.is_empty(),
+            Item::Str(ref key) => key == RELEASE,
+            Item::List(ref items) => items.is_empty(),
+        }
+    }
+
+    /// Compares the item with another, or with nothing.
+    fn compare(&self, other: Option<&Item>) -> Ordering {
+        match (self, other) {
+            (Item::Int(n), None) => {
+                if n.is_empty() {
+                    Ordering::Equal
+                } else {
+                    Ordering::Greater
+                }
+            }
+            (Item::Str(key), None) => key.as_str().cmp(RELEASE),
+            (Item::List(items), None) => {
+                items.first().map(|i| i.compare(None)).unwrap_or(Ordering::Equal)
+            }
+            (Item::Int(a), Some(Item::Int(b))) => a.len().cmp(&b.len()).then(a.cmp(b)),
+            (Item::Int(_), Some(_)) => Ordering::Greater,
+            (Item::Str(a), Some(Item::Str(b))) => a.cmp(b),
+            (Item::Str(_), Some(_)) => Ordering::Less,
+            (Item::List(a), Some(Item::List(b))) => compare_lists(a, b),
+            (Item::List(_), Some(Item::Int(_))) => Ordering::Less,
+            (Item::List(_), Some(Item::Str(_))) => Ordering::Greater,
+        }
+    }
+}
+
+/// Compares two lists of items, where missing items are nothing.
+fn compare_lists(a: &[Item], b: &[Item]) -> Ordering {
+    for i in 0..a.len().max(b.len()) {
+        let ordering = match (a.get(i), b.get(i)) {
+            (Some(x), y) => x.compare(y),
+            (None, Some(y)) => y.compare(None).reverse(),
+            (None, None) => Ordering::Equal,
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    Ordering::Equal
+}
+
+/// Removes the trailing items that are the same as nothing.
+///
+/// Lists are skipped over, so `1.0-beta` becomes `1-beta`.
+fn normalize(items: &mut Vec<Item>) {
+    for i in (0..items.len()).rev() {
+        if items[i].is_null() {
+            items.remove(i);
+        } else if let Item::List(_) = items[i] {
+            continue;
+        } else {
+            break;
+        }
+    }
+}
+
+/// Parses a version number into its items.
+fn parse_items(s: &str) -> Vec<Item> {
+    let s = s.to_lowercase();
+    let mut stack: Vec<Vec<Item>> = vec![Vec::new()];
+    let mut start = 0;
+    let mut digit = false;
+
+    for (i, c) in s.char_indices() {
+        if c == '.' || c == '-' {
+            let item = if i == start {
+                Item::Int(String::new())
+            } else {
+                Item::parse(&s[start..i], digit)
+            };
+
+            stack.last_mut().unwrap().push(item);
+            start = i + 1;
+
+            if c == '-' {
+                stack.push(Vec::new());
+            }
+        } else if c.is_ascii_digit() {
+            if !digit && i > start {
+                stack.last_mut().unwrap().push(Item::qualifier(&s[start..i], true));
+                start = i;
+                stack.push(Vec::new());
+            }
+
+            digit = true;
+        } else {
+            if digit && i > start {
+                stack.last_mut().unwrap().push(Item::parse(&s[start..i], true));
+                start = i;
+                stack.push(Vec::new());
+            }
+
+            digit = false;
+        }
+    }
+
+    if s.len() > start {
+        stack.last_mut().unwrap().push(Item::parse(&s[start..], digit));
+    }
+
+    while stack.len() > 1 {
+        let mut items = stack.pop().unwrap();
+
+        normalize(&mut items);
+        stack.last_mut().unwrap().push(Item::List(items));
+    }
+
+    let mut items = stack.pop().unwrap();
+
+    normalize(&mut items);
+
+    items
+}
+
+/// Checks if any qualifier in the items comes before a release.
+fn has_prerelease(items: &[Item]) -> bool {
+    items.iter().any(|item| match *item {
+        Item::Str(ref key) => key.as_str() < RELEASE,
+        Item::List(ref items) => has_prerelease(items),
+        Item::Int(_) => false,
+    })
+}
+
+/// Represents a Maven version number.
+///
+/// The original string is kept for display, while ordering and equality use
+/// the parsed items, so `1.0` and `1.0.0.Final` are equal.
+#[derive(Clone, Debug)]
+pub struct Version {
+    /// The version number as it was given.
+    original: String,
+
+    /// The parsed items.
+    items: Vec<Item>,
+}
+
+impl Version {
+    /// Converts a semantic version number into a Maven version number.
+    ///
+    /// A pre-release must start with one of the qualifiers that come before a
+    /// release (`alpha`, `beta`, `milestone`, `rc`, or `snapshot`), in lower
+    /// case, and can be followed by numbers that do not end with zero (`0`).
+    /// Together, these rules keep the order of the version numbers. Build
+    /// metadata is dropped.
+    pub fn from_semver(semver: &version::Version) -> Result<Version, String> {
+        let unsupported = || format!("Unsupported pre-release for Maven: {}", semver);
+        let mut string = format!("{}.{}.{}", semver.major, semver.minor, semver.patch);
+
+        if let Some((first, rest)) = semver.pre.split_first() {
+            match *first {
+                Identifier::Alpha(ref q) if QUALIFIERS[..5].contains(&q.as_str()) => {}
+                _ => return Err(unsupported()),
+            }
+
+            for identifier in rest {
+                match *identifier {
+                    Identifier::Number(n) if n > 0 || identifier != rest.last().unwrap() => {}
+                    _ => return Err(unsupported()),
+                }
+            }
+
+            string.push('-');
+            string.push_str(&semver.pre
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<String>>()
+                .join("."));
+        }
+
+        string.parse()
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.original)
+    }
+}
+
+/// Enables parsing of strings as Maven version numbers.
+///
+/// Almost any string is a valid version number, except for empty strings and
+/// those with whitespace or the characters used by ranges.
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Version, String> {
+        let s = s.trim();
+
+        if s.is_empty() || s.contains(|c: char| c.is_whitespace() || "[](),".contains(c)) {
+            return Err(format!("Invalid version number: {}", s));
+        }
+
+        Ok(Version {
+            original: s.to_string(),
+            items: parse_items(s),
+        })
+    }
+}
+
+impl Eq for Version {}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Version) -> Ordering {
+        compare_lists(&self.items, &other.items)
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Version) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Version) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl VersionScheme for Version {
+    /// Increments the leading numbers, and removes any qualifiers.
+    fn bump(&mut self, level: Bump) {
+        *self = bump_dotted(&self.original, level)
+            .parse()
+            .expect("A dotted number is a valid version number.");
+    }
+
+    /// A version number is stable if none of its qualifiers come before a
+    /// release (`SNAPSHOT`, `alpha`, and so on).
+    fn is_stable(&self) -> bool {
+        !has_prerelease(&self.items)
+    }
+}
+
+/// Represents a Maven version range.
+pub struct Range {
+    /// The restrictions, of which at least one must be satisfied.
+    pub constraints: Constraints<Version>,
+
+    /// The version number of a soft requirement (e.g. `1.0`).
+    pub recommended: Option<Version>,
+}
+
+impl Constraint<Version> for Range {
+    fn allows(&self, version: &Version) -> bool {
+        self.constraints.allows(version)
+    }
+}
+
+/// Enables parsing of strings as Maven version ranges.
+///
+/// A bare version number is a soft requirement, which allows any version
+/// number. Otherwise, the string is a comma separated list of restrictions,
+/// which must be given in order and cannot overlap.
+impl FromStr for Range {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Range, String> {
+        let invalid = |reason: &str| format!("Invalid version range ({}): {}", reason, s);
+        let mut rest = s.trim();
+
+        if !rest.starts_with(['[', '(']) {
+            return Ok(Range {
+                constraints: Constraints::And(Vec::new()),
+                recommended: Some(rest.parse()?),
+            });
+        }
+
+        let mut restrictions: Vec<Box<dyn Constraint<Version>>> = Vec::new();
+        let mut previous: Option<Version> = None;
+
+        loop {
+            let close = rest.find([']', ')']).ok_or_else(|| invalid("not closed"))?;
+            let inclusive = (rest.starts_with('['), rest[close..].starts_with(']'));
+            let inner = &rest[1..close];
+            let bound = |s: &str| -> Result<Option<Version>, String> {
+                let s = s.trim();
+
+                if s.is_empty() {
+                    Ok(None)
+                } else {
+                    s.parse().map(Some)
+                }
+            };
+
+            let (lower, upper) = match inner.find(',') {
+                None => {
+                    if inclusive != (true, true) {
+                        return Err(invalid("a single version number must use brackets"));
+                    }
+
+                    let version = bound(inner)?.ok_or_else(|| invalid("empty restriction"))?;
+
+                    (Some(version.clone()), Some(version))
+                }
+
+                Some(comma) => (bound(&inner[..comma])?, bound(&inner[comma + 1..])?),
+            };
+
+            if let (Some(lower), Some(upper)) = (&lower, &upper) {
+                if lower > upper || (lower == upper && inclusive != (true, true)) {
+                    return Err(invalid("the lower bound is above the upper bound"));
+                }
+            }
+
+            match (&previous, &lower) {
+                (Some(_), None) => return Err(invalid("ranges overlap")),
+                (Some(previous), Some(lower)) if lower < previous => {
+                    return Err(invalid("ranges overlap"))
+                }
+                _ => {}
+            }
+
+            let mut operations: Vec<Box<dyn Constraint<Version>>> = Vec::new();
+
+            if lower.is_some() && lower == upper {
+                operations.push(Box::new(Operation::Exactly(lower.clone().unwrap())));
+            } else {
+                if let Some(ref lower) = lower {
+                    operations.push(Box::new(if inclusive.0 {
+                        Operation::GreaterThanOrEqualTo(lower.clone())
+                    } else {
+                        Operation::GreaterThan(lower.clone())
+                    }));
+                }
+
+                if let Some(ref upper) = upper {
+                    operations.push(Box::new(if inclusive.1 {
+                        Operation::LessThanOrEqualTo(upper.clone())
+                    } else {
+                        Operation::LessThan(upper.clone())
+                    }));
+                }
+            }
+
+            restrictions.push(Box::new(Constraints::And(operations)));
+
+            previous = match upper {
+                Some(upper) => Some(upper),
+                None if close + 1 < rest.len() => return Err(invalid("ranges overlap")),
+                None => None,
+            };
+
+            rest = rest[close + 1..].trim_start();
+
+            if rest.is_empty() {
+                break;
+            }
+
+            if !rest.starts_with(',') {
+                return Err(invalid("expected a comma"));
+            }
+
+            rest = rest[1..].trim_start();
+
+            if !rest.starts_with(['[', '(']) {
+                return Err(invalid("expected a restriction"));
+            }
+        }
+
+        Ok(Range {
+            constraints: Constraints::Or(restrictions),
+            recommended: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Range, Version};
+    use super::super::resolve::{resolve, Constraint};
+    use super::super::scheme::{Bump, VersionScheme};
+
+    fn check_order(versions: &[&str]) {
+        let versions: Vec<Version> = versions.iter().map(|s| s.parse().unwrap()).collect();
+
+        for (i, a) in versions.iter().enumerate() {
+            for b in &versions[i + 1..] {
+                assert!(a < b, "{} < {}", a, b);
+                assert!(b > a, "{} > {}", b, a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_qualifier_order() {
+        check_order(&["1-alpha2snapshot", "1-alpha2", "1-alpha-123", "1-beta-2", "1-beta123",
+                      "1-m2", "1-m11", "1-rc", "1-cr2", "1-rc123", "1-SNAPSHOT", "1", "1-sp",
+                      "1-sp2", "1-sp123", "1-abc", "1-def", "1-pom-1", "1-1-snapshot", "1-1",
+                      "1-2", "1-123"]);
+    }
+
+    #[test]
+    fn test_number_order() {
+        check_order(&["2.0", "2-1", "2.0.a", "2.0.0.a", "2.0.2", "2.0.123", "2.1.0", "2.1-a",
+                      "2.1b", "2.1-c", "2.1-1", "2.1.0.1", "2.2", "2.123", "11.a2", "11.a11",
+                      "11.b2", "11.b11", "11.m2", "11.m11", "11", "11.a", "11b", "11c", "11m"]);
+    }
+
+    #[test]
+    fn test_equality() {
+        for group in &[&["1", "1.0", "1.0.0", "1-0", "1.0-0", "1.0.0.Final", "1-ga", "1-release",
+                         "1final"][..],
+                       &["1a", "1-a", "1.0-a", "1.0.0-a", "1.0.0.0.0.0-a"],
+                       &["1cr", "1rc", "1-CR", "1.0-RC"],
+                       &["1.0.01", "1.0.1"]] {
+            let first: Version = group[0].parse().unwrap();
+
+            for s in group.iter() {
+                assert_eq!(first, s.parse().unwrap(), "{} == {}", group[0], s);
+            }
+        }
+
+        assert_eq!("1.0.0.Final", "1.0.0.Final".parse::<Version>().unwrap().to_string());
+        assert!("".parse::<Version>().is_err());
+        assert!("1.0 beta".parse::<Version>().is_err());
+        assert!("[1.0]".parse::<Version>().is_err());
+    }
+
+    #[test]
+    fn test_scheme() {
+        let mut version: Version = "1.4-SNAPSHOT".parse().unwrap();
+
+        assert!(!version.is_stable());
+
+        version.bump(Bump::Minor);
+
+        assert_eq!("1.5", version.to_string());
+        assert!(version.is_stable());
+        assert!("1.0.0.Final".parse::<Version>().unwrap().is_stable());
+        assert!("1.0-sp1".parse::<Version>().unwrap().is_stable());
+        assert!(!"1.0-beta-1".parse::<Version>().unwrap().is_stable());
+    }
+
+    #[test]
+    fn test_range() {
+        let range: Range = "[1.0,2.0)".parse().unwrap();
+
+        assert!(range.allows(&"1.0".parse().unwrap()));
+        assert!(range.allows(&"1.9.9".parse().unwrap()));
+        assert!(range.allows(&"2.0-SNAPSHOT".parse().unwrap()));
+        assert!(!range.allows(&"2.0".parse().unwrap()));
+        assert!(!range.allows(&"1.0-rc1".parse().unwrap()));
+
+        let range: Range = "(,1.5],[2.0,)".parse().unwrap();
+
+        assert!(range.allows(&"0.1".parse().unwrap()));
+        assert!(range.allows(&"1.5".parse().unwrap()));
+        assert!(!range.allows(&"1.6".parse().unwrap()));
+        assert!(range.allows(&"2.0".parse().unwrap()));
+
+        let range: Range = "[1.5]".parse().unwrap();
+
+        assert!(range.allows(&"1.5.0".parse().unwrap()));
+        assert!(!range.allows(&"1.5.1".parse().unwrap()));
+
+        let range: Range = "(1.0, 1.2]".parse().unwrap();
+
+        assert!(!range.allows(&"1.0".parse().unwrap()));
+        assert!(range.allows(&"1.2".parse().unwrap()));
+
+        let range: Range = "1.0".parse().unwrap();
+
+        assert_eq!(Some("1.0".parse::<Version>().unwrap()), range.recommended);
+        assert!(range.allows(&"3.0".parse().unwrap()));
+
+        let versions: Vec<Version> = ["1.0", "1.1", "1.6", "2.0"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let range: Range = "[1.1,1.6),[2.0]".parse().unwrap();
+
+        assert_eq!(resolve(&versions, &range.constraints),
+                   vec![versions[1].clone(), versions[3].clone()]);
+
+        let range: Range = "[1.0,2.0),[2.0,3.0)".parse().unwrap();
+
+        assert!(range.allows(&"1.5".parse().unwrap()));
+        assert!(range.allows(&"2.0".parse().unwrap()));
+        assert!(!range.allows(&"3.0".parse().unwrap()));
+
+        for s in &["[1.0", "[1.0)", "[]", "[2.0,1.0]", "(1.0,1.0]", "[1.0,2.0],[1.5,)",
+                   "[2.0,),[3.0,)", "[1.0,2.0)x", "[1.0,2.0),", "[1.0,2.0) [3.0,)"] {
+            assert!(s.parse::<Range>().is_err(), "{} should be invalid", s);
+        }
+    }
+
+    #[test]
+    fn test_from_semver() {
+        let ordered = ["1.0.0-alpha",
+                       "1.0.0-alpha.1",
+                       "1.0.0-alpha.2",
+                       "1.0.0-alpha.10",
+                       "1.0.0-beta",
+                       "1.0.0-beta.0.1",
+                       "1.0.0-milestone.1",
+                       "1.0.0-rc.1",
+                       "1.0.0-snapshot",
+                       "1.0.0",
+                       "1.0.1",
+                       "1.10.0"];
+
+        for pair in ordered.windows(2) {
+            let a = Version::from_semver(&pair[0].parse().unwrap()).unwrap();
+            let b = Version::from_semver(&pair[1].parse().unwrap()).unwrap();
+
+            assert!(a < b, "{} < {}", a, b);
+        }
+
+        assert_eq!("1.0.0-rc.1",
+                   Version::from_semver(&"1.0.0-rc.1+build.5".parse().unwrap())
+                       .unwrap()
+                       .to_string());
+
+        for s in &["1.0.0-1", "1.0.0-foo", "1.0.0-SNAPSHOT", "1.0.0-rc.0", "1.0.0-rc.x"] {
+            assert!(Version::from_semver(&s.parse().unwrap()).is_err(),
+                    "{} should not convert",
+                    s);
+        }
+    }
+
+}
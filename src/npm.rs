@@ -0,0 +1,412 @@
+//! Parses and evaluates version ranges the way npm does.
+//!
+//! The **npm** module provides you with a `Range`, which is created from the
+//! range strings found in `package.json` files. The grammar is the one used
+//! by [node-semver][], and each range is compiled into a tree of `Operation`
+//! constraints with the same meaning that npm gives them.
+//!
+//! [node-semver]: https://github.com/npm/node-semver
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate recital;
+//! use recital::npm::Range;
+//! use recital::resolve::Constraint;
+//!
+//! # fn main() {
+//! let range: Range = "1.2.7 || >=1.2.9 <2.0.0".parse().unwrap();
+//!
+//! assert!(range.allows(&version!(1, 2, 7)));
+//! assert!(!range.allows(&version!(1, 2, 8)));
+//! assert!(range.allows(&version!(1, 4, 6)));
+//! # }
+//! ```
+//!
+//! The following syntax is supported:
+//!
+//! | Range             | Equivalent                |
+//! |-------------------|---------------------------|
+//! | `1.2.3`           | `=1.2.3`                  |
+//! | `1.2.3 - 2.3.4`   | `>=1.2.3 <=2.3.4`         |
+//! | `1.2.3 - 2.3`     | `>=1.2.3 <2.4.0-0`        |
+//! | `*`, `""`         | any version number        |
+//! | `1.x`, `1`        | `>=1.0.0 <2.0.0-0`        |
+//! | `1.2.*`           | `>=1.2.0 <1.3.0-0`        |
+//! | `~1.2.3`          | `>=1.2.3 <1.3.0-0`        |
+//! | `~1`              | `>=1.0.0 <2.0.0-0`        |
+//! | `^1.2.3`          | `>=1.2.3 <2.0.0-0`        |
+//! | `^0.2.3`          | `>=0.2.3 <0.3.0-0`        |
+//! | `^0.0.3`          | `>=0.0.3 <0.0.4-0`        |
+//! | `^0.0`            | `>=0.0.0 <0.1.0-0`        |
+//! | `a \|\| b`        | either `a` or `b`         |
+//!
+//! Pre-release version numbers are only allowed by a set of comparators if
+//! at least one of them is a pre-release of the same major, minor, and patch
+//! version numbers. This can be turned off with the `includePrerelease`
+//! option, which is available through `Range::parse`.
+use std::str::FromStr;
+use super::parser::{parse_partial, Partial};
+use super::resolve::{Constraint, Constraints, Operation};
+use super::resolve::Operation::*;
+use super::version::{next_floor, Version};
+
+/// Represents a set of comparators that must all be satisfied.
+///
+/// A set is one side of a `||` in a range.
+pub struct Set {
+    /// The constraints that all must be satisfied.
+    pub constraints: Constraints,
+
+    /// The version numbers whose pre-releases are allowed.
+    pub prerelease: Vec<Version>,
+
+    /// Whether all pre-releases are allowed.
+    pub include_prerelease: bool,
+}
+
+impl Constraint for Set {
+    fn allows(&self, version: &Version) -> bool {
+        if !self.constraints.allows(version) {
+            return false;
+        }
+
+        self.include_prerelease || version.pre.is_empty() ||
+        self.prerelease.iter().any(|p| {
+            p.major == version.major && p.minor == version.minor && p.patch == version.patch
+        })
+    }
+}
+
+/// Represents an npm version range.
+pub struct Range {
+    /// The sets of comparators, of which at least one must be satisfied.
+    pub constraints: Constraints,
+}
+
+impl Constraint for Range {
+    fn allows(&self, version: &Version) -> bool {
+        self.constraints.allows(version)
+    }
+}
+
+/// Enables parsing of strings as npm version ranges.
+///
+/// This is the same as calling `Range::parse` without `includePrerelease`.
+///
+/// ```
+/// # use recital::npm::Range;
+/// let range: Range = "^1.2.3 || ~2.0".parse().unwrap();
+/// ```
+impl FromStr for Range {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Range, String> {
+        Range::parse(s, false)
+    }
+}
+
+impl Range {
+    /// Parses a string as an npm version range.
+    ///
+    /// When `include_prerelease` is `true`, pre-release version numbers are
+    /// allowed by any range that they satisfy. The lower bounds of partial
+    /// version numbers and wildcards (such as `1.2` or `^1.x`) then also
+    /// include pre-releases, but those of complete version numbers do not.
+    pub fn parse(s: &str, include_prerelease: bool) -> Result<Range, String> {
+        let mut sets: Vec<Box<dyn Constraint>> = Vec::new();
+
+        for set in s.split("||") {
+            sets.push(Box::new(parse_set(set, include_prerelease)
+                .map_err(|_| format!("Invalid range: {}", s))?));
+        }
+
+        Ok(Range { constraints: Constraints::Or(sets) })
+    }
+}
+
+/// Creates a version number that comes before any pre-release of another.
+fn floor(major: u64, minor: u64, patch: u64) -> Version {
+    version!(major, minor, patch, vec![id!(0)])
+}
+
+/// Returns a lower bound, which also allows pre-releases if requested and the
+/// bound comes from a partial version number.
+fn lower(mut version: Version, partial: &Partial, include_prerelease: bool) -> Version {
+    version.build.clear();
+
+    if include_prerelease && partial.patch.is_none() && version.pre.is_empty() {
+        version.pre.push(id!(0));
+    }
+
+    version
+}
+
+/// Parses a version number with an optional `v` or `=` prefix.
+fn parse_version(s: &str) -> Result<Partial, String> {
+    let s = s.trim_start_matches('=').trim_start_matches('v');
+
+    parse_partial(s)
+}
+
+/// Parses one side of a `||` as a set of comparators.
+fn parse_set(s: &str, include_prerelease: bool) -> Result<Set, String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut pending = String::new();
+
+    for token in s.split_whitespace() {
+        pending.push_str(token);
+
+        if !["<", ">", "<=", ">=", "=", "~", "~>", "^"].contains(&token) {
+            tokens.push(pending.clone());
+            pending.clear();
+        }
+    }
+
+    if !pending.is_empty() {
+        return Err(format!("Invalid range: {}", s));
+    }
+
+    let mut operations = Vec::new();
+    let mut prerelease = Vec::new();
+
+    if tokens.len() == 3 && tokens[1] == "-" {
+        let from = parse_version(&tokens[0])?;
+        let to = parse_version(&tokens[2])?;
+
+        if from.major.is_some() {
+            operations.push(GreaterThanOrEqualTo(lower(from.to_version(),
+                                                       &from,
+                                                       include_prerelease)));
+        }
+
+        if to.patch.is_some() {
+            operations.push(LessThanOrEqualTo(to.to_version()));
+        } else {
+            operations.extend(to.next_floor().map(LessThan));
+        }
+
+        for partial in &[from, to] {
+            if !partial.pre.is_empty() {
+                prerelease.push(partial.to_version());
+            }
+        }
+    } else {
+        for token in &tokens {
+            let (op, rest) = split_op(token);
+            let partial = parse_version(rest)?;
+
+            if !partial.pre.is_empty() {
+                prerelease.push(partial.to_version());
+            }
+
+            expand(op, &partial, include_prerelease, &mut operations);
+        }
+    }
+
+    let mut constraints: Vec<Box<dyn Constraint>> = Vec::new();
+
+    for operation in operations {
+        constraints.push(Box::new(operation));
+    }
+
+    Ok(Set {
+        constraints: Constraints::And(constraints),
+        prerelease,
+        include_prerelease,
+    })
+}
+
+/// Adds the operations for a single comparator.
+fn expand(op: &str, partial: &Partial, include: bool, operations: &mut Vec<Operation>) {
+    let (major, minor, patch) = match partial.major {
+        Some(major) => (major, partial.minor, partial.patch),
+        None => {
+            // Only `<*` and `>*` do not allow every version number.
+            if op == "<" || op == ">" {
+                operations.push(LessThan(floor(0, 0, 0)));
+            }
+
+            return;
+        }
+    };
+
+    let version = partial.to_version();
+    let complete = patch.is_some();
+    let upper = partial.next_floor();
+
+    match op {
+        "" | "=" if complete => operations.push(Exactly(version)),
+        "" | "=" => {
+            operations.push(GreaterThanOrEqualTo(lower(version, partial, include)));
+            operations.extend(upper.map(LessThan));
+        }
+
+        ">" if complete => operations.push(GreaterThan(version)),
+        ">" => {
+            match upper {
+                Some(mut next) => {
+                    next.pre.clear();
+                    operations.push(GreaterThanOrEqualTo(lower(next, partial, include)));
+                }
+
+                None => operations.push(GreaterThan(version!(u64::MAX, u64::MAX, u64::MAX))),
+            }
+        }
+
+        ">=" => operations.push(GreaterThanOrEqualTo(lower(version, partial, include))),
+        "<" if complete => operations.push(LessThan(version)),
+        "<" => operations.push(LessThan(floor(major, minor.unwrap_or(0), 0))),
+        "<=" if complete => operations.push(LessThanOrEqualTo(version)),
+        "<=" => operations.extend(upper.map(LessThan)),
+
+        "~" | "~>" => {
+            let upper = match minor {
+                Some(minor) => next_floor(major, minor, u64::MAX),
+                None => next_floor(major, u64::MAX, u64::MAX),
+            };
+
+            operations.push(GreaterThanOrEqualTo(lower(version, partial, include)));
+            operations.extend(upper.map(LessThan));
+        }
+
+        _ => {
+            let upper = match (major, minor, patch) {
+                (0, Some(0), Some(patch)) => next_floor(0, 0, patch),
+                (0, Some(minor), _) => next_floor(0, minor, u64::MAX),
+                (major, _, _) => next_floor(major, u64::MAX, u64::MAX),
+            };
+
+            operations.push(GreaterThanOrEqualTo(lower(version, partial, include)));
+            operations.extend(upper.map(LessThan));
+        }
+    }
+}
+
+/// Splits the operator from the beginning of a comparator.
+fn split_op(comparator: &str) -> (&str, &str) {
+    for op in &[">=", "<=", "~>", ">", "<", "~", "^", "="] {
+        if let Some(rest) = comparator.strip_prefix(op) {
+            return (op, rest);
+        }
+    }
+
+    ("", comparator)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Range;
+    use super::super::resolve::Constraint;
+
+    #[test]
+    fn test_hyphen() {
+        check_allows!(Range::parse("1.2.3 - 2.3.4", false), ["1.2.3", "2.3.4"], ["1.2.2", "2.3.5"]);
+        check_allows!(Range::parse("1.2 - 2.3.4", false), ["1.2.0"], ["1.1.9"]);
+        check_allows!(Range::parse("1.2.3 - 2.3", false), ["2.3.9"], ["2.4.0", "2.4.0-alpha"]);
+        check_allows!(Range::parse("1.2.3 - 2", false), ["2.9.9"], ["3.0.0"]);
+    }
+
+    #[test]
+    fn test_x_range() {
+        check_allows!(Range::parse("*", false), ["0.0.0", "9.9.9"], ["1.0.0-alpha"]);
+        check_allows!(Range::parse("", false), ["0.0.0", "9.9.9"], []);
+        check_allows!(Range::parse("1.x", false), ["1.0.0", "1.9.9"], ["0.9.9", "2.0.0"]);
+        check_allows!(Range::parse("1", false), ["1.0.0", "1.9.9"], ["0.9.9", "2.0.0"]);
+        check_allows!(Range::parse("1.2.*", false), ["1.2.0", "1.2.9"], ["1.1.9", "1.3.0"]);
+        check_allows!(Range::parse("1.2", false), ["1.2.0", "1.2.9"], ["1.1.9", "1.3.0"]);
+        check_allows!(Range::parse("1.2.3", false), ["1.2.3"], ["1.2.4"]);
+        check_allows!(Range::parse("v1.2.3", false), ["1.2.3"], ["1.2.4"]);
+    }
+
+    #[test]
+    fn test_tilde() {
+        check_allows!(Range::parse("~1.2.3", false), ["1.2.3", "1.2.9"], ["1.2.2", "1.3.0"]);
+        check_allows!(Range::parse("~1.2", false), ["1.2.0", "1.2.9"], ["1.3.0"]);
+        check_allows!(Range::parse("~1", false), ["1.0.0", "1.9.9"], ["2.0.0"]);
+        check_allows!(Range::parse("~0.2.3", false), ["0.2.3", "0.2.9"], ["0.3.0"]);
+        check_allows!(Range::parse("~>1.2.3", false), ["1.2.9"], ["1.3.0"]);
+        check_allows!(Range::parse("~1.2.3-beta.2", false),
+                      ["1.2.3-beta.2", "1.2.3-beta.4", "1.2.4"],
+                      ["1.2.3-beta.1", "1.2.4-beta.2"]);
+    }
+
+    #[test]
+    fn test_caret() {
+        check_allows!(Range::parse("^1.2.3", false), ["1.2.3", "1.9.9"], ["1.2.2", "2.0.0"]);
+        check_allows!(Range::parse("^0.2.3", false), ["0.2.3", "0.2.9"], ["0.3.0"]);
+        check_allows!(Range::parse("^0.0.3", false), ["0.0.3"], ["0.0.4"]);
+        check_allows!(Range::parse("^1.2.x", false), ["1.2.0", "1.9.9"], ["2.0.0"]);
+        check_allows!(Range::parse("^0.0.x", false), ["0.0.0", "0.0.9"], ["0.1.0"]);
+        check_allows!(Range::parse("^0.0", false), ["0.0.0", "0.0.9"], ["0.1.0"]);
+        check_allows!(Range::parse("^1.x", false), ["1.0.0"], ["2.0.0"]);
+        check_allows!(Range::parse("^0.x", false), ["0.0.0", "0.9.9"], ["1.0.0"]);
+        check_allows!(Range::parse("^1.2.3-beta.2", false),
+                      ["1.2.3-beta.4", "1.9.9"],
+                      ["1.2.3-beta.1", "1.2.4-beta.2"]);
+    }
+
+    #[test]
+    fn test_primitive() {
+        check_allows!(Range::parse(">1.2.3", false), ["1.2.4"], ["1.2.3"]);
+        check_allows!(Range::parse(">1.2", false), ["1.3.0"], ["1.2.9"]);
+        check_allows!(Range::parse(">1", false), ["2.0.0"], ["1.9.9"]);
+        check_allows!(Range::parse(">= 1.2", false), ["1.2.0"], ["1.1.9"]);
+        check_allows!(Range::parse("<1.2", false), ["1.1.9"], ["1.2.0"]);
+        check_allows!(Range::parse("<=1.2", false), ["1.2.9"], ["1.3.0"]);
+        check_allows!(Range::parse("=1.2.3", false), ["1.2.3"], ["1.2.4"]);
+        check_allows!(Range::parse(">*", false), [], ["0.0.0", "1.0.0"]);
+    }
+
+    #[test]
+    fn test_union() {
+        check_allows!(Range::parse("1.2.7 || >=1.2.9 <2.0.0", false),
+                      ["1.2.7", "1.2.9", "1.4.6"],
+                      ["1.2.8", "2.0.0"]);
+
+        check_allows!(Range::parse("<1.0.0 || >=2.0.0", false), ["0.9.9", "2.0.0"], ["1.0.0"]);
+    }
+
+    #[test]
+    fn test_include_prerelease() {
+        check_allows!(Range::parse(">1.2.3-alpha.3", false),
+                      ["1.2.3-alpha.7", "3.4.5"],
+                      ["3.4.5-alpha.9"]);
+        check_allows!(Range::parse(">1.2.3-alpha.3", true), ["1.2.3-alpha.7", "3.4.5-alpha.9"], []);
+        check_allows!(Range::parse("1.x", true), ["1.0.0-0", "1.5.0-beta"], ["2.0.0-0", "0.9.9"]);
+        check_allows!(Range::parse("^1.2.3", true), ["1.9.0-beta"], ["1.2.3-rc.1", "2.0.0-rc.1"]);
+        check_allows!(Range::parse("~1.2.3", true), ["1.2.4-rc.1"], ["1.2.3-rc.1", "1.3.0-rc.1"]);
+        check_allows!(Range::parse("^1.2", true), ["1.2.0-rc.1", "1.9.0-beta"], ["2.0.0-rc.1"]);
+        check_allows!(Range::parse(">=1.2", true), ["1.2.0-rc.1"], ["1.1.9"]);
+        check_allows!(Range::parse("1.2.3 - 2", true), ["2.9.9-rc.1"], ["1.2.3-rc.1", "3.0.0-0"]);
+        check_allows!(Range::parse("1.2 - 2.3.4", true), ["1.2.0-0"], ["2.3.5-0"]);
+        check_allows!(Range::parse("*", true), ["1.0.0-alpha"], []);
+    }
+
+    #[test]
+    fn test_largest() {
+        let max = "18446744073709551615";
+
+        check_allows!(Range::parse(&format!("^{}", max), false), [format!("{0}.{0}.{0}", max)], []);
+        check_allows!(Range::parse(&format!("~1.{}", max), false),
+                      [format!("1.{}.9", max)],
+                      ["2.0.0"]);
+        check_allows!(Range::parse(&format!("^0.0.{}", max), false), [], ["0.1.0"]);
+        check_allows!(Range::parse(&format!("{}.x", max), false),
+                      [format!("{}.1.0", max)],
+                      ["1.0.0"]);
+        check_allows!(Range::parse(&format!(">{}", max), false), [], [format!("{0}.{0}.{0}", max)]);
+        check_allows!(Range::parse(&format!("1.0.0 - {}", max), false),
+                      [format!("{0}.{0}.{0}", max)],
+                      []);
+    }
+
+    #[test]
+    fn test_invalid() {
+        for range in &["1.2.3 >=", "1.*.3", "a.b.c", ">=1.2.3 || foo", "01.2.3", "^1.2.3-rc.01",
+                       ">=v1.02"] {
+            assert!(range.parse::<Range>().is_err(), "{} should be invalid", range);
+        }
+    }
+
+}
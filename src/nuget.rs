@@ -0,0 +1,266 @@
+//! Creates, parses, and compares NuGet version numbers.
+//!
+//! The **nuget** module provides you with a `Version` that follows the rules
+//! used by NuGet. A NuGet version number is like a semantic version number,
+//! with an optional fourth number (the revision), and pre-release labels that
+//! are compared without regard to case.
+//!
+//! ```
+//! use recital::nuget::Version;
+//!
+//! let a: Version = "1.0.0-Beta".parse().unwrap();
+//! let b: Version = "1.0.0-beta".parse().unwrap();
+//! let c: Version = "1.0.0.1".parse().unwrap();
+//!
+//! assert_eq!(a, b);
+//! assert!(b < c);
+//! assert_eq!("1.2.0", "1.2".parse::<Version>().unwrap().to_string());
+//! ```
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use super::scheme::{Bump, VersionScheme};
+use super::version::{unchecked, Identifier};
+
+/// The largest number allowed in a version number.
+pub const MAX_NUMBER: u64 = i32::MAX as u64;
+
+/// Represents a NuGet version number.
+#[derive(Clone, Debug, Default)]
+pub struct Version {
+    /// The major version number.
+    pub major: u64,
+
+    /// The minor version number.
+    pub minor: u64,
+
+    /// The patch version number.
+    pub patch: u64,
+
+    /// The revision number, which is only shown if it is not zero (`0`).
+    pub revision: u64,
+
+    /// The pre-release identifiers.
+    pub pre: Vec<Identifier>,
+
+    /// The build metadata identifiers.
+    pub build: Vec<Identifier>,
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+
+        if self.revision != 0 {
+            write!(f, ".{}", self.revision)?;
+        }
+
+        for (prefix, identifiers) in &[("-", &self.pre), ("+", &self.build)] {
+            if !identifiers.is_empty() {
+                let identifiers: Vec<String> = identifiers.iter().map(|i| i.to_string()).collect();
+
+                write!(f, "{}{}", prefix, identifiers.join("."))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Enables parsing of strings as NuGet version numbers.
+///
+/// One to four numbers may be given, and the missing ones are zero (`0`).
+/// Each number must not be greater than `MAX_NUMBER`.
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Version, String> {
+        let invalid = || format!("Invalid version number: {}", s);
+        let (rest, build) = match s.find('+') {
+            Some(plus) => (&s[..plus], Some(&s[plus + 1..])),
+            None => (s, None),
+        };
+        let (numbers, pre) = match rest.find('-') {
+            Some(hyphen) => (&rest[..hyphen], Some(&rest[hyphen + 1..])),
+            None => (rest, None),
+        };
+
+        let numbers: Vec<u64> = numbers.split('.')
+            .map(|n| {
+                if n.is_empty() || !n.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(invalid());
+                }
+
+                n.parse().ok().filter(|&n| n <= MAX_NUMBER).ok_or_else(invalid)
+            })
+            .collect::<Result<_, _>>()?;
+
+        if numbers.len() > 4 {
+            return Err(invalid());
+        }
+
+        let identifiers = |s: Option<&str>| -> Result<Vec<Identifier>, String> {
+            match s {
+                None => Ok(Vec::new()),
+                Some(s) => {
+                    s.split('.')
+                        .map(|i| {
+                            if i.is_empty() ||
+                               !i.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                                Err(invalid())
+                            } else {
+                                Ok(unchecked(i))
+                            }
+                        })
+                        .collect()
+                }
+            }
+        };
+
+        let number = |i: usize| numbers.get(i).cloned().unwrap_or(0);
+
+        Ok(Version {
+            major: number(0),
+            minor: number(1),
+            patch: number(2),
+            revision: number(3),
+            pre: identifiers(pre)?,
+            build: identifiers(build)?,
+        })
+    }
+}
+
+/// Compares two pre-release identifiers, ignoring the case of letters.
+fn compare_identifiers(a: &Identifier, b: &Identifier) -> Ordering {
+    match (a, b) {
+        (Identifier::Number(a), Identifier::Number(b)) => a.cmp(b),
+        (Identifier::Number(_), Identifier::Alpha(_)) => Ordering::Less,
+        (Identifier::Alpha(_), Identifier::Number(_)) => Ordering::Greater,
+        (Identifier::Alpha(a), Identifier::Alpha(b)) => {
+            a.to_ascii_lowercase().cmp(&b.to_ascii_lowercase())
+        }
+    }
+}
+
+impl Eq for Version {}
+
+/// Build metadata is ignored, and a pre-release comes before its release.
+impl Ord for Version {
+    fn cmp(&self, other: &Version) -> Ordering {
+        (self.major, self.minor, self.patch, self.revision)
+            .cmp(&(other.major, other.minor, other.patch, other.revision))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => {
+                    self.pre
+                        .iter()
+                        .zip(other.pre.iter())
+                        .map(|(a, b)| compare_identifiers(a, b))
+                        .find(|&o| o != Ordering::Equal)
+                        .unwrap_or_else(|| self.pre.len().cmp(&other.pre.len()))
+                }
+            })
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Version) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Version) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl VersionScheme for Version {
+    /// Increments a version number, setting the revision to zero (`0`).
+    fn bump(&mut self, level: Bump) {
+        match level {
+            Bump::Major => {
+                self.major += 1;
+                self.minor = 0;
+                self.patch = 0;
+            }
+            Bump::Minor => {
+                self.minor += 1;
+                self.patch = 0;
+            }
+            Bump::Patch => self.patch += 1,
+        }
+
+        self.revision = 0;
+        self.pre.clear();
+        self.build.clear();
+    }
+
+    /// A version number is stable if it has no pre-release identifiers.
+    fn is_stable(&self) -> bool {
+        self.pre.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Version;
+    use super::super::scheme::{Bump, VersionScheme};
+
+    #[test]
+    fn test_parse() {
+        let version: Version = "1.2.3.4-beta.2+sha.5".parse().unwrap();
+
+        assert_eq!((1, 2, 3, 4), (version.major, version.minor, version.patch, version.revision));
+        assert_eq!(vec![id!("beta"), id!(2)], version.pre);
+        assert_eq!(vec![id!("sha"), id!(5)], version.build);
+        assert_eq!("1.2.3.4-beta.2+sha.5", version.to_string());
+        assert_eq!("1.0.0", "1".parse::<Version>().unwrap().to_string());
+        assert_eq!("1.2.3", "1.2.3.0".parse::<Version>().unwrap().to_string());
+
+        for s in &["", "1.2.3.4.5", "1..2", "a.b", "1.2.3-", "1.2.3-a..b", "1.2.3+", "1.2.3-a_b",
+                   "2147483648.0.0"] {
+            assert!(s.parse::<Version>().is_err(), "{} should be invalid", s);
+        }
+    }
+
+    #[test]
+    fn test_ordering() {
+        let ordered = ["1.0.0-1",
+                       "1.0.0-Alpha",
+                       "1.0.0-alpha.1",
+                       "1.0.0-BETA",
+                       "1.0.0-rc.1",
+                       "1.0.0",
+                       "1.0.0.1-alpha",
+                       "1.0.0.1",
+                       "1.0.1",
+                       "1.10.0"];
+
+        let versions: Vec<Version> = ordered.iter().map(|s| s.parse().unwrap()).collect();
+
+        for pair in versions.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+
+        assert_eq!("1.0.0-RC.1".parse::<Version>().unwrap(),
+                   "1.0.0-rc.1+build".parse().unwrap());
+    }
+
+    #[test]
+    fn test_scheme() {
+        let mut version: Version = "1.2.3.4-rc.1".parse().unwrap();
+
+        assert!(!version.is_stable());
+
+        version.bump(Bump::Patch);
+
+        assert_eq!("1.2.4", version.to_string());
+        assert!(version.is_stable());
+        assert!("0.1.0".parse::<Version>().unwrap().is_stable());
+    }
+
+}
@@ -1,6 +1,13 @@
+#[cfg(feature = "std")]
 use nom::{digit, IResult};
 use std::str;
-use super::version::{Identifier, Version};
+use super::borrowed::VersionRef;
+#[cfg(feature = "std")]
+use super::version::{next_floor, unchecked, Identifier};
+use super::version::{check, Version};
+
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
 
 /// Checks if a given character is part of a valid identifier.
 ///
@@ -24,18 +31,14 @@ fn is_id_char(c: u8) -> bool {
 }
 
 /// Parses a collection of strings into a vector of `Identifier`s.
+#[cfg(feature = "std")]
 #[allow(dead_code)]
 fn parse_ids(identifiers: Vec<&str>) -> Vec<Identifier> {
-    let mut parsed = Vec::new();
-
-    for identifier in identifiers {
-        parsed.push(Identifier::from(identifier));
-    }
-
-    parsed
+    identifiers.into_iter().map(unchecked).collect()
 }
 
 /// Parse a string as an `Identifier`.
+#[cfg(feature = "std")]
 #[allow(dead_code)]
 named!(
     identifiers<&[u8], Vec<Identifier> >,
@@ -52,6 +55,7 @@ named!(
 );
 
 /// Parses a string of pre-release identifiers into a vector of `Identifier`s.
+#[cfg(feature = "std")]
 #[allow(dead_code)]
 named!(
     build<&[u8], Option<Vec<Identifier>> >,
@@ -59,6 +63,7 @@ named!(
 );
 
 /// Parses a string of build identifiers into a vector of `Identifier`s.
+#[cfg(feature = "std")]
 #[allow(dead_code)]
 named!(
     prerelease<&[u8], Option<Vec<Identifier>> >,
@@ -66,6 +71,7 @@ named!(
 );
 
 /// Optionally parse pre-release and build identifiers.
+#[cfg(feature = "std")]
 #[allow(dead_code)]
 named!(
     extensions<&[u8], (Option<Vec<Identifier>>, Option<Vec<Identifier>>)>,
@@ -73,6 +79,7 @@ named!(
 );
 
 /// Parses a numeric string as a `u64`.
+#[cfg(feature = "std")]
 #[allow(dead_code)]
 named!(
     number<&[u8], u64>,
@@ -83,6 +90,7 @@ named!(
 );
 
 /// Parses a numeric string preceded by a dot (.) as u64.
+#[cfg(feature = "std")]
 #[allow(dead_code)]
 named!(
     next_number<&[u8], u64>,
@@ -90,6 +98,7 @@ named!(
 );
 
 /// Parse a string as a `Version`.
+#[cfg(feature = "std")]
 #[allow(dead_code)]
 named!(
     version<&[u8], Version>,
@@ -110,36 +119,276 @@ named!(
     )
 );
 
+/// Represents a version number that may be missing some of its parts.
+///
+/// Requirement strings in most ecosystems allow a version number to be only
+/// partially given (`1.2`), or to have some of its parts replaced with a
+/// wildcard (`1.2.*`, `1.x`). Missing and wildcard parts are both `None`, but
+/// the use of a wildcard is remembered, since some ecosystems treat the two
+/// differently.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Partial {
+    /// The major version number, if given.
+    pub major: Option<u64>,
+
+    /// The minor version number, if given.
+    pub minor: Option<u64>,
+
+    /// The patch version number, if given.
+    pub patch: Option<u64>,
+
+    /// The pre-release identifiers.
+    pub pre: Vec<Identifier>,
+
+    /// The build identifiers.
+    pub build: Vec<Identifier>,
+
+    /// Whether a wildcard (`*`, `x`, or `X`) was used for any part.
+    pub wildcard: bool,
+}
+
+#[cfg(feature = "std")]
+impl Partial {
+    /// Checks if all three version numbers were given.
+    pub fn is_complete(&self) -> bool {
+        self.major.is_some() && self.minor.is_some() && self.patch.is_some()
+    }
+
+    /// Creates a version number, replacing missing parts with zero (`0`).
+    pub fn to_version(&self) -> Version {
+        Version {
+            major: self.major.unwrap_or(0),
+            minor: self.minor.unwrap_or(0),
+            patch: self.patch.unwrap_or(0),
+            pre: self.pre.clone(),
+            build: self.build.clone(),
+        }
+    }
+
+    /// Returns the release that comes after every version number the partial
+    /// matches, such as `2.0.0` for `1` and `1.3.0` for `1.2`.
+    ///
+    /// There is none if the partial is complete or only a wildcard, or if no
+    /// version number can come after it, such as for `18446744073709551615`.
+    pub fn next_release(&self) -> Option<Version> {
+        self.last().and_then(|last| last.next_release())
+    }
+
+    /// Returns the version number that comes before any pre-release of the
+    /// next release, such as `2.0.0-0` for `1`.
+    pub fn next_floor(&self) -> Option<Version> {
+        self.last().and_then(|last| next_floor(last.major, last.minor, last.patch))
+    }
+
+    /// Returns the greatest release matched by an incomplete partial.
+    fn last(&self) -> Option<Version> {
+        match (self.major, self.minor, self.patch) {
+            (Some(major), None, _) => Some(version!(major, u64::MAX, u64::MAX)),
+            (Some(major), Some(minor), None) => Some(version!(major, minor, u64::MAX)),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a wildcard used in place of a version number.
+#[cfg(feature = "std")]
+#[allow(dead_code)]
+named!(
+    wildcard<&[u8], Option<u64> >,
+    value!(None, alt!(tag!("*") | tag!("x") | tag!("X")))
+);
+
+/// Parses a version number or a wildcard.
+#[cfg(feature = "std")]
+#[allow(dead_code)]
+named!(
+    part<&[u8], Option<u64> >,
+    alt!(map!(number, Some) | wildcard)
+);
+
+/// Parses a version number or a wildcard preceded by a dot (.).
+#[cfg(feature = "std")]
+#[allow(dead_code)]
+named!(
+    next_part<&[u8], Option<Option<u64>> >,
+    opt!(complete!(preceded!(char!('.'), part)))
+);
+
+/// Parse a string as a `Partial` version number.
+#[cfg(feature = "std")]
+#[allow(dead_code)]
+named!(
+    partial<&[u8], Partial>,
+    chain!(
+        major: part ~
+        minor: next_part ~
+        patch: next_part ~
+        extensions: extensions,
+        || {
+            Partial {
+                major,
+                minor: minor.unwrap_or(None),
+                patch: patch.unwrap_or(None),
+                pre: extensions.0.clone().unwrap_or(Vec::new()),
+                build: extensions.1.clone().unwrap_or(Vec::new()),
+                wildcard: major.is_none() || minor == Some(None) || patch == Some(None),
+            }
+        }
+    )
+);
+
+/// Checks the parts of a partial version number in the string it came from.
+///
+/// The numbers are parsed as numbers, so the string is checked instead, with
+/// the same rules as `parse_ref`: numbers may not have leading zeros, and the
+/// identifiers must be valid.
+#[cfg(feature = "std")]
+fn is_valid_partial(s: &str) -> bool {
+    let (rest, build) = s.split_once('+').unwrap_or((s, ""));
+    let (numbers, pre) = rest.split_once('-').unwrap_or((rest, ""));
+
+    numbers.split('.').all(|n| n.len() < 2 || !n.starts_with('0')) &&
+    (pre.is_empty() || pre.split('.').all(|i| check(i, false).is_ok())) &&
+    (build.is_empty() || build.split('.').all(|i| check(i, true).is_ok()))
+}
+
+/// Parses a string as a `Partial` version number.
+///
+/// Once a part is missing or a wildcard, every part after it must also be
+/// missing or a wildcard (`1.*.3` is not valid). Pre-release and build
+/// identifiers may only be given with a complete version number, and, as
+/// with `Version`, numbers and numeric pre-release identifiers may not have
+/// leading zeros (`01.2.3` and `1.2.3-rc.01` are not valid).
+#[cfg(feature = "std")]
+pub fn parse_partial(s: &str) -> Result<Partial, String> {
+    if let IResult::Done(remainder, partial) = partial(s.as_bytes()) {
+        let ordered = (partial.minor.is_some() || partial.patch.is_none()) &&
+                      (partial.major.is_some() || partial.minor.is_none());
+        let extended = !partial.pre.is_empty() || !partial.build.is_empty();
+
+        if remainder.is_empty() && ordered && (partial.is_complete() || !extended) &&
+           is_valid_partial(s) {
+            return Ok(partial);
+        }
+    }
+
+    Err(format!("Invalid version number: {}", s))
+}
+
+/// Parses a version number at the start of a byte string.
+///
+/// Unlike parsing a whole string, the version number may be followed by
+/// anything. The version number is returned with the number of bytes that
+/// were used, or `None` if the byte string does not start with one.
+#[cfg(feature = "std")]
+pub fn parse_prefix(input: &[u8]) -> Option<(Version, usize)> {
+    match version(input) {
+        IResult::Done(remainder, version) => Some((version, input.len() - remainder.len())),
+        _ => None,
+    }
+}
+
+/// Splits the digits at the start of a string from the rest, as a `u64`.
+///
+/// Like numeric identifiers, numbers may not have leading zeros.
+fn split_number(s: &str) -> Option<(u64, &str)> {
+    let length = s.bytes().take_while(u8::is_ascii_digit).count();
+
+    if length > 1 && s.starts_with('0') {
+        return None;
+    }
+
+    s[..length].parse().ok().map(|number| (number, &s[length..]))
+}
+
+/// Splits the identifiers at the start of a string from the rest.
+///
+/// The identifiers are recognized in the same way as `identifiers`, but only
+/// the string they were found in is returned, so nothing is allocated.
+fn split_identifiers(s: &str) -> Option<(&str, &str)> {
+    let run = |start: usize| s.bytes().skip(start).take_while(|&c| is_id_char(c)).count();
+    let mut end = run(0);
+
+    if end == 0 {
+        return None;
+    }
+
+    while s.as_bytes().get(end) == Some(&b'.') {
+        match run(end + 1) {
+            0 => break,
+            length => end += 1 + length,
+        }
+    }
+
+    Some((&s[..end], &s[end..]))
+}
+
+/// Parses a string as a `VersionRef` without allocating.
+///
+/// The pre-release and build identifiers are borrowed from the string. This
+/// parser does not use `nom`, so that version numbers can also be parsed
+/// without `std`.
+pub fn parse_ref(s: &str) -> Result<VersionRef<'_>, String> {
+    let invalid = || format!("Invalid version number: {}", s);
+    let (major, rest) = split_number(s).ok_or_else(invalid)?;
+    let (minor, rest) = rest.strip_prefix('.').and_then(split_number).ok_or_else(invalid)?;
+    let (patch, rest) = rest.strip_prefix('.').and_then(split_number).ok_or_else(invalid)?;
+
+    let (pre, rest) = match rest.strip_prefix('-') {
+        Some(rest) => split_identifiers(rest).ok_or_else(invalid)?,
+        None => ("", rest),
+    };
+
+    if !pre.is_empty() && pre.split('.').any(|identifier| check(identifier, false).is_err()) {
+        return Err(invalid());
+    }
+
+    let (build, rest) = match rest.strip_prefix('+') {
+        Some(rest) => split_identifiers(rest).ok_or_else(invalid)?,
+        None => ("", rest),
+    };
+
+    if !rest.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(VersionRef {
+        major,
+        minor,
+        patch,
+        pre,
+        build,
+    })
+}
+
 /// Enables parsing of strings as semantic version numbers.
 ///
 /// ```
 /// # use recital::version::Version;
 /// let example: Version = "1.2.3-abc.456+def.789".parse().unwrap();
+///
+/// assert!("1.2.3-rc.01".parse::<Version>().is_err());
 /// ```
+///
+/// The numbers and numeric pre-release identifiers may not have leading zeros,
+/// and numeric identifiers must fit in a `u64`, so a parsed version number is
+/// always valid according to `Version::validate`.
 impl str::FromStr for Version {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Version, String> {
-        match version(s.as_bytes()) {
-            IResult::Done(remainder, version) => {
-                if remainder.is_empty() {
-                    return Ok(version);
-                }
-            }
-
-            _ => {}
-        }
-
-        Err(format!("Invalid version number: {}", s.to_string()))
+        parse_ref(s).map(|version| version.to_owned())
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
 
     use nom::IResult;
     use super::{build, extensions, identifiers, is_id_char, next_number, number, parse_ids,
-                prerelease, version};
+                parse_partial, parse_prefix, parse_ref, partial, prerelease, split_identifiers,
+                split_number, version, Partial};
     use super::super::version::Version;
 
     macro_rules! parsed {
@@ -215,12 +464,110 @@ mod test {
         assert_eq!(parse_ids(vec!["abc", "123"]), vec![id!("abc"), id!(123)]);
     }
 
+    #[test]
+    fn test_partial() {
+        assert_eq!(partial(b"1.x"),
+                   parsed!(Partial {
+                       major: Some(1),
+                       minor: None,
+                       patch: None,
+                       pre: Vec::new(),
+                       build: Vec::new(),
+                       wildcard: true,
+                   }));
+
+        assert_eq!(partial(b"1.2"),
+                   parsed!(Partial {
+                       major: Some(1),
+                       minor: Some(2),
+                       patch: None,
+                       pre: Vec::new(),
+                       build: Vec::new(),
+                       wildcard: false,
+                   }));
+    }
+
+    #[test]
+    fn test_parse_partial() {
+        assert_eq!(parse_partial("1.2.3-rc.1").unwrap().to_version(),
+                   version!(1, 2, 3, vec![id!("rc"), id!(1)]));
+
+        assert!(parse_partial("*").unwrap().wildcard);
+        assert!(parse_partial("1.2.*").unwrap().wildcard);
+        assert!(!parse_partial("1").unwrap().wildcard);
+
+        assert!(parse_partial("1.*.3").is_err());
+        assert!(parse_partial("1.2-rc.1").is_err());
+        assert!(parse_partial("1.2.3.4").is_err());
+        assert!(parse_partial("01.2.3").is_err());
+        assert!(parse_partial("1.02").is_err());
+        assert!(parse_partial("1.2.3-rc.01").is_err());
+        assert!(parse_partial("1.2.3-rc.99999999999999999999").is_err());
+        assert!(parse_partial("1.2.3-").is_err());
+        assert!(parse_partial("").is_err());
+    }
+
+    #[test]
+    fn test_partial_next() {
+        assert_eq!(parse_partial("1").unwrap().next_release(), Some(version!(2, 0, 0)));
+        assert_eq!(parse_partial("1.2.x").unwrap().next_release(), Some(version!(1, 3, 0)));
+        assert_eq!(parse_partial("1.2").unwrap().next_floor(),
+                   Some(version!(1, 3, 0, vec![id!(0)])));
+
+        assert_eq!(parse_partial("1.2.3").unwrap().next_release(), None);
+        assert_eq!(parse_partial("*").unwrap().next_floor(), None);
+        assert_eq!(parse_partial("18446744073709551615").unwrap().next_release(), None);
+    }
+
+    #[test]
+    fn test_parse_prefix() {
+        assert_eq!(parse_prefix(b"1.2.3-rc.1 is out"),
+                   Some((version!(1, 2, 3, vec![id!("rc"), id!(1)]), 10)));
+        assert_eq!(parse_prefix(b"1.2.3."), Some((version!(1, 2, 3), 5)));
+        assert_eq!(parse_prefix(b"1.2 is out"), None);
+        assert_eq!(parse_prefix(b"v1.2.3"), None);
+    }
+
+    #[test]
+    fn test_parse_ref() {
+        let version = parse_ref("1.2.3-rc.1+build.7").unwrap();
+
+        assert_eq!((1, 2, 3, "rc.1", "build.7"),
+                   (version.major, version.minor, version.patch, version.pre, version.build));
+        assert_eq!("", parse_ref("1.2.3").unwrap().pre);
+        assert_eq!("build.007", parse_ref("1.2.3+build.007").unwrap().build);
+        assert!(parse_ref("1.2.3-rc..1").is_err());
+        assert!(parse_ref("1.2").is_err());
+
+        for s in &["01.2.3", "1.02.3", "1.2.03", "1.2.3-01", "1.2.3-rc.01",
+                   "1.2.3-18446744073709551616"] {
+            assert!(parse_ref(s).is_err(), "{} should be invalid", s);
+        }
+    }
+
     #[test]
     fn test_prerelease() {
         assert_eq!(prerelease(b"-abc.123"),
                    parsed!(Some(vec![id!("abc"), id!(123)])));
     }
 
+    #[test]
+    fn test_split_identifiers() {
+        assert_eq!(split_identifiers("abc.123.xyz-890"), Some(("abc.123.xyz-890", "")));
+        assert_eq!(split_identifiers("rc..1"), Some(("rc", "..1")));
+        assert_eq!(split_identifiers("rc.+1"), Some(("rc", ".+1")));
+        assert_eq!(split_identifiers("+1"), None);
+    }
+
+    #[test]
+    fn test_split_number() {
+        assert_eq!(split_number("123.4"), Some((123, ".4")));
+        assert_eq!(split_number("0.1"), Some((0, ".1")));
+        assert_eq!(split_number("007"), None);
+        assert_eq!(split_number("18446744073709551616"), None);
+        assert_eq!(split_number("x"), None);
+    }
+
     #[test]
     fn test_version() {
         assert_eq!(version(b"12.34.56-ab.78"),
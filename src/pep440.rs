@@ -0,0 +1,899 @@
+//! Creates, parses, and compares Python (PEP 440) version numbers.
+//!
+//! The **pep440** module provides you with a `Version` that follows the
+//! rules of [PEP 440][], which is the versioning scheme used by Python
+//! packages. Version numbers are normalized when they are parsed, and they
+//! are ordered the same way that `pip` orders them.
+//!
+//! [PEP 440]: https://peps.python.org/pep-0440/
+//!
+//! ```
+//! use recital::pep440::Version;
+//!
+//! let version: Version = "1!2.0-Preview.3.post-1_DEV2+Ubuntu-1".parse().unwrap();
+//!
+//! assert_eq!("1!2.0rc3.post1.dev2+ubuntu.1", version.to_string());
+//!
+//! let a: Version = "1.0.dev1".parse().unwrap();
+//! let b: Version = "1.0a1".parse().unwrap();
+//! let c: Version = "1.0".parse().unwrap();
+//! let d: Version = "1.0.post1".parse().unwrap();
+//!
+//! assert!(a < b && b < c && c < d);
+//! ```
+//!
+//! Version specifiers (`~=`, `==`, `!=`, `<=`, `>=`, `<`, `>`, and `===`) are
+//! parsed into a `SpecifierSet`, which can be used like any other constraint.
+//!
+//! ```
+//! use recital::pep440::{SpecifierSet, Version};
+//! use recital::resolve::Constraint;
+//!
+//! let set: SpecifierSet = "~=1.4.2, !=1.4.5".parse().unwrap();
+//!
+//! assert!(set.allows(&"1.4.9".parse::<Version>().unwrap()));
+//! assert!(!set.allows(&"1.4.5".parse::<Version>().unwrap()));
+//! assert!(!set.allows(&"1.5.0".parse::<Version>().unwrap()));
+//! ```
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use super::resolve::{Constraint, Constraints};
+use super::scheme::{Bump, VersionScheme};
+use super::version::{self, Identifier};
+
+/// Represents the kind of a pre-release.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Pre {
+    /// An alpha release (`a`, `alpha`).
+    Alpha,
+
+    /// A beta release (`b`, `beta`).
+    Beta,
+
+    /// A release candidate (`rc`, `c`, `pre`, `preview`).
+    ReleaseCandidate,
+}
+
+impl Display for Pre {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Pre::Alpha => write!(f, "a"),
+            Pre::Beta => write!(f, "b"),
+            Pre::ReleaseCandidate => write!(f, "rc"),
+        }
+    }
+}
+
+/// Represents a segment of a local version label.
+///
+/// Numeric segments are always greater than alphanumeric ones.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Local {
+    /// An alphanumeric segment.
+    Alpha(String),
+
+    /// A numeric segment.
+    Number(u64),
+}
+
+impl Display for Local {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Local::Alpha(ref a) => write!(f, "{}", a),
+            Local::Number(ref n) => write!(f, "{}", n),
+        }
+    }
+}
+
+/// Represents a PEP 440 version number.
+#[derive(Clone, Debug, Default)]
+pub struct Version {
+    /// The epoch (`1!`), which is zero (`0`) if not given.
+    pub epoch: u64,
+
+    /// The release segments (`1.2.3`).
+    pub release: Vec<u64>,
+
+    /// The pre-release kind and number (`a1`, `rc2`).
+    pub pre: Option<(Pre, u64)>,
+
+    /// The post-release number (`.post1`).
+    pub post: Option<u64>,
+
+    /// The development release number (`.dev1`).
+    pub dev: Option<u64>,
+
+    /// The local version label (`+ubuntu.1`).
+    pub local: Vec<Local>,
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}!", self.epoch)?;
+        }
+
+        let release: Vec<String> = self.release.iter().map(|n| n.to_string()).collect();
+
+        write!(f, "{}", release.join("."))?;
+
+        if let Some((kind, n)) = self.pre {
+            write!(f, "{}{}", kind, n)?;
+        }
+
+        if let Some(n) = self.post {
+            write!(f, ".post{}", n)?;
+        }
+
+        if let Some(n) = self.dev {
+            write!(f, ".dev{}", n)?;
+        }
+
+        if !self.local.is_empty() {
+            let local: Vec<String> = self.local.iter().map(|l| l.to_string()).collect();
+
+            write!(f, "+{}", local.join("."))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A helper for the parts of a version number that are missing.
+///
+/// PEP 440 orders a missing part either before or after every given part,
+/// depending on which part it is.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Key<T> {
+    Before,
+    Given(T),
+    After,
+}
+
+/// The key that a version number is ordered by.
+type SortKey<'a> = (u64, &'a [u64], Key<(Pre, u64)>, Key<u64>, Key<u64>, Key<&'a [Local]>);
+
+impl Version {
+    /// Checks if the version number is a development release.
+    pub fn is_dev(&self) -> bool {
+        self.dev.is_some()
+    }
+
+    /// Checks if the version number is a post-release.
+    pub fn is_post(&self) -> bool {
+        self.post.is_some()
+    }
+
+    /// Checks if the version number is a pre-release.
+    ///
+    /// Development releases are also pre-releases.
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    /// Creates the public version number, without a local version label.
+    pub fn public(&self) -> Version {
+        Version {
+            local: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Creates the base version number, with only an epoch and release.
+    pub fn base(&self) -> Version {
+        Version {
+            epoch: self.epoch,
+            release: self.release.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the release segments without any trailing zeros.
+    fn trimmed(&self) -> &[u64] {
+        let mut end = self.release.len();
+
+        while end > 0 && self.release[end - 1] == 0 {
+            end -= 1;
+        }
+
+        &self.release[..end]
+    }
+
+    /// Converts a semantic version number into a PEP 440 version number.
+    ///
+    /// A conversion is only possible if it is lossless: there can be no
+    /// build identifiers, and the pre-release identifiers must be empty or
+    /// exactly one of `alpha`, `beta`, or `rc` followed by a number (`-rc.1`
+    /// is `rc1`).
+    pub fn from_semver(semver: &version::Version) -> Result<Version, String> {
+        let invalid = || format!("No lossless PEP 440 version number for: {}", semver);
+
+        if !semver.build.is_empty() {
+            return Err(invalid());
+        }
+
+        let pre = match semver.pre.as_slice() {
+            [] => None,
+            [Identifier::Alpha(label), Identifier::Number(n)] => {
+                match label.as_str() {
+                    "alpha" => Some((Pre::Alpha, *n)),
+                    "beta" => Some((Pre::Beta, *n)),
+                    "rc" => Some((Pre::ReleaseCandidate, *n)),
+                    _ => return Err(invalid()),
+                }
+            }
+            _ => return Err(invalid()),
+        };
+
+        Ok(Version {
+            release: vec![semver.major, semver.minor, semver.patch],
+            pre,
+            ..Default::default()
+        })
+    }
+
+    /// Converts this version number into a semantic version number.
+    ///
+    /// A conversion is only possible if it is lossless: the epoch must be
+    /// zero, there may be no more than three non-zero release segments, and
+    /// there may be no post-release, development release, or local version
+    /// label. Pre-releases are converted to `-alpha.N`, `-beta.N`, and
+    /// `-rc.N`, which keep their order.
+    pub fn to_semver(&self) -> Result<version::Version, String> {
+        let invalid = || format!("No lossless semantic version number for: {}", self);
+
+        if self.epoch != 0 || self.trimmed().len() > 3 || self.post.is_some() ||
+           self.dev.is_some() || !self.local.is_empty() {
+            return Err(invalid());
+        }
+
+        let segment = |i: usize| self.release.get(i).cloned().unwrap_or(0);
+        let pre = match self.pre {
+            None => Vec::new(),
+            Some((Pre::Alpha, n)) => vec![id!("alpha"), id!(n)],
+            Some((Pre::Beta, n)) => vec![id!("beta"), id!(n)],
+            Some((Pre::ReleaseCandidate, n)) => vec![id!("rc"), id!(n)],
+        };
+
+        Ok(version!(segment(0), segment(1), segment(2), pre))
+    }
+
+    /// Returns the key that the version number is ordered by.
+    fn key(&self) -> SortKey<'_> {
+        let pre = match (self.pre, self.post, self.dev) {
+            (None, None, Some(_)) => Key::Before,
+            (None, _, _) => Key::After,
+            (Some(pre), _, _) => Key::Given(pre),
+        };
+
+        let post = match self.post {
+            None => Key::Before,
+            Some(n) => Key::Given(n),
+        };
+
+        let dev = match self.dev {
+            None => Key::After,
+            Some(n) => Key::Given(n),
+        };
+
+        let local = if self.local.is_empty() {
+            Key::Before
+        } else {
+            Key::Given(self.local.as_slice())
+        };
+
+        (self.epoch, self.trimmed(), pre, post, dev, local)
+    }
+}
+
+impl Eq for Version {}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Version) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Version) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Version) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Returns the kind of pre-release for a label, in any of its spellings.
+fn pre_kind(label: &str) -> Option<Pre> {
+    match label {
+        "a" | "alpha" => Some(Pre::Alpha),
+        "b" | "beta" => Some(Pre::Beta),
+        "rc" | "c" | "pre" | "preview" => Some(Pre::ReleaseCandidate),
+        _ => None,
+    }
+}
+
+/// A simple cursor over the bytes of a version string.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// Consumes a specific byte.
+    fn byte(&mut self, b: u8) -> bool {
+        if self.bytes.get(self.offset) == Some(&b) {
+            self.offset += 1;
+
+            return true;
+        }
+
+        false
+    }
+
+    /// Consumes a number.
+    fn number(&mut self) -> Option<u64> {
+        let start = self.offset;
+
+        while self.offset < self.bytes.len() && self.bytes[self.offset].is_ascii_digit() {
+            self.offset += 1;
+        }
+
+        if start == self.offset {
+            return None;
+        }
+
+        String::from_utf8_lossy(&self.bytes[start..self.offset]).parse().ok()
+    }
+
+    /// Consumes an optional separator (`.`, `-`, or `_`).
+    fn separator(&mut self) -> bool {
+        self.byte(b'.') || self.byte(b'-') || self.byte(b'_')
+    }
+
+    /// Consumes an optional separator followed by one of the given labels.
+    ///
+    /// Nothing is consumed if none of the labels follow.
+    fn label(&mut self, labels: &[&'static str]) -> Option<&'static str> {
+        let start = self.offset;
+
+        self.separator();
+
+        for label in labels {
+            if self.bytes[self.offset..].starts_with(label.as_bytes()) {
+                self.offset += label.len();
+
+                return Some(label);
+            }
+        }
+
+        self.offset = start;
+
+        None
+    }
+
+    /// Consumes an optional separator followed by a number.
+    ///
+    /// If there is no number, nothing is consumed and zero (`0`) is returned.
+    fn implicit_number(&mut self) -> u64 {
+        let start = self.offset;
+
+        self.separator();
+
+        match self.number() {
+            Some(n) => n,
+            None => {
+                self.offset = start;
+
+                0
+            }
+        }
+    }
+}
+
+/// Enables parsing of strings as PEP 440 version numbers.
+///
+/// The string is normalized as it is parsed, so alternative spellings, case,
+/// separators, and leading zeros are all accepted.
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Version, String> {
+        let invalid = || format!("Invalid version number: {}", s);
+        let normalized = s.trim().to_lowercase();
+        let normalized = normalized.strip_prefix('v').unwrap_or(&normalized);
+        let mut cursor = Cursor {
+            bytes: normalized.as_bytes(),
+            offset: 0,
+        };
+
+        let mut version = Version::default();
+        let first = cursor.number().ok_or_else(invalid)?;
+
+        if cursor.byte(b'!') {
+            version.epoch = first;
+            version.release.push(cursor.number().ok_or_else(invalid)?);
+        } else {
+            version.release.push(first);
+        }
+
+        loop {
+            let start = cursor.offset;
+
+            if !cursor.byte(b'.') {
+                break;
+            }
+
+            match cursor.number() {
+                Some(n) => version.release.push(n),
+                None => {
+                    cursor.offset = start;
+
+                    break;
+                }
+            }
+        }
+
+        let labels = ["alpha", "a", "beta", "b", "preview", "pre", "rc", "c"];
+
+        if let Some(label) = cursor.label(&labels) {
+            version.pre = Some((pre_kind(label).ok_or_else(invalid)?, cursor.implicit_number()));
+        }
+
+        let start = cursor.offset;
+
+        if cursor.byte(b'-') && cursor.number().is_some() {
+            cursor.offset = start + 1;
+            version.post = cursor.number();
+        } else {
+            cursor.offset = start;
+
+            if cursor.label(&["post", "rev", "r"]).is_some() {
+                version.post = Some(cursor.implicit_number());
+            }
+        }
+
+        if cursor.label(&["dev"]).is_some() {
+            version.dev = Some(cursor.implicit_number());
+        }
+
+        if cursor.byte(b'+') {
+            let rest = &normalized[cursor.offset..];
+
+            for segment in rest.split(['.', '-', '_']) {
+                if segment.is_empty() || !segment.bytes().all(|b| b.is_ascii_alphanumeric()) {
+                    return Err(invalid());
+                }
+
+                version.local.push(match segment.parse() {
+                    Ok(n) => Local::Number(n),
+                    Err(_) => Local::Alpha(segment.to_string()),
+                });
+            }
+
+            cursor.offset = cursor.bytes.len();
+        }
+
+        if cursor.offset != cursor.bytes.len() {
+            return Err(invalid());
+        }
+
+        Ok(version)
+    }
+}
+
+impl VersionScheme for Version {
+    /// Increments a release segment, padding the release as needed.
+    ///
+    /// The major, minor, and patch levels are the first three release
+    /// segments. The segments after the incremented one are set to zero, and
+    /// any pre-release, post-release, development release, and local version
+    /// label is removed.
+    fn bump(&mut self, level: Bump) {
+        let index = match level {
+            Bump::Major => 0,
+            Bump::Minor => 1,
+            Bump::Patch => 2,
+        };
+
+        while self.release.len() <= index {
+            self.release.push(0);
+        }
+
+        self.release[index] += 1;
+
+        for segment in self.release.iter_mut().skip(index + 1) {
+            *segment = 0;
+        }
+
+        self.pre = None;
+        self.post = None;
+        self.dev = None;
+        self.local.clear();
+    }
+
+    fn is_stable(&self) -> bool {
+        !self.is_prerelease()
+    }
+}
+
+/// Represents the operator of a version specifier.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operator {
+    /// A compatible release. (~=)
+    Compatible,
+
+    /// A version match, optionally with a prefix. (==)
+    Equal,
+
+    /// A version exclusion, optionally with a prefix. (!=)
+    NotEqual,
+
+    /// An inclusive ordered comparison. (<=)
+    LessThanOrEqualTo,
+
+    /// An inclusive ordered comparison. (>=)
+    GreaterThanOrEqualTo,
+
+    /// An exclusive ordered comparison. (<)
+    LessThan,
+
+    /// An exclusive ordered comparison. (>)
+    GreaterThan,
+
+    /// An arbitrary string equality. (===)
+    Arbitrary,
+}
+
+/// Represents a single version specifier (e.g. `>=1.2`, `==1.4.*`).
+#[derive(Clone, Debug)]
+pub struct Specifier {
+    /// The operator of the specifier.
+    pub operator: Operator,
+
+    /// The version number of the specifier.
+    pub version: Version,
+
+    /// Whether the version number ended with a wildcard (`.*`).
+    pub wildcard: bool,
+
+    /// The original string for an arbitrary equality.
+    pub raw: String,
+}
+
+impl Specifier {
+    /// Checks if the specifier explicitly allows pre-releases.
+    ///
+    /// This is the case for inclusive operators that are used with a version
+    /// number that is itself a pre-release.
+    pub fn is_prerelease(&self) -> bool {
+        match self.operator {
+            Operator::NotEqual | Operator::LessThan | Operator::GreaterThan => false,
+            _ => self.version.is_prerelease(),
+        }
+    }
+
+    /// Checks if the release of a version number starts with a prefix.
+    fn prefix_matches(&self, version: &Version) -> bool {
+        if self.version.epoch != version.epoch {
+            return false;
+        }
+
+        (0..self.version.release.len())
+            .all(|i| version.release.get(i).cloned().unwrap_or(0) == self.version.release[i])
+    }
+}
+
+impl Constraint<Version> for Specifier {
+    fn allows(&self, version: &Version) -> bool {
+        let spec = &self.version;
+
+        match self.operator {
+            Operator::Compatible => {
+                let mut prefix = self.clone();
+
+                prefix.version.release.pop();
+
+                version.public() >= *spec && prefix.prefix_matches(version)
+            }
+
+            Operator::Equal if self.wildcard => self.prefix_matches(version),
+            Operator::NotEqual if self.wildcard => !self.prefix_matches(version),
+
+            Operator::Equal | Operator::NotEqual => {
+                let equal = if spec.local.is_empty() {
+                    version.public() == *spec
+                } else {
+                    version == spec
+                };
+
+                equal == (self.operator == Operator::Equal)
+            }
+
+            Operator::LessThanOrEqualTo => version.public() <= *spec,
+            Operator::GreaterThanOrEqualTo => version.public() >= *spec,
+
+            Operator::LessThan => {
+                version.public() < *spec &&
+                (spec.is_prerelease() || !version.is_prerelease() ||
+                 version.base() != spec.base())
+            }
+
+            Operator::GreaterThan => {
+                version.public() > *spec &&
+                (spec.is_post() || !version.is_post() || version.base() != spec.base()) &&
+                (version.local.is_empty() || version.base() != spec.base())
+            }
+
+            Operator::Arbitrary => version.to_string() == self.raw,
+        }
+    }
+}
+
+/// Enables parsing of strings as PEP 440 version specifiers.
+impl FromStr for Specifier {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Specifier, String> {
+        let invalid = || format!("Invalid specifier: {}", s);
+        let s = s.trim();
+
+        let operators = [("===", Operator::Arbitrary),
+                         ("~=", Operator::Compatible),
+                         ("==", Operator::Equal),
+                         ("!=", Operator::NotEqual),
+                         ("<=", Operator::LessThanOrEqualTo),
+                         (">=", Operator::GreaterThanOrEqualTo),
+                         ("<", Operator::LessThan),
+                         (">", Operator::GreaterThan)];
+
+        let (operator, rest) = operators.iter()
+            .filter_map(|&(prefix, op)| s.strip_prefix(prefix).map(|rest| (op, rest.trim())))
+            .next()
+            .ok_or_else(invalid)?;
+
+        if operator == Operator::Arbitrary {
+            return Ok(Specifier {
+                operator,
+                version: rest.parse().unwrap_or_default(),
+                wildcard: false,
+                raw: rest.to_string(),
+            });
+        }
+
+        let (rest, wildcard) = match rest.strip_suffix(".*") {
+            Some(rest) => (rest, true),
+            None => (rest, false),
+        };
+
+        let version: Version = rest.parse().map_err(|_| invalid())?;
+        let local = !version.local.is_empty();
+
+        let valid = match operator {
+            Operator::Equal | Operator::NotEqual if wildcard => {
+                version.pre.is_none() && version.post.is_none() && version.dev.is_none() && !local
+            }
+
+            Operator::Equal | Operator::NotEqual => true,
+            Operator::Compatible => !wildcard && !local && version.release.len() >= 2,
+            _ => !wildcard && !local,
+        };
+
+        if !valid {
+            return Err(invalid());
+        }
+
+        Ok(Specifier {
+            operator,
+            version,
+            wildcard,
+            raw: rest.to_string(),
+        })
+    }
+}
+
+/// Represents a comma-separated set of version specifiers.
+///
+/// Pre-releases are only allowed if at least one of the specifiers
+/// explicitly allows them, or if `prereleases` is set to `true`.
+pub struct SpecifierSet {
+    /// The specifiers that all must be satisfied.
+    pub constraints: Constraints<Version>,
+
+    /// Whether pre-releases are allowed.
+    pub prereleases: bool,
+}
+
+impl Constraint<Version> for SpecifierSet {
+    fn allows(&self, version: &Version) -> bool {
+        (self.prereleases || !version.is_prerelease()) && self.constraints.allows(version)
+    }
+}
+
+/// Enables parsing of strings as PEP 440 specifier sets.
+impl FromStr for SpecifierSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<SpecifierSet, String> {
+        let mut constraints: Vec<Box<dyn Constraint<Version>>> = Vec::new();
+        let mut prereleases = false;
+
+        for specifier in s.split(',').filter(|s| !s.trim().is_empty()) {
+            let specifier: Specifier = specifier.parse()?;
+
+            prereleases |= specifier.is_prerelease();
+            constraints.push(Box::new(specifier));
+        }
+
+        Ok(SpecifierSet {
+            constraints: Constraints::And(constraints),
+            prereleases,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+    use super::{Local, Pre, SpecifierSet, Version};
+    use super::super::resolve::{resolve, Constraint};
+    use super::super::scheme::{Bump, VersionScheme};
+
+    #[test]
+    fn test_parse() {
+        let version: Version = "1!2.3.4a5.post6.dev7+abc.8".parse().unwrap();
+
+        assert_eq!(1, version.epoch);
+        assert_eq!(vec![2, 3, 4], version.release);
+        assert_eq!(Some((Pre::Alpha, 5)), version.pre);
+        assert_eq!(Some(6), version.post);
+        assert_eq!(Some(7), version.dev);
+        assert_eq!(vec![Local::Alpha("abc".to_string()), Local::Number(8)], version.local);
+
+        for s in &["", "1.", "a1", "1.0+", "1.0+a..b", "1.0 2", "1.0-", "1.0.post.x"] {
+            assert!(s.parse::<Version>().is_err(), "{} should be invalid", s);
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let cases = [("v1.0", "1.0"),
+                     ("01.002.0003", "1.2.3"),
+                     ("1.0ALPHA1", "1.0a1"),
+                     ("1.0-beta.2", "1.0b2"),
+                     ("1.0c1", "1.0rc1"),
+                     ("1.0preview_1", "1.0rc1"),
+                     ("1.0a", "1.0a0"),
+                     ("1.0-1", "1.0.post1"),
+                     ("1.0-r2", "1.0.post2"),
+                     ("1.0rev", "1.0.post0"),
+                     ("1.0dev", "1.0.dev0"),
+                     ("1.0+Ubuntu-1_2", "1.0+ubuntu.1.2"),
+                     (" 0!1.0 ", "1.0")];
+
+        for &(input, normalized) in &cases {
+            assert_eq!(normalized, input.parse::<Version>().unwrap().to_string(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_ordering() {
+        let ordered = ["1.0.dev456",
+                       "1.0a1",
+                       "1.0a2.dev456",
+                       "1.0a12.dev456",
+                       "1.0a12",
+                       "1.0b1.dev456",
+                       "1.0b2",
+                       "1.0b2.post345.dev456",
+                       "1.0b2.post345",
+                       "1.0rc1.dev456",
+                       "1.0rc1",
+                       "1.0",
+                       "1.0+abc.5",
+                       "1.0+abc.7",
+                       "1.0+5",
+                       "1.0.post456.dev34",
+                       "1.0.post456",
+                       "1.0.15",
+                       "1.1.dev1",
+                       "1!0.1"];
+
+        let versions: Vec<Version> = ordered.iter().map(|s| s.parse().unwrap()).collect();
+
+        for pair in versions.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+
+        assert_eq!("1.0".parse::<Version>().unwrap(), "1.0.0".parse::<Version>().unwrap());
+        assert_eq!("1.0a1".parse::<Version>().unwrap(), "1.0.0alpha1".parse::<Version>().unwrap());
+    }
+
+    #[test]
+    fn test_bump() {
+        let mut version: Version = "1.2rc1".parse().unwrap();
+
+        version.bump(Bump::Patch);
+
+        assert_eq!("1.2.1", version.to_string());
+
+        version.bump(Bump::Major);
+
+        assert_eq!("2.0.0", version.to_string());
+        assert!(version.is_stable());
+        assert!(!"2.0.dev1".parse::<Version>().unwrap().is_stable());
+    }
+
+    #[test]
+    fn test_semver() {
+        assert_eq!("1.2.3rc1".parse::<Version>().unwrap(),
+                   Version::from_semver(&version!(1, 2, 3, vec![id!("rc"), id!(1)])).unwrap());
+
+        assert!(Version::from_semver(&version!(1, 2, 3, vec![id!("rc")])).is_err());
+        assert!(Version::from_semver(&version!(1, 2, 3, vec![id!("pre"), id!(1)])).is_err());
+        assert!(Version::from_semver(&version!(1, 2, 3, vec![], vec![id!("b")])).is_err());
+
+        assert_eq!(version!(1, 2, 0, vec![id!("beta"), id!(2)]),
+                   "1.2b2".parse::<Version>().unwrap().to_semver().unwrap());
+
+        assert_eq!(version!(1, 2, 3), "1.2.3.0".parse::<Version>().unwrap().to_semver().unwrap());
+
+        for s in &["1!1.0", "1.2.3.4", "1.0.post1", "1.0.dev1", "1.0+local"] {
+            assert!(s.parse::<Version>().unwrap().to_semver().is_err(), "{} should not convert", s);
+        }
+    }
+
+    #[test]
+    fn test_specifiers() {
+        check_allows!(SpecifierSet::from_str("~=2.2"), ["2.2", "2.9"], ["2.1", "3.0"]);
+        check_allows!(SpecifierSet::from_str("~=1.4.5"), ["1.4.5", "1.4.9"], ["1.5.0", "1.4.4"]);
+        check_allows!(SpecifierSet::from_str("==1.1"),
+                      ["1.1", "1.1.0", "1.1+local"],
+                      ["1.1.1", "1.1.post1"]);
+        check_allows!(SpecifierSet::from_str("==1.1+local"), ["1.1+local"], ["1.1", "1.1+other"]);
+        check_allows!(SpecifierSet::from_str("==1.1.*"),
+                      ["1.1", "1.1.9", "1.1.post1"],
+                      ["1.2", "1.1a1"]);
+        check_allows!(SpecifierSet::from_str("!=1.1.*"), ["1.2", "1.0"], ["1.1.5"]);
+        check_allows!(SpecifierSet::from_str("!=1.1"), ["1.2"], ["1.1.0"]);
+        check_allows!(SpecifierSet::from_str("<=2.0"), ["2.0", "2.0+local"], ["2.0.post1"]);
+        check_allows!(SpecifierSet::from_str(">=2.0"), ["2.0", "3.0"], ["1.9"]);
+        check_allows!(SpecifierSet::from_str("<2.0"), ["1.9"], ["2.0", "2.0rc1"]);
+        check_allows!(SpecifierSet::from_str("<2.0rc1"), ["1.9"], ["2.0rc1", "2.0b1"]);
+        check_allows!(SpecifierSet::from_str(">1.7"), ["1.7.1"], ["1.7", "1.7.post2", "1.7+local"]);
+        check_allows!(SpecifierSet::from_str(">1.7.post2"), ["1.7.post3", "1.7.1"], ["1.7.post2"]);
+        check_allows!(SpecifierSet::from_str(">1.7a1"),
+                      ["1.7", "1.7.1+local"],
+                      ["1.7a1", "1.7+local"]);
+        check_allows!(SpecifierSet::from_str("===foobar"), [], ["1.0"]);
+        check_allows!(SpecifierSet::from_str("===1.0"), ["1.0"], ["1.0.0"]);
+        check_allows!(SpecifierSet::from_str(">=1.0,!=1.3.4.*,<2.0"),
+                      ["1.3.3", "1.9"],
+                      ["1.3.4.1", "2.0"]);
+        check_allows!(SpecifierSet::from_str(">=1.0b1"), ["1.0b2", "1.0"], ["1.0a9"]);
+        check_allows!(SpecifierSet::from_str(">=1.0"), [], ["1.1b1"]);
+        check_allows!(SpecifierSet::from_str(""), ["1.0"], ["1.0a1"]);
+
+        for s in &["1.0", "~=1", "~=1.0.*", ">=1.0.*", "==1.0a1.*", "<1.0+local", "=>1"] {
+            assert!(s.parse::<SpecifierSet>().is_err(), "{} should be invalid", s);
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        let pool: Vec<Version> = ["1.0", "1.1rc1", "1.1", "1.2.post1", "2.0"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let set: SpecifierSet = ">=1.0,<2".parse().unwrap();
+
+        assert_eq!(vec![pool[0].clone(), pool[2].clone(), pool[3].clone()],
+                   resolve(&pool, &constraints!(And, set)));
+    }
+
+}
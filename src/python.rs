@@ -0,0 +1,288 @@
+//! Exposes version numbers and constraints to Python.
+//!
+//! The **python** module provides you with a Python extension module, named
+//! `recital`, whose `Version`s support the comparison operators and `hash`,
+//! and whose `Requirement`s support `in`. Invalid input raises `ValueError`,
+//! and bumping a number that is already the largest raises `OverflowError`.
+//!
+//! ```python
+//! from recital import Requirement, Version, resolve
+//!
+//! a = Version.parse("1.0.0-alpha.1")
+//! b = Version.parse("1.0.0-alpha.beta")
+//!
+//! assert a < b
+//! assert str(b.bump_minor()) == "1.1.0"
+//! assert Version(1, 0, 0, pre=["alpha", 1]) == a
+//!
+//! requirement = Requirement.parse("^1.2", syntax="cargo")
+//! versions = [Version.parse(v) for v in ["1.1.0", "1.2.5", "2.0.0"]]
+//!
+//! assert resolve(versions, requirement) == [Version(1, 2, 5)]
+//! ```
+//!
+//! The extension is built with [maturin][] (see `pyproject.toml`), or with:
+//!
+//! ```sh
+//! cargo rustc --release --lib --features python,pyo3/extension-module --crate-type cdylib
+//! ```
+//!
+//! after which the library has to be renamed to `recital.so` (`recital.pyd`
+//! on Windows).
+//!
+//! [maturin]: https://www.maturin.rs/
+
+// The conversions are generated by the `pyo3` macros.
+#![allow(clippy::useless_conversion)]
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::{PyOverflowError, PyValueError};
+use pyo3::prelude::*;
+use super::builder::{Value, VersionBuilder};
+use super::cargo;
+use super::composer;
+use super::npm;
+use super::resolve::Constraint;
+use super::scheme::{Bump, VersionScheme};
+use super::version::{Identifier, Version};
+
+/// Represents a semantic version number.
+///
+/// Version numbers are immutable, so the bump methods return a new one.
+#[pyclass(name = "Version", module = "recital", frozen)]
+#[derive(Clone)]
+pub struct PyVersion {
+    version: Version,
+}
+
+/// Converts identifiers into a list of Python integers and strings.
+fn identifiers(py: Python, identifiers: &[Identifier]) -> Vec<PyObject> {
+    identifiers.iter()
+        .map(|i| match *i {
+            Identifier::Number(n) => n.into_py(py),
+            Identifier::Alpha(ref a) => a.into_py(py),
+        })
+        .collect()
+}
+
+/// Represents an identifier given from Python, as an integer or a string.
+#[derive(FromPyObject)]
+enum PyIdentifier {
+    Number(u64),
+    Negative(i64),
+    Text(String),
+}
+
+impl From<PyIdentifier> for Value {
+    fn from(identifier: PyIdentifier) -> Value {
+        match identifier {
+            PyIdentifier::Number(n) => Value::Number(n),
+            PyIdentifier::Negative(n) => Value::Negative(n),
+            PyIdentifier::Text(s) => Value::Text(s),
+        }
+    }
+}
+
+impl PyVersion {
+    /// Creates a copy of the version number that has been bumped, raising
+    /// `OverflowError` if the number to increment is already the largest.
+    fn bumped(&self, level: Bump) -> PyResult<PyVersion> {
+        let number = match level {
+            Bump::Major => self.version.major,
+            Bump::Minor => self.version.minor,
+            Bump::Patch => self.version.patch,
+        };
+
+        if number.checked_add(1).is_none() {
+            return Err(PyOverflowError::new_err(format!("Version number too large to bump: {}",
+                                                        self.version)));
+        }
+
+        let mut version = self.version.clone();
+
+        version.bump(level);
+
+        Ok(PyVersion { version })
+    }
+}
+
+#[pymethods]
+impl PyVersion {
+    /// Creates a version number, raising `ValueError` if any of the
+    /// pre-release or build identifiers are not valid.
+    #[new]
+    #[pyo3(signature = (major = 0, minor = 0, patch = 0, *, pre = Vec::new(), build = Vec::new()))]
+    fn new(major: u64,
+           minor: u64,
+           patch: u64,
+           pre: Vec<PyIdentifier>,
+           build: Vec<PyIdentifier>)
+           -> PyResult<PyVersion> {
+        let mut builder = VersionBuilder::new().major(major).minor(minor).patch(patch);
+
+        for identifier in pre {
+            builder = builder.pre(identifier);
+        }
+
+        for identifier in build {
+            builder = builder.build_identifier(identifier);
+        }
+
+        builder.build()
+            .map(|version| PyVersion { version })
+            .map_err(|error| PyValueError::new_err(error.to_string()))
+    }
+
+    /// Parses a string as a version number, raising `ValueError` if it is
+    /// not valid.
+    #[staticmethod]
+    fn parse(s: &str) -> PyResult<PyVersion> {
+        s.parse()
+            .map(|version| PyVersion { version })
+            .map_err(PyValueError::new_err)
+    }
+
+    #[getter]
+    fn major(&self) -> u64 {
+        self.version.major
+    }
+
+    #[getter]
+    fn minor(&self) -> u64 {
+        self.version.minor
+    }
+
+    #[getter]
+    fn patch(&self) -> u64 {
+        self.version.patch
+    }
+
+    /// The pre-release identifiers, as integers and strings.
+    #[getter]
+    fn pre(&self, py: Python) -> Vec<PyObject> {
+        identifiers(py, &self.version.pre)
+    }
+
+    /// The build identifiers, as integers and strings.
+    #[getter]
+    fn build(&self, py: Python) -> Vec<PyObject> {
+        identifiers(py, &self.version.build)
+    }
+
+    /// Whether the major version number is not zero (`0`), and there are no
+    /// pre-release identifiers.
+    #[getter]
+    fn is_stable(&self) -> bool {
+        self.version.is_stable()
+    }
+
+    fn bump_major(&self) -> PyResult<PyVersion> {
+        self.bumped(Bump::Major)
+    }
+
+    fn bump_minor(&self) -> PyResult<PyVersion> {
+        self.bumped(Bump::Minor)
+    }
+
+    fn bump_patch(&self) -> PyResult<PyVersion> {
+        self.bumped(Bump::Patch)
+    }
+
+    fn __richcmp__(&self, other: PyRef<PyVersion>, op: CompareOp) -> bool {
+        op.matches(self.version.cmp(&other.version))
+    }
+
+    /// Hashes the version number without its build identifiers, since they
+    /// are ignored when version numbers are compared.
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        (self.version.major, self.version.minor, self.version.patch).hash(&mut hasher);
+
+        for identifier in &self.version.pre {
+            match *identifier {
+                Identifier::Number(n) => (0, n).hash(&mut hasher),
+                Identifier::Alpha(ref a) => (1, a).hash(&mut hasher),
+            }
+        }
+
+        hasher.finish()
+    }
+
+    fn __str__(&self) -> String {
+        self.version.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Version('{}')", self.version)
+    }
+}
+
+/// Represents a version requirement.
+#[pyclass(name = "Requirement", module = "recital", unsendable)]
+pub struct PyRequirement {
+    constraint: Box<dyn Constraint>,
+    source: String,
+}
+
+#[pymethods]
+impl PyRequirement {
+    /// Parses a version requirement, raising `ValueError` if it is not valid.
+    ///
+    /// The syntax is that of `cargo` (`^1.2, <1.5`), `npm` (`^1.2 || 2.x`),
+    /// or `composer` (`^1.2|^2.0`).
+    #[staticmethod]
+    #[pyo3(signature = (s, syntax = "cargo"))]
+    fn parse(s: &str, syntax: &str) -> PyResult<PyRequirement> {
+        let constraint: Result<Box<dyn Constraint>, String> = match syntax {
+            "cargo" => s.parse::<cargo::Requirement>().map(|r| Box::new(r) as _),
+            "composer" => s.parse::<composer::Requirement>().map(|r| Box::new(r) as _),
+            "npm" => s.parse::<npm::Range>().map(|r| Box::new(r) as _),
+            _ => return Err(PyValueError::new_err(format!("Unknown syntax: {}", syntax))),
+        };
+
+        constraint.map(|constraint| {
+                PyRequirement {
+                    constraint,
+                    source: s.to_string(),
+                }
+            })
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Checks if a version number satisfies the requirement.
+    fn allows(&self, version: PyRef<PyVersion>) -> bool {
+        self.constraint.allows(&version.version)
+    }
+
+    fn __contains__(&self, version: PyRef<PyVersion>) -> bool {
+        self.allows(version)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Requirement('{}')", self.source)
+    }
+}
+
+/// Returns the version numbers that satisfy a requirement, in the same order.
+#[pyfunction]
+fn resolve(versions: Vec<PyRef<PyVersion>>,
+           requirement: PyRef<PyRequirement>)
+           -> Vec<PyVersion> {
+    versions.iter()
+        .filter(|v| requirement.constraint.allows(&v.version))
+        .map(|v| (**v).clone())
+        .collect()
+}
+
+/// Initializes the `recital` Python module.
+#[pymodule]
+fn recital(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<PyVersion>()?;
+    m.add_class::<PyRequirement>()?;
+    m.add_function(wrap_pyfunction!(self::resolve, m)?)?;
+
+    Ok(())
+}
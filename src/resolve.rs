@@ -52,12 +52,23 @@
 //! ```
 //!
 //! As you can probably tell, the macros make this easy and concise.
+//!
+//! Constraints are not limited to semantic version numbers. Every type in this
+//! module is generic over a `VersionScheme`, which defaults to `Version`, so
+//! the same constraints can be used with any other scheme.
+use super::scheme::VersionScheme;
 use super::version::Version;
 
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+
 /// Defines how a constraint must be implemented.
-pub trait Constraint {
+///
+/// The type of version number that is checked defaults to `Version`, but may
+/// be any type that implements `VersionScheme`.
+pub trait Constraint<V = Version> {
     /// Checks if the given version number satisifies this constraint.
-    fn allows(&self, version: &Version) -> bool;
+    fn allows(&self, version: &V) -> bool;
 }
 
 /// Represents a set of constraints.
@@ -87,20 +98,20 @@ pub trait Constraint {
 /// }
 /// # }
 /// ```
-pub enum Constraints {
+pub enum Constraints<V = Version> {
     /// All constraints must be satisified.
-    And(Vec<Box<Constraint>>),
+    And(Vec<Box<dyn Constraint<V>>>),
 
     /// At least one constraint must be satisified.
-    Or(Vec<Box<Constraint>>),
+    Or(Vec<Box<dyn Constraint<V>>>),
 }
 
-impl Constraint for Constraints {
-    fn allows(&self, version: &Version) -> bool {
+impl<V> Constraint<V> for Constraints<V> {
+    fn allows(&self, version: &V) -> bool {
         match *self {
             Constraints::And(ref constraints) => {
                 for constraint in constraints {
-                    if !constraint.allows(&version) {
+                    if !constraint.allows(version) {
                         return false;
                     }
                 }
@@ -110,7 +121,7 @@ impl Constraint for Constraints {
 
             Constraints::Or(ref constraints) => {
                 for constraint in constraints {
-                    if constraint.allows(&version) {
+                    if constraint.allows(version) {
                         return true;
                     }
                 }
@@ -152,14 +163,30 @@ impl Constraint for Constraints {
 #[macro_export]
 macro_rules! constraints {
     ($a:ident, $($b:expr), *) => {
+        $crate::resolve::Constraints::$a($crate::__private::vec![$(
+            $crate::__private::Box::new($b)
+                as $crate::__private::Box<dyn $crate::resolve::Constraint<_>>
+        ),*])
+    }
+}
+
+/// Asserts that a parsed constraint allows each version number in the first
+/// list, and none of those in the second.
+#[cfg(test)]
+macro_rules! check_allows {
+    ($constraint:expr, [$($yes:expr),*], [$($no:expr),*]) => {
         {
-            let mut constraints = Vec::<Box<$crate::resolve::Constraint>>::new();
+            let constraint = $constraint.unwrap();
 
             $(
-                constraints.push(Box::new($b));
+                assert!(constraint.allows(&$yes.parse().unwrap()),
+                        "{} should allow {}", stringify!($constraint), $yes);
             )*
 
-            $crate::resolve::Constraints::$a(constraints)
+            $(
+                assert!(!constraint.allows(&$no.parse().unwrap()),
+                        "{} should not allow {}", stringify!($constraint), $no);
+            )*
         }
     }
 }
@@ -193,28 +220,29 @@ macro_rules! constraints {
 /// doing direct comparisons (e.g. `a > b`). You are expected to use multiple
 /// instances of this enum as a set of constraints. Please see the
 /// `Constraints` enum documentation.
-pub enum Operation {
+#[derive(Clone, Debug)]
+pub enum Operation<V = Version> {
     /// Match the exact version number. (=)
-    Exactly(Version),
+    Exactly(V),
 
     /// Exclude the exact version number. (!=)
-    ExactlyNot(Version),
+    ExactlyNot(V),
 
     /// Exclusively match any greater version number. (>)
-    GreaterThan(Version),
+    GreaterThan(V),
 
     /// Inclusively match any greater version number. (>=)
-    GreaterThanOrEqualTo(Version),
+    GreaterThanOrEqualTo(V),
 
     /// Exclusively match any lesser version number. (<)
-    LessThan(Version),
+    LessThan(V),
 
     /// Inclusively match any lesser version number. (<=)
-    LessThanOrEqualTo(Version),
+    LessThanOrEqualTo(V),
 }
 
-impl Constraint for Operation {
-    fn allows(&self, version: &Version) -> bool {
+impl<V: VersionScheme> Constraint<V> for Operation<V> {
+    fn allows(&self, version: &V) -> bool {
         match *self {
             Operation::Exactly(ref base) => *base == *version,
             Operation::ExactlyNot(ref base) => *base != *version,
@@ -254,11 +282,11 @@ impl Constraint for Operation {
 /// let versions = resolve(&pool, &constraints);
 /// # }
 /// ```
-pub fn resolve(versions: &Vec<Version>, constraints: &Constraints) -> Vec<Version> {
+pub fn resolve<V: VersionScheme>(versions: &Vec<V>, constraints: &Constraints<V>) -> Vec<V> {
     let mut allowed = Vec::new();
 
     for version in versions {
-        if constraints.allows(&version) {
+        if constraints.allows(version) {
             allowed.push(version.clone());
         }
     }
@@ -266,12 +294,15 @@ pub fn resolve(versions: &Vec<Version>, constraints: &Constraints) -> Vec<Versio
     allowed
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
 
-    use super::Constraint;
+    use proptest::prelude::*;
+    use super::{Constraint, Constraints};
     use super::Operation::*;
     use super::resolve;
+    use super::super::arbitrary::ConstraintTree;
+    use super::super::version::Version;
 
     #[test]
     fn test_exact() {
@@ -363,4 +394,52 @@ mod tests {
                    vec![version!(1, 3, 0), version!(1, 3, 1), version!(1, 4, 0)]);
     }
 
+    /// Creates the tree that allows exactly what the given tree does not.
+    fn complement(tree: ConstraintTree) -> ConstraintTree {
+        match tree {
+            ConstraintTree::Operation(operation) => {
+                ConstraintTree::Operation(match operation {
+                    Exactly(v) => ExactlyNot(v),
+                    ExactlyNot(v) => Exactly(v),
+                    GreaterThan(v) => LessThanOrEqualTo(v),
+                    GreaterThanOrEqualTo(v) => LessThan(v),
+                    LessThan(v) => GreaterThanOrEqualTo(v),
+                    LessThanOrEqualTo(v) => GreaterThan(v),
+                })
+            }
+            ConstraintTree::And(trees) => {
+                ConstraintTree::Or(trees.into_iter().map(complement).collect())
+            }
+            ConstraintTree::Or(trees) => {
+                ConstraintTree::And(trees.into_iter().map(complement).collect())
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_constraints_tree(tree: ConstraintTree, v: Version) {
+            let constraints: Constraints = tree.clone().into();
+
+            prop_assert_eq!(constraints.allows(&v), tree.allows(&v));
+        }
+
+        #[test]
+        fn test_constraints_complement(tree: ConstraintTree, v: Version) {
+            let constraints: Constraints = tree.clone().into();
+            let complement: Constraints = complement(tree).into();
+
+            prop_assert_ne!(constraints.allows(&v), complement.allows(&v));
+        }
+
+        #[test]
+        fn test_resolve_allows(pool: Vec<Version>, tree: ConstraintTree) {
+            let constraints: Constraints = tree.into();
+            let allowed = resolve(&pool, &constraints);
+
+            prop_assert!(allowed.iter().all(|v| constraints.allows(v)));
+            prop_assert_eq!(allowed.len(), pool.iter().filter(|v| constraints.allows(v)).count());
+        }
+    }
+
 }
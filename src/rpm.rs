@@ -0,0 +1,500 @@
+//! Creates, parses, and compares RPM package version numbers.
+//!
+//! The **rpm** module provides you with a `Version` that is ordered using the
+//! same algorithm as `rpmvercmp`. An RPM version number has an optional epoch,
+//! a version, and an optional release, in the form `[epoch:]version[-release]`.
+//!
+//! ```
+//! use recital::rpm::Version;
+//!
+//! let a: Version = "1.0~rc1-1".parse().unwrap();
+//! let b: Version = "1.0-1".parse().unwrap();
+//! let c: Version = "1.0^20240101-1".parse().unwrap();
+//!
+//! assert!(a < b && b < c);
+//! ```
+//!
+//! Dependency relations (`>= 1.2-3`, `< 2.0`) can be parsed as a `Relation`,
+//! which is a constraint. The release is only compared if the relation has
+//! one, so `= 1.2` allows any release of `1.2`.
+//!
+//! ```
+//! use recital::rpm::{Relation, Version};
+//! use recital::resolve::Constraint;
+//!
+//! let relation: Relation = "= 1.2".parse().unwrap();
+//!
+//! assert!(relation.allows(&"1.2-5.fc40".parse::<Version>().unwrap()));
+//! assert!(!relation.allows(&"1.2.1-1".parse::<Version>().unwrap()));
+//! ```
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use super::debian::letters_only;
+use super::resolve::Constraint;
+use super::scheme::{bump_dotted, Bump, VersionScheme};
+use super::version;
+
+/// Represents an RPM package version number.
+#[derive(Clone, Debug, Default)]
+pub struct Version {
+    /// The epoch, which is zero (`0`) if not given.
+    pub epoch: u64,
+
+    /// The version.
+    pub version: String,
+
+    /// The release, if given.
+    pub release: Option<String>,
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}:", self.epoch)?;
+        }
+
+        write!(f, "{}", self.version)?;
+
+        if let Some(ref release) = self.release {
+            write!(f, "-{}", release)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Enables parsing of strings as RPM version numbers.
+///
+/// The version and release may only contain alphanumerics and `.`, `_`, `+`,
+/// `~`, and `^`, and neither may be empty.
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Version, String> {
+        let invalid = || format!("Invalid version number: {}", s);
+        let s = s.trim();
+
+        let (epoch, rest) = match s.find(':') {
+            Some(colon) => (s[..colon].parse().map_err(|_| invalid())?, &s[colon + 1..]),
+            None => (0, s),
+        };
+
+        let (version, release) = match rest.rfind('-') {
+            Some(hyphen) => (&rest[..hyphen], Some(&rest[hyphen + 1..])),
+            None => (rest, None),
+        };
+
+        let valid = |part: &str| {
+            !part.is_empty() &&
+            part.chars().all(|c| c.is_ascii_alphanumeric() || "._+~^".contains(c))
+        };
+
+        if !valid(version) || !release.map(valid).unwrap_or(true) {
+            return Err(invalid());
+        }
+
+        Ok(Version {
+            epoch,
+            version: version.to_string(),
+            release: release.map(|r| r.to_string()),
+        })
+    }
+}
+
+impl Eq for Version {}
+
+/// Version numbers without a release sort before those with one.
+impl Ord for Version {
+    fn cmp(&self, other: &Version) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| compare(&self.version, &other.version))
+            .then_with(|| match (&self.release, &other.release) {
+                (Some(a), Some(b)) => compare(a, b),
+                (a, b) => a.is_some().cmp(&b.is_some()),
+            })
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Version) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Version) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl VersionScheme for Version {
+    /// Increments the leading numeric components of the version.
+    ///
+    /// Anything after the numeric components is removed, and the release is
+    /// reset to `1` if there was one.
+    fn bump(&mut self, level: Bump) {
+        self.version = bump_dotted(&self.version, level);
+
+        if self.release.is_some() {
+            self.release = Some("1".to_string());
+        }
+    }
+
+    /// A version number is stable if its version has no tilde (`~`).
+    fn is_stable(&self) -> bool {
+        !self.version.contains('~')
+    }
+}
+
+impl Version {
+    /// Converts a semantic version number into an RPM version number.
+    ///
+    /// The pre-release identifiers follow a tilde (`~`), so that they sort
+    /// before the release. Numeric identifiers are prefixed with a caret (`^`)
+    /// so that they sort before alphanumeric ones (`1.0.0-rc.1` is
+    /// `1.0.0~rc.^1`). Build identifiers are dropped.
+    ///
+    /// The order of the version numbers is kept for every conversion that
+    /// succeeds. This requires alphanumeric identifiers to only have letters,
+    /// since `rpmvercmp` splits letters from digits.
+    pub fn from_semver(semver: &version::Version) -> Result<Version, String> {
+        let mut string = format!("{}.{}.{}", semver.major, semver.minor, semver.patch);
+
+        if !semver.pre.is_empty() {
+            let identifiers: Vec<String> = letters_only(semver)?
+                .into_iter()
+                .map(|i| if i.starts_with(|c: char| c.is_ascii_digit()) {
+                    format!("^{}", i)
+                } else {
+                    i
+                })
+                .collect();
+
+            string.push('~');
+            string.push_str(&identifiers.join("."));
+        }
+
+        Ok(Version {
+            version: string,
+            ..Default::default()
+        })
+    }
+}
+
+/// Represents the operator of a dependency relation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operator {
+    /// Less than (`<`).
+    Less,
+
+    /// Less than or equal to (`<=`).
+    LessOrEqual,
+
+    /// Equal to (`=`).
+    Equal,
+
+    /// Greater than or equal to (`>=`).
+    GreaterOrEqual,
+
+    /// Greater than (`>`).
+    Greater,
+}
+
+/// Represents a dependency relation (e.g. `>= 1.2-3`).
+#[derive(Clone, Debug)]
+pub struct Relation {
+    /// The operator.
+    pub operator: Operator,
+
+    /// The version number being compared against.
+    pub version: Version,
+}
+
+impl Constraint<Version> for Relation {
+    /// Compares the version number, ignoring its release if this relation
+    /// does not have one.
+    fn allows(&self, version: &Version) -> bool {
+        let ordering = match self.version.release {
+            Some(_) => version.cmp(&self.version),
+            None => {
+                version.epoch
+                    .cmp(&self.version.epoch)
+                    .then_with(|| compare(&version.version, &self.version.version))
+            }
+        };
+
+        match self.operator {
+            Operator::Less => ordering == Ordering::Less,
+            Operator::LessOrEqual => ordering != Ordering::Greater,
+            Operator::Equal => ordering == Ordering::Equal,
+            Operator::GreaterOrEqual => ordering != Ordering::Less,
+            Operator::Greater => ordering == Ordering::Greater,
+        }
+    }
+}
+
+/// Enables parsing of strings as dependency relations.
+///
+/// The operators are `<`, `<=`, `=`, `>=`, and `>`. The `<<` and `>>`
+/// operators are also accepted as `<` and `>`, for relations written for
+/// Debian packages.
+impl FromStr for Relation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Relation, String> {
+        let trimmed = s.trim();
+
+        for &(op, operator) in &[("<<", Operator::Less),
+                                 ("<=", Operator::LessOrEqual),
+                                 (">=", Operator::GreaterOrEqual),
+                                 (">>", Operator::Greater),
+                                 ("=", Operator::Equal),
+                                 ("<", Operator::Less),
+                                 (">", Operator::Greater)] {
+            if let Some(rest) = trimmed.strip_prefix(op) {
+                return Ok(Relation {
+                    operator,
+                    version: rest.parse()?,
+                });
+            }
+        }
+
+        Err(format!("Invalid relation: {}", s))
+    }
+}
+
+/// Compares two versions or releases the way `rpmvercmp` does.
+///
+/// The strings are split into segments of letters or digits, with anything
+/// else as a separator. A tilde (`~`) sorts before everything, even the end of
+/// the string, and a caret (`^`) sorts after the end of the string but before
+/// anything else. A numeric segment is always newer than a letter segment.
+pub fn compare(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+    let separator = |c: u8| !c.is_ascii_alphanumeric() && c != b'~' && c != b'^';
+
+    loop {
+        while i < a.len() && separator(a[i]) {
+            i += 1;
+        }
+
+        while j < b.len() && separator(b[j]) {
+            j += 1;
+        }
+
+        let (ac, bc) = (a.get(i).cloned(), b.get(j).cloned());
+
+        if ac == Some(b'~') || bc == Some(b'~') {
+            if ac != Some(b'~') {
+                return Ordering::Greater;
+            }
+
+            if bc != Some(b'~') {
+                return Ordering::Less;
+            }
+
+            i += 1;
+            j += 1;
+
+            continue;
+        }
+
+        if ac == Some(b'^') || bc == Some(b'^') {
+            if ac.is_none() {
+                return Ordering::Less;
+            }
+
+            if bc.is_none() {
+                return Ordering::Greater;
+            }
+
+            if ac != Some(b'^') {
+                return Ordering::Greater;
+            }
+
+            if bc != Some(b'^') {
+                return Ordering::Less;
+            }
+
+            i += 1;
+            j += 1;
+
+            continue;
+        }
+
+        if ac.is_none() || bc.is_none() {
+            return ac.is_some().cmp(&bc.is_some());
+        }
+
+        let numeric = a[i].is_ascii_digit();
+        let kind = |c: &u8| if numeric {
+            c.is_ascii_digit()
+        } else {
+            c.is_ascii_alphabetic()
+        };
+
+        let a_end = i + a[i..].iter().take_while(|c| kind(c)).count();
+        let b_end = j + b[j..].iter().take_while(|c| kind(c)).count();
+
+        if b_end == j {
+            return if numeric {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+        }
+
+        let (mut x, mut y) = (&a[i..a_end], &b[j..b_end]);
+
+        if numeric {
+            while x.first() == Some(&b'0') {
+                x = &x[1..];
+            }
+
+            while y.first() == Some(&b'0') {
+                y = &y[1..];
+            }
+
+            let ordering = x.len().cmp(&y.len());
+
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+
+        let ordering = x.cmp(y);
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+
+        i = a_end;
+        j = b_end;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::cmp::Ordering;
+    use super::{compare, Operator, Relation, Version};
+    use super::super::resolve::Constraint;
+    use super::super::scheme::{Bump, VersionScheme};
+
+    #[test]
+    fn test_parse() {
+        let version: Version = "3:1.0.2k-25.el8".parse().unwrap();
+
+        assert_eq!(3, version.epoch);
+        assert_eq!("1.0.2k", version.version);
+        assert_eq!(Some("25.el8".to_string()), version.release);
+        assert_eq!("3:1.0.2k-25.el8", version.to_string());
+        assert_eq!(None, "1.0".parse::<Version>().unwrap().release);
+
+        for s in &["", "1.0-", "-1", "x:1.0", "1.0 1"] {
+            assert!(s.parse::<Version>().is_err(), "{} should be invalid", s);
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(Ordering::Equal, compare("1.0", "1.0"));
+        assert_eq!(Ordering::Equal, compare("1.01", "1.1"));
+        assert_eq!(Ordering::Equal, compare("1_0", "1.0"));
+        assert_eq!(Ordering::Less, compare("1.0", "1.0.1"));
+        assert_eq!(Ordering::Less, compare("1.9", "1.10"));
+        assert_eq!(Ordering::Less, compare("1.0a", "1.0.1"));
+        assert_eq!(Ordering::Less, compare("a", "1"));
+        assert_eq!(Ordering::Less, compare("1.0~rc1", "1.0"));
+        assert_eq!(Ordering::Less, compare("1.0~~", "1.0~"));
+        assert_eq!(Ordering::Less, compare("1.0", "1.0^git1"));
+        assert_eq!(Ordering::Less, compare("1.0^git1", "1.0.1"));
+        assert_eq!(Ordering::Less, compare("1.0^git1", "1.0a"));
+    }
+
+    #[test]
+    fn test_ordering() {
+        let ordered = ["1.0~rc1", "1.0", "1.0-1", "1.0-2", "1.0^1", "1.1", "1:0.1"];
+
+        let versions: Vec<Version> = ordered.iter().map(|s| s.parse().unwrap()).collect();
+
+        for pair in versions.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+
+        assert_eq!("1.0".parse::<Version>().unwrap(), "0:1.0".parse::<Version>().unwrap());
+    }
+
+    #[test]
+    fn test_relation() {
+        assert_eq!(Operator::Less, "<< 2.0".parse::<Relation>().unwrap().operator);
+
+        for &(s, version, allowed) in &[(">= 1.2-3", "1.2-3", true),
+                                        (">= 1.2-3", "1.2-2", false),
+                                        (">= 1.2", "1.2-1", true),
+                                        ("< 2.0", "2.0~rc1", true),
+                                        ("< 2.0", "2.0-1", false),
+                                        ("> 2.0", "2.0.1", true),
+                                        ("> 2.0", "2.0-9", false),
+                                        ("= 2.0", "2.0-9", true),
+                                        ("= 2.0-1", "2.0-9", false),
+                                        ("<= 2.0", "2.0-9", true)] {
+            let relation: Relation = s.parse().unwrap();
+
+            assert_eq!(allowed,
+                       relation.allows(&version.parse().unwrap()),
+                       "{} allows {}",
+                       s,
+                       version);
+        }
+
+        assert!("~ 2.0".parse::<Relation>().is_err());
+        assert!("> ".parse::<Relation>().is_err());
+    }
+
+    #[test]
+    fn test_bump() {
+        let mut version: Version = "2.4~rc1-3.el9".parse().unwrap();
+
+        version.bump(Bump::Major);
+
+        assert_eq!("3.0-1", version.to_string());
+        assert!(version.is_stable());
+        assert!(!"2.4~rc1".parse::<Version>().unwrap().is_stable());
+    }
+
+    #[test]
+    fn test_from_semver() {
+        let ordered = ["1.0.0-1",
+                       "1.0.0-2",
+                       "1.0.0-10",
+                       "1.0.0-alpha",
+                       "1.0.0-alpha.1",
+                       "1.0.0-alpha.beta",
+                       "1.0.0-beta",
+                       "1.0.0-beta.2",
+                       "1.0.0-beta.11",
+                       "1.0.0-rc.1",
+                       "1.0.0",
+                       "1.0.1",
+                       "1.10.0"];
+
+        for pair in ordered.windows(2) {
+            let a = Version::from_semver(&pair[0].parse().unwrap()).unwrap();
+            let b = Version::from_semver(&pair[1].parse().unwrap()).unwrap();
+
+            assert!(a < b, "{} < {}", a, b);
+        }
+
+        assert_eq!("1.0.0~rc.^1",
+                   Version::from_semver(&"1.0.0-rc.1+build.5".parse().unwrap())
+                       .unwrap()
+                       .to_string());
+
+        assert!(Version::from_semver(&"1.0.0-rc1".parse().unwrap()).is_err());
+    }
+
+}
@@ -0,0 +1,242 @@
+//! Finds semantic version numbers in free-form text.
+//!
+//! The **scan** module provides you with `scan`, which finds every version
+//! number in a piece of text, such as a log line, release notes, an HTML
+//! page, or a `FROM` line in a Dockerfile. Each version number is returned
+//! with its byte span in the text.
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate recital;
+//! use recital::scan::scan;
+//!
+//! # fn main() {
+//! let text = "FROM node:18.17.1-alpine3.18 (was 18.16.0, see v19.0.0)";
+//! let found = scan(text, false);
+//!
+//! assert_eq!(2, found.len());
+//! assert_eq!(version!(18, 17, 1, vec![id!("alpine3"), id!(18)]), found[0].version);
+//! assert_eq!("18.17.1-alpine3.18", &text[found[0].span.clone()]);
+//! assert_eq!(version!(18, 16, 0), found[1].version);
+//!
+//! assert_eq!(3, scan(text, true).len());
+//! # }
+//! ```
+//!
+//! A version number has to stand on its own to be found: it cannot be part
+//! of a word (`python3.1.2.3`) or of a longer dotted number, such as an IP
+//! address (`192.168.1.1`). Version numbers with leading zeros, which are not
+//! valid, and ones that look like a date (`2024.1.15`) are also skipped.
+use std::ops::Range;
+use super::parser::parse_prefix;
+use super::version::{Identifier, Version};
+
+/// Represents a version number that was found in text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Match {
+    /// The version number.
+    pub version: Version,
+
+    /// The byte span of the version number in the text.
+    ///
+    /// In lenient mode, this includes the `v` prefix.
+    pub span: Range<usize>,
+}
+
+/// Checks if a byte can be part of the word or number before a version.
+fn is_joined_before(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'.' || byte == b'+' || byte >= 0x80
+}
+
+/// Checks if a byte can be part of the word or number after a version.
+fn is_joined_after(bytes: &[u8], end: usize) -> bool {
+    match bytes.get(end) {
+        Some(&b'.') => bytes.get(end + 1).map(|b| b.is_ascii_digit()).unwrap_or(false),
+        Some(&byte) => byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 0x80,
+        None => false,
+    }
+}
+
+/// Checks if any of the identifiers are empty (`1.2.3-rc..1`).
+fn has_empty(identifiers: &[Identifier]) -> bool {
+    identifiers.iter().any(|i| match *i {
+        Identifier::Alpha(ref a) => a.is_empty(),
+        Identifier::Number(_) => false,
+    })
+}
+
+/// Checks if any number in the text is zero padded (`01`).
+///
+/// Zero padded numbers are parsed as numbers, so the text is checked instead.
+/// Only the version numbers and the numeric pre-release identifiers are
+/// checked, since build metadata (`+001`) and alphanumeric identifiers
+/// (`rc-01`) may start with a zero.
+fn has_padding(text: &str) -> bool {
+    let text = text.split('+').next().unwrap_or(text);
+    let (numbers, pre) = text.split_once('-').unwrap_or((text, ""));
+
+    numbers.split('.').chain(pre.split('.')).any(|part| {
+        part.len() > 1 && part.starts_with('0') && part.bytes().all(|b| b.is_ascii_digit())
+    })
+}
+
+/// Checks if the version number is more likely to be a date.
+fn is_date(version: &Version) -> bool {
+    (1900..2100).contains(&version.major) && (1..=12).contains(&version.minor) &&
+    (1..=31).contains(&version.patch) && version.pre.is_empty() && version.build.is_empty()
+}
+
+/// Parses a version number at the given position, returning it and its end.
+///
+/// If there are empty pre-release or build identifiers, only the major,
+/// minor, and patch version numbers are used. A version number that would
+/// not be valid when parsed on its own is not returned.
+fn parse_at(text: &str, start: usize) -> Option<(Version, usize)> {
+    let (mut version, length) = parse_prefix(&text.as_bytes()[start..])?;
+    let mut end = start + length;
+
+    if has_empty(&version.pre) || has_empty(&version.build) {
+        end = start + text[start..end].find(['-', '+']).unwrap_or(length);
+        version.pre.clear();
+        version.build.clear();
+    }
+
+    if has_padding(&text[start..end]) || version.validate().is_err() {
+        return None;
+    }
+
+    Some((version, end))
+}
+
+/// Finds every semantic version number in the text.
+///
+/// When `lenient` is `true`, a version number may also have a `v` or `V`
+/// prefix (`v1.2.3`), which is otherwise treated as part of a word.
+pub fn scan(text: &str, lenient: bool) -> Vec<Match> {
+    let bytes = text.as_bytes();
+    let mut found = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let joined = i > 0 && is_joined_before(bytes[i - 1]);
+        let prefixed = lenient && !joined && (bytes[i] == b'v' || bytes[i] == b'V') &&
+                       bytes.get(i + 1).map(|b| b.is_ascii_digit()).unwrap_or(false);
+        let start = if prefixed { i + 1 } else { i };
+
+        if (prefixed || !joined) && bytes[start].is_ascii_digit() {
+            if let Some((version, end)) = parse_at(text, start) {
+                if !is_joined_after(bytes, end) {
+                    if !is_date(&version) {
+                        found.push(Match {
+                            version,
+                            span: i..end,
+                        });
+                    }
+
+                    i = end;
+
+                    continue;
+                }
+            }
+        }
+
+        i += 1;
+    }
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{scan, Match};
+
+    #[test]
+    fn test_scan() {
+        let text = "Upgraded from 1.2.3 to 2.0.0-rc.1.";
+        let found = scan(text, false);
+
+        assert_eq!(2, found.len());
+        assert_eq!(version!(1, 2, 3), found[0].version);
+        assert_eq!("1.2.3", &text[found[0].span.clone()]);
+        assert_eq!(version!(2, 0, 0, vec![id!("rc"), id!(1)]), found[1].version);
+        assert_eq!("2.0.0-rc.1", &text[found[1].span.clone()]);
+
+        assert_eq!(vec![Match {
+                            version: version!(3, 1, 4),
+                            span: 7..12,
+                        }],
+                   scan("<li><b>3.1.4</b></li>", false));
+        assert_eq!(vec![Match {
+                            version: version!(1, 25, 3),
+                            span: 6..12,
+                        }],
+                   scan("nginx-1.25.3.tar.gz", false));
+        assert_eq!(vec![Match {
+                            version: version!(1, 0, 0, vec![], vec![id!("build"), id!(7)]),
+                            span: 1..14,
+                        }],
+                   scan("(1.0.0+build.7)", false));
+        assert_eq!(vec![Match {
+                            version: version!(1, 2, 3),
+                            span: 0..5,
+                        }],
+                   scan("1.2.3-", false));
+        assert_eq!(vec![Match {
+                            version: version!(1, 2, 3, vec![id!("rc")]),
+                            span: 0..8,
+                        }],
+                   scan("1.2.3-rc..1 is broken", false));
+
+        let text = "1.0.0-alpha+001, 1.2.3-rc-01 and 1.2.3-x.7z.92";
+        let spans: Vec<&str> = scan(text, false).into_iter().map(|m| &text[m.span]).collect();
+
+        assert_eq!(vec!["1.0.0-alpha+001", "1.2.3-rc-01", "1.2.3-x.7z.92"], spans);
+        assert!(scan("release 1.2.3-99999999999999999999999 is out", false).is_empty());
+        assert!(scan("", false).is_empty());
+    }
+
+    #[test]
+    fn test_scan_span() {
+        let text = "été 1.2.3 — v4.5.6";
+        let found = scan(text, true);
+
+        assert_eq!(2, found.len());
+        assert_eq!("1.2.3", &text[found[0].span.clone()]);
+        assert_eq!(version!(4, 5, 6), found[1].version);
+        assert_eq!("v4.5.6", &text[found[1].span.clone()]);
+
+        let found = scan(text, false);
+
+        assert_eq!(1, found.len());
+        assert_eq!(version!(1, 2, 3), found[0].version);
+        assert_eq!("1.2.3", &text[found[0].span.clone()]);
+    }
+
+    #[test]
+    fn test_scan_lenient() {
+        assert!(scan("v1.2.3 and V1.2.3", false).is_empty());
+        assert_eq!(2, scan("v1.2.3 and V1.2.3", true).len());
+        assert_eq!(0, scan("dev1.2.3", true).len());
+        assert_eq!(0, scan("vv1.2.3", true).len());
+    }
+
+    #[test]
+    fn test_scan_false_positives() {
+        for text in &["connect to 192.168.1.1:8080",
+                      "10.0.0.255",
+                      "released 2024.1.15 and 2024.01.15",
+                      "python3.1.2 and abc1.2.3 and 1.2.3abc and 1.2.3_x",
+                      "01.2.3 and 1.02.3 and 1.2.3-01 and 1.2.3-x.7z.092",
+                      "1.2 or 1.2."] {
+            assert!(scan(text, false).is_empty(), "{} should not have versions", text);
+        }
+
+        assert_eq!(vec![Match {
+                            version: version!(2024, 13, 1),
+                            span: 0..9,
+                        }],
+                   scan("2024.13.1", false));
+    }
+
+}
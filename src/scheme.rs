@@ -0,0 +1,265 @@
+//! Defines what a versioning scheme must be able to do.
+//!
+//! The **scheme** module provides you with the `VersionScheme` trait, which
+//! is implemented by every type of version number that can be used with the
+//! constraints in the `resolve` module. A scheme must be able to parse and
+//! display its version numbers, order them, and increment them.
+//!
+//! `Version` is the default implementation, so semantic version numbers can
+//! be used without naming a scheme at all.
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate recital;
+//! use recital::scheme::{Bump, VersionScheme};
+//! use recital::version::Version;
+//!
+//! # fn main() {
+//! fn next<V: VersionScheme>(version: &str) -> Result<String, String> {
+//!     let mut version: V = version.parse()?;
+//!
+//!     version.bump(Bump::Minor);
+//!
+//!     Ok(version.to_string())
+//! }
+//!
+//! assert_eq!(Ok("1.3.0".to_string()), next::<Version>("1.2.3-rc.1"));
+//! # }
+//! ```
+use std::fmt::Display;
+use std::str::FromStr;
+use super::version::Version;
+
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+
+/// Represents how much a version number is incremented.
+///
+/// The levels are ordered from the least to the most significant, so the
+/// greater of two levels is the one that should win when both apply to the
+/// same package. How each level is applied is up to the scheme.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Bump {
+    /// Increment the patch version number.
+    Patch,
+
+    /// Increment the minor version number.
+    Minor,
+
+    /// Increment the major version number.
+    Major,
+}
+
+impl Bump {
+    /// Applies this level of increment to the given version number.
+    pub fn apply<V: VersionScheme>(&self, version: &mut V) {
+        version.bump(*self);
+    }
+}
+
+/// Defines how a versioning scheme must be implemented.
+///
+/// Version numbers are parsed with `FromStr`, displayed with `Display`, and
+/// ordered with `Ord`. The display of a parsed version number does not have
+/// to be the original string, as long as it can be parsed again into an
+/// equal version number.
+pub trait VersionScheme: Clone + Display + FromStr<Err = String> + Ord {
+    /// Increments the version number by the given level.
+    fn bump(&mut self, level: Bump);
+
+    /// Checks if the version number is a stable release.
+    fn is_stable(&self) -> bool;
+}
+
+/// Increments a string of decimal digits by one (`199` becomes `200`).
+///
+/// The digits are incremented as a string, so there is no largest number.
+#[cfg(feature = "std")]
+fn increment_digits(digits: &str) -> String {
+    let mut incremented: Vec<u8> = digits.bytes().collect();
+    let mut carry = true;
+
+    for digit in incremented.iter_mut().rev() {
+        if *digit == b'9' {
+            *digit = b'0';
+        } else {
+            *digit += 1;
+            carry = false;
+
+            break;
+        }
+    }
+
+    if carry {
+        incremented.insert(0, b'1');
+    }
+
+    String::from_utf8(incremented).expect("The digits are ASCII.")
+}
+
+/// Increments a dotted numeric release string (`1.2.3`) by a level.
+///
+/// Only the leading numeric components are kept. The release is padded with
+/// zeros (`0`) until it has the component being incremented, and every
+/// component after it is set to zero. For example, `2.30~rc1` becomes `2.30.1`
+/// for a patch increment.
+///
+/// The components are kept as digits, like dpkg and RPM compare them, so a
+/// component of any length is incremented without overflowing.
+#[cfg(feature = "std")]
+pub(crate) fn bump_dotted(release: &str, level: Bump) -> String {
+    let mut components: Vec<String> = Vec::new();
+
+    for component in release.split('.') {
+        let digits: &str = &component[..component.bytes().take_while(u8::is_ascii_digit).count()];
+
+        if digits.is_empty() {
+            break;
+        }
+
+        match digits.trim_start_matches('0') {
+            "" => components.push("0".to_string()),
+            trimmed => components.push(trimmed.to_string()),
+        }
+
+        if digits.len() < component.len() {
+            break;
+        }
+    }
+
+    let index = match level {
+        Bump::Major => 0,
+        Bump::Minor => 1,
+        Bump::Patch => 2,
+    };
+
+    while components.len() <= index {
+        components.push("0".to_string());
+    }
+
+    components[index] = increment_digits(&components[index]);
+
+    for component in components.iter_mut().skip(index + 1) {
+        *component = "0".to_string();
+    }
+
+    components.join(".")
+}
+
+/// Semantic version numbers are the default scheme.
+impl VersionScheme for Version {
+    fn bump(&mut self, level: Bump) {
+        match level {
+            Bump::Major => self.increment_major(),
+            Bump::Minor => self.increment_minor(),
+            Bump::Patch => self.increment_patch(),
+        }
+    }
+
+    fn is_stable(&self) -> bool {
+        Version::is_stable(self)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use std::cmp::Ordering;
+    use std::fmt;
+    use std::str::FromStr;
+    use super::{bump_dotted, Bump, VersionScheme};
+    use super::super::resolve::{resolve, Constraint};
+    use super::super::resolve::Operation::*;
+
+    // A minimal scheme where version numbers are a single integer.
+    #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+    struct Serial(u64);
+
+    impl fmt::Display for Serial {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "r{}", self.0)
+        }
+    }
+
+    impl FromStr for Serial {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Serial, String> {
+            s.trim_start_matches('r').parse().map(Serial).map_err(|_| s.to_string())
+        }
+    }
+
+    impl VersionScheme for Serial {
+        fn bump(&mut self, _: Bump) {
+            self.0 += 1;
+        }
+
+        fn is_stable(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_bump_apply() {
+        let mut v = version!(1, 2, 3, vec![id!("rc"), id!(1)]);
+
+        Bump::Patch.apply(&mut v);
+
+        assert_eq!(version!(1, 2, 4), v);
+
+        Bump::Minor.apply(&mut v);
+
+        assert_eq!(version!(1, 3, 0), v);
+
+        Bump::Major.apply(&mut v);
+
+        assert_eq!(version!(2, 0, 0), v);
+    }
+
+    #[test]
+    fn test_bump_dotted() {
+        assert_eq!("1.2.4", bump_dotted("1.2.3~rc1", Bump::Patch));
+        assert_eq!("2.31", bump_dotted("2.30", Bump::Minor));
+        assert_eq!("2.30.1", bump_dotted("2.30", Bump::Patch));
+        assert_eq!("3.0.0.0", bump_dotted("2.5.1.4", Bump::Major));
+        assert_eq!("1", bump_dotted("beta", Bump::Major));
+        assert_eq!("1.3", bump_dotted("01.02", Bump::Minor));
+        assert_eq!("18446744073709551616",
+                   bump_dotted("18446744073709551615", Bump::Major));
+        assert_eq!("100000000000000000000000.0",
+                   bump_dotted("99999999999999999999999.1", Bump::Major));
+        assert_eq!("1.99999999999999999999999.1",
+                   bump_dotted("1.99999999999999999999999.0", Bump::Patch));
+    }
+
+    #[test]
+    fn test_bump_order() {
+        assert_eq!(Ordering::Less, Bump::Patch.cmp(&Bump::Minor));
+        assert_eq!(Ordering::Less, Bump::Minor.cmp(&Bump::Major));
+    }
+
+    #[test]
+    fn test_version_is_stable() {
+        assert!(VersionScheme::is_stable(&version!(1, 0, 0)));
+        assert!(!VersionScheme::is_stable(&version!(1, 0, 0, vec![id!("rc")])));
+    }
+
+    #[test]
+    fn test_other_scheme() {
+        let mut v: Serial = "r41".parse().unwrap();
+
+        v.bump(Bump::Major);
+
+        assert_eq!("r42", v.to_string());
+
+        let c = constraints!(And, GreaterThan(Serial(10)), ExactlyNot(Serial(12)));
+
+        assert!(c.allows(&Serial(11)));
+        assert!(!c.allows(&Serial(12)));
+        assert!(!c.allows(&Serial(10)));
+
+        assert_eq!(resolve(&vec![Serial(9), Serial(11), Serial(12), Serial(13)], &c),
+                   vec![Serial(11), Serial(13)]);
+    }
+
+}
@@ -30,7 +30,12 @@
 //! # }
 //! ```
 use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
 
 /// Represents an alphanumeric or numberic identifier.
 ///
@@ -70,25 +75,113 @@ impl Display for Identifier {
     }
 }
 
-impl<'a> From<&'a str> for Identifier {
-    fn from(s: &'a str) -> Identifier {
-        match s.parse::<u64>() {
-            Ok(n) => Identifier::Number(n),
-            Err(_) => Identifier::Alpha(s.to_string()),
+impl FromStr for Identifier {
+    type Err = String;
+
+    /// Parses a string as an identifier, checking that it is valid.
+    ///
+    /// The identifier must not be empty, may only contain letters (a-z, A-Z),
+    /// numbers (0-9), and hyphens, and may not have leading zeros if it is
+    /// numeric.
+    fn from_str(s: &str) -> Result<Identifier, String> {
+        check(s, false).map_err(|invalid| invalid.message(s))?;
+
+        Ok(unchecked(s))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Identifier {
+    type Error = String;
+
+    /// Converts a string into an identifier, checking it as `parse` does.
+    fn try_from(s: &'a str) -> Result<Identifier, String> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for Identifier {
+    type Error = String;
+
+    /// Converts a string into an identifier, checking it as `parse` does.
+    fn try_from(s: String) -> Result<Identifier, String> {
+        match s.parse()? {
+            Identifier::Alpha(_) => Ok(Identifier::Alpha(s)),
+            number => Ok(number),
         }
     }
 }
 
-impl From<String> for Identifier {
-    fn from(s: String) -> Identifier {
-        match s.parse::<u64>() {
-            Ok(n) => Identifier::Number(n),
-            Err(_) => Identifier::Alpha(s),
+/// Converts a string that is already known to be valid into an identifier.
+///
+/// The identifier is numeric if the string can be parsed as a number.
+pub(crate) fn unchecked(s: &str) -> Identifier {
+    match s.parse::<u64>() {
+        Ok(n) => Identifier::Number(n),
+        Err(_) => Identifier::Alpha(s.to_string()),
+    }
+}
+
+/// Checks if a string only has numbers (0-9).
+fn is_numeric(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Represents why a string is not a valid identifier.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Invalid {
+    /// The identifier is empty.
+    Empty,
+
+    /// The identifier has a character that is not allowed.
+    Character(char),
+
+    /// A numeric pre-release identifier has leading zeros.
+    LeadingZeros,
+
+    /// A numeric pre-release identifier does not fit in a `u64`.
+    TooLarge,
+}
+
+impl Invalid {
+    /// Describes why an identifier is not valid.
+    pub(crate) fn message(self, s: &str) -> String {
+        match self {
+            Invalid::Empty => "Identifier cannot be empty".to_string(),
+            Invalid::Character(_) => format!("Invalid identifier: {}", s),
+            Invalid::LeadingZeros => format!("Numeric identifier has leading zeros: {}", s),
+            Invalid::TooLarge => format!("Numeric identifier is too large: {}", s),
+        }
+    }
+}
+
+/// Checks that a string is a valid identifier.
+///
+/// Numeric pre-release identifiers must not have leading zeros, and must fit
+/// in a `u64`, since they would otherwise be compared as text. Build
+/// identifiers may have either, since they are not compared.
+pub(crate) fn check(s: &str, build: bool) -> Result<(), Invalid> {
+    if s.is_empty() {
+        return Err(Invalid::Empty);
+    }
+
+    if let Some(c) = s.chars().find(|&c| !c.is_ascii_alphanumeric() && c != '-') {
+        return Err(Invalid::Character(c));
+    }
+
+    if !build && is_numeric(s) {
+        if s.len() > 1 && s.starts_with('0') {
+            return Err(Invalid::LeadingZeros);
+        }
+
+        if s.parse::<u64>().is_err() {
+            return Err(Invalid::TooLarge);
         }
     }
+
+    Ok(())
 }
 
-// Add From trait for all number types.
+// Add From trait for the unsigned number types, which always fit.
 macro_rules! from_n {
     ($x:ty) => {
         impl From<$x> for Identifier {
@@ -99,17 +192,53 @@ macro_rules! from_n {
     }
 }
 
-from_n!(f32);
-from_n!(f64);
-from_n!(i8);
-from_n!(i16);
-from_n!(i32);
-from_n!(i64);
 from_n!(u8);
 from_n!(u16);
 from_n!(u32);
 from_n!(u64);
 
+// Add TryFrom trait for the other signed number types, rejecting negatives.
+macro_rules! try_from_n {
+    ($x:ty) => {
+        impl TryFrom<$x> for Identifier {
+            type Error = String;
+
+            fn try_from(n: $x) -> Result<Identifier, String> {
+                u64::try_from(n)
+                    .map(Identifier::Number)
+                    .map_err(|_| format!("Numeric identifier cannot be negative: {}", n))
+            }
+        }
+    }
+}
+
+try_from_n!(i8);
+try_from_n!(i16);
+try_from_n!(i32);
+try_from_n!(i64);
+
+// Add TryFrom trait for the floating point types, rejecting anything that is
+// not a whole number that fits in a `u64`.
+macro_rules! try_from_f {
+    ($x:ty) => {
+        impl TryFrom<$x> for Identifier {
+            type Error = String;
+
+            fn try_from(n: $x) -> Result<Identifier, String> {
+                // 2^64 is the smallest number that does not fit.
+                if (0.0..18446744073709551616.0).contains(&n) && n == (n as u64) as $x {
+                    Ok(Identifier::Number(n as u64))
+                } else {
+                    Err(format!("Invalid numeric identifier: {}", n))
+                }
+            }
+        }
+    }
+}
+
+try_from_f!(f32);
+try_from_f!(f64);
+
 /// Creates a new identifier.
 ///
 /// ```
@@ -129,11 +258,81 @@ from_n!(u64);
 /// let a = Identifier::Alpha("abc".to_string());
 /// let b = Identifier::Number(123);
 /// ```
+///
+/// Number literals are `u64`s, so a negative number does not compile. Other
+/// expressions may also be unsigned numbers, `String`s, or `Identifier`s. Like
+/// the variants, strings are not checked, and are numeric if they can be
+/// parsed as a number. `Identifier::try_from` checks them.
 #[macro_export]
 macro_rules! id {
-    ($a:expr) => {{ $crate::version::Identifier::from($a) }}
+    ($a:literal) => {{ $crate::__private::Literal::into_identifier($a) }};
+    ($a:expr) => {{ $crate::__private::IntoIdentifier::into_identifier($a) }}
+}
+
+/// Converts the string and number literals that are given to `id!`.
+///
+/// Since `u64` is the only number type, integer literals are inferred to be
+/// one.
+#[doc(hidden)]
+pub trait Literal {
+    fn into_identifier(self) -> Identifier;
+}
+
+impl Literal for &str {
+    fn into_identifier(self) -> Identifier {
+        unchecked(self)
+    }
+}
+
+impl Literal for u64 {
+    fn into_identifier(self) -> Identifier {
+        Identifier::Number(self)
+    }
+}
+
+/// Converts the other expressions that are given to `id!`.
+#[doc(hidden)]
+pub trait IntoIdentifier {
+    fn into_identifier(self) -> Identifier;
+}
+
+impl IntoIdentifier for &str {
+    fn into_identifier(self) -> Identifier {
+        unchecked(self)
+    }
+}
+
+impl IntoIdentifier for String {
+    fn into_identifier(self) -> Identifier {
+        match self.parse::<u64>() {
+            Ok(n) => Identifier::Number(n),
+            Err(_) => Identifier::Alpha(self),
+        }
+    }
+}
+
+impl IntoIdentifier for Identifier {
+    fn into_identifier(self) -> Identifier {
+        self
+    }
+}
+
+// Add IntoIdentifier trait for the unsigned number types, which always fit.
+macro_rules! into_identifier_n {
+    ($x:ty) => {
+        impl IntoIdentifier for $x {
+            fn into_identifier(self) -> Identifier {
+                Identifier::from(self)
+            }
+        }
+    }
 }
 
+into_identifier_n!(u8);
+into_identifier_n!(u16);
+into_identifier_n!(u32);
+into_identifier_n!(u64);
+
 /// Represents a semantic version number.
 ///
 /// A semantic version number is a version number that conforms to a set of
@@ -209,30 +408,21 @@ impl Default for Version {
     }
 }
 
+/// Joins a list of identifiers with dots (`.`).
+pub(crate) fn join(identifiers: &[Identifier]) -> String {
+    identifiers.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(".")
+}
+
 impl Display for Version {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         try!(write!(f, "{}.{}.{}", self.major, self.minor, self.patch));
 
-        macro_rules! join {
-            ($a:expr) => {
-                {
-                    let mut joined: Vec<String> = Vec::new();
-
-                    for identifier in &$a {
-                        joined.push(format!("{}", identifier));
-                    }
-
-                    joined.join(".")
-                }
-            }
-        }
-
         if !self.pre.is_empty() {
-            try!(write!(f, "-{}", join!(self.pre)));
+            try!(write!(f, "-{}", join(&self.pre)));
         }
 
         if !self.build.is_empty() {
-            try!(write!(f, "+{}", join!(self.build)));
+            try!(write!(f, "+{}", join(&self.build)));
         }
 
         Ok(())
@@ -280,7 +470,64 @@ impl PartialOrd for Version {
     }
 }
 
+/// Creates a release without any identifiers.
+fn release(major: u64, minor: u64, patch: u64) -> Version {
+    Version {
+        major,
+        minor,
+        patch,
+        ..Default::default()
+    }
+}
+
+/// Returns the release numbers that come right after the given ones.
+pub(crate) fn next_numbers(major: u64, minor: u64, patch: u64) -> Option<(u64, u64, u64)> {
+    if let Some(patch) = patch.checked_add(1) {
+        Some((major, minor, patch))
+    } else if let Some(minor) = minor.checked_add(1) {
+        Some((major, minor, 0))
+    } else {
+        major.checked_add(1).map(|major| (major, 0, 0))
+    }
+}
+
+/// Creates a version number that comes before any pre-release of the next
+/// release, or `None` if there is no next release.
+pub(crate) fn next_floor(major: u64, minor: u64, patch: u64) -> Option<Version> {
+    next_numbers(major, minor, patch).map(|(major, minor, patch)| {
+        Version { pre: vec![id!(0)], ..release(major, minor, patch) }
+    })
+}
+
 impl Version {
+    /// Returns the bounds of the versions allowed by a caret (`^`) requirement.
+    ///
+    /// The lower bound is inclusive, and is the version number without its
+    /// build identifiers. The upper bound is exclusive, and is the first
+    /// pre-release of the next incompatible version, so that its
+    /// pre-releases are excluded. There is no upper bound if the next
+    /// incompatible version cannot be represented.
+    ///
+    /// ```
+    /// # #[macro_use]
+    /// # extern crate recital;
+    /// # fn main() {
+    /// let (lower, upper) = version!(0, 2, 3).caret_bounds();
+    ///
+    /// assert_eq!("0.2.3", lower.to_string());
+    /// assert_eq!("0.3.0-0", upper.unwrap().to_string());
+    /// # }
+    /// ```
+    pub fn caret_bounds(&self) -> (Version, Option<Version>) {
+        let upper = match (self.major, self.minor) {
+            (0, 0) => next_floor(0, 0, self.patch),
+            (0, minor) => next_floor(0, minor, u64::MAX),
+            (major, _) => next_floor(major, u64::MAX, u64::MAX),
+        };
+
+        (self.without_build(), upper)
+    }
+
     /// Removes all of the build identifiers.
     pub fn clear_build(&mut self) {
         self.build = Vec::new();
@@ -348,6 +595,133 @@ impl Version {
     pub fn new() -> Version {
         Default::default()
     }
+
+    /// Returns the smallest release that is greater than this version number.
+    ///
+    /// The release of a pre-release is the next release, so `1.2.3-rc.1` is
+    /// followed by `1.2.3`, while `1.2.3` is followed by `1.2.4`. There is no
+    /// next release if the numbers cannot be incremented any further.
+    pub fn next_release(&self) -> Option<Version> {
+        if !self.pre.is_empty() {
+            return Some(release(self.major, self.minor, self.patch));
+        }
+
+        next_numbers(self.major, self.minor, self.patch)
+            .map(|(major, minor, patch)| release(major, minor, patch))
+    }
+
+    /// Returns the greatest release that is less than this version number.
+    ///
+    /// The release before `1.2.3`, or any of its pre-releases, is `1.2.2`, and
+    /// the one before `1.2.0` is `1.1.18446744073709551615`. There is no
+    /// release before `0.0.0`. Since any number of pre-releases may come
+    /// before a version number, there is no greatest pre-release before it.
+    pub fn previous_release(&self) -> Option<Version> {
+        match (self.major, self.minor, self.patch) {
+            (major, minor, patch) if patch > 0 => Some(release(major, minor, patch - 1)),
+            (major, minor, _) if minor > 0 => Some(release(major, minor - 1, u64::MAX)),
+            (major, _, _) if major > 0 => Some(release(major - 1, u64::MAX, u64::MAX)),
+            _ => None,
+        }
+    }
+
+    /// Returns the smallest version number that is greater than this one.
+    ///
+    /// A pre-release is followed by the same pre-release with a `0`
+    /// identifier appended (`1.2.3-rc.1.0`), and a release by the first
+    /// pre-release of the next patch (`1.2.4-0`). This turns an exclusive
+    /// bound into an inclusive one, as `> v` allows the same version numbers
+    /// as `>= v.successor()`. Build identifiers are not kept.
+    ///
+    /// There is no successor for the largest release that can be represented.
+    ///
+    /// ```
+    /// # #[macro_use]
+    /// # extern crate recital;
+    /// # fn main() {
+    /// let a = version!(1, 2, 3);
+    /// let b = version!(1, 2, 3, vec![id!("rc"), id!(1)]);
+    ///
+    /// assert_eq!("1.2.4-0", a.successor().unwrap().to_string());
+    /// assert_eq!("1.2.3-rc.1.0", b.successor().unwrap().to_string());
+    /// # }
+    /// ```
+    pub fn successor(&self) -> Option<Version> {
+        if self.pre.is_empty() {
+            return next_floor(self.major, self.minor, self.patch);
+        }
+
+        let mut successor = self.without_build();
+
+        successor.pre.push(id!(0));
+
+        Some(successor)
+    }
+
+    /// Returns the bounds of the versions allowed by a tilde (`~`) requirement.
+    ///
+    /// The bounds are the same as for `caret_bounds`, except that the upper
+    /// bound is always the next minor version.
+    ///
+    /// ```
+    /// # #[macro_use]
+    /// # extern crate recital;
+    /// # fn main() {
+    /// let (lower, upper) = version!(1, 2, 3).tilde_bounds();
+    ///
+    /// assert_eq!("1.2.3", lower.to_string());
+    /// assert_eq!("1.3.0-0", upper.unwrap().to_string());
+    /// # }
+    /// ```
+    pub fn tilde_bounds(&self) -> (Version, Option<Version>) {
+        (self.without_build(), next_floor(self.major, self.minor, u64::MAX))
+    }
+
+    /// Checks that every identifier is valid.
+    ///
+    /// Alphanumeric identifiers must not be empty, and may only contain
+    /// letters (a-z, A-Z), numbers (0-9), and hyphens. Alphanumeric
+    /// pre-release identifiers must also have a letter or hyphen, since they
+    /// would otherwise be compared as text instead of as numbers.
+    ///
+    /// ```
+    /// # #[macro_use]
+    /// # extern crate recital;
+    /// use recital::version::Identifier;
+    ///
+    /// # fn main() {
+    /// assert!(version!(1, 2, 3, vec![id!("rc"), id!(1)]).validate().is_ok());
+    /// assert!(version!(1, 2, 3, vec![id!("rc 1")]).validate().is_err());
+    /// assert!(version!(1, 2, 3, vec![Identifier::Alpha("01".to_string())]).validate().is_err());
+    /// # }
+    /// ```
+    pub fn validate(&self) -> Result<(), String> {
+        for identifier in &self.pre {
+            if let Identifier::Alpha(ref a) = *identifier {
+                check(a, false).map_err(|invalid| invalid.message(a))?;
+
+                if is_numeric(a) {
+                    return Err(format!("Invalid pre-release identifier: {}", a));
+                }
+            }
+        }
+
+        for identifier in &self.build {
+            if let Identifier::Alpha(ref a) = *identifier {
+                check(a, true).map_err(|invalid| invalid.message(a))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates a copy of the version number without build identifiers.
+    fn without_build(&self) -> Version {
+        Version {
+            build: Vec::new(),
+            ..self.clone()
+        }
+    }
 }
 
 /// Creates a new version number.
@@ -436,11 +810,16 @@ macro_rules! version {
     };
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
 
-    use super::Identifier::{Alpha, Number};
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use std::cmp::Ordering;
+    use std::convert::TryFrom;
+    use super::Identifier::{self, Alpha, Number};
     use super::Version;
+    use super::super::arbitrary::identifiers;
 
     // Verify that an identifier can be displayed.
     #[test]
@@ -449,6 +828,94 @@ mod tests {
         assert_eq!("123", format!("{}", Number(123)));
     }
 
+    // Verify that an identifier can be parsed and checked.
+    #[test]
+    fn test_identifier_from_str() {
+        assert_eq!(Ok(Alpha("rc-1".to_string())), "rc-1".parse());
+        assert_eq!(Ok(Alpha("0a".to_string())), "0a".parse());
+        assert_eq!(Ok(Number(0)), "0".parse());
+        assert_eq!(Ok(Number(123)), "123".parse());
+
+        for invalid in &["", "rc 1", "rc.1", "é", "01", "18446744073709551616"] {
+            assert!(invalid.parse::<Identifier>().is_err(), "{:?}", invalid);
+        }
+    }
+
+    // Verify that the macro takes literals and other expressions.
+    #[test]
+    fn test_identifier_macro() {
+        let (small, large): (u8, u32) = (7, 4294967295);
+        let (label, owned) = ("rc", "123".to_string());
+
+        assert_eq!(Number(7), id!(7));
+        assert_eq!(Alpha("rc".to_string()), id!("rc"));
+        assert_eq!(Number(7), id!(small));
+        assert_eq!(Number(4294967295), id!(large));
+        assert_eq!(Number(8), id!(u64::from(small) + 1));
+        assert_eq!(Alpha("rc".to_string()), id!(label));
+        assert_eq!(Number(123), id!(owned));
+        assert_eq!(Number(7), id!(id!(7)));
+    }
+
+    // Verify that numbers are converted without wrapping or truncating.
+    #[test]
+    fn test_identifier_try_from() {
+        assert_eq!(Ok(Number(7)), Identifier::try_from(7i64));
+        assert_eq!(Ok(Number(7)), Identifier::try_from(7.0f64));
+        assert_eq!(Ok(Number(16777216)), Identifier::try_from(16777216f32));
+        assert_eq!(Ok(Number(7)), Identifier::try_from(7i32));
+        assert_eq!(Number(7), Identifier::from(7u8));
+        assert_eq!(Number(7), id!(7));
+
+        assert!(Identifier::try_from(-1i8).is_err());
+        assert!(Identifier::try_from(-1i32).is_err());
+        assert!(Identifier::try_from(-1i64).is_err());
+        assert!(Identifier::try_from(-1.0f64).is_err());
+        assert!(Identifier::try_from(1.5f64).is_err());
+        assert!(Identifier::try_from(f64::NAN).is_err());
+        assert!(Identifier::try_from(f32::INFINITY).is_err());
+        assert!(Identifier::try_from(18446744073709551616.0f64).is_err());
+    }
+
+    // Verify that strings are checked when converted.
+    #[test]
+    fn test_identifier_try_from_str() {
+        assert_eq!(Ok(Alpha("rc".to_string())), Identifier::try_from("rc"));
+        assert_eq!(Ok(Number(1)), Identifier::try_from("1".to_string()));
+        assert_eq!(Ok(Alpha("x-1".to_string())), Identifier::try_from("x-1".to_string()));
+
+        for invalid in &["", "a b", "01"] {
+            assert!(Identifier::try_from(*invalid).is_err(), "{:?}", invalid);
+            assert!(Identifier::try_from(invalid.to_string()).is_err(), "{:?}", invalid);
+        }
+    }
+
+    // Verify that the identifiers of a version can be checked.
+    #[test]
+    fn test_version_validate() {
+        let alpha = |a: &str| Alpha(a.to_string());
+
+        assert_eq!(Ok(()), version!(1, 2, 3).validate());
+        assert_eq!(Ok(()),
+                   version!(1, 2, 3, vec![alpha("rc"), Number(1)], vec![alpha("007")]).validate());
+
+        for pre in &[alpha(""), alpha("r c"), alpha("rc.1"), alpha("1"), alpha("01")] {
+            assert!(version!(1, 2, 3, vec![pre.clone()]).validate().is_err(), "{:?}", pre);
+        }
+
+        for build in &[alpha(""), alpha("sha+1"), alpha("a.b")] {
+            assert!(version!(1, 2, 3, vec![], vec![build.clone()]).validate().is_err(),
+                    "{:?}",
+                    build);
+        }
+
+        for s in &["1.2.3-99999999999999999999999", "1.2.3-01", "1.2.3-rc.01", "01.2.3"] {
+            assert!(s.parse::<Version>().is_err(), "{} should be invalid", s);
+        }
+
+        assert_eq!(Ok(()), "1.2.3-rc.1+build.007".parse::<Version>().unwrap().validate());
+    }
+
     // Verify that default version values are minimal.
     #[test]
     fn test_version_default() {
@@ -643,4 +1110,150 @@ mod tests {
         assert!(!v.is_stable());
     }
 
+    // Verify that the smallest greater version can be found.
+    #[test]
+    fn test_version_successor() {
+        let max = u64::MAX;
+
+        assert_eq!(Some(version!(1, 2, 4, vec![id!(0)])), version!(1, 2, 3).successor());
+        assert_eq!(Some(version!(1, 3, 0, vec![id!(0)])), version!(1, 2, max).successor());
+        assert_eq!(Some(version!(2, 0, 0, vec![id!(0)])), version!(1, max, max).successor());
+        assert_eq!(None, version!(max, max, max).successor());
+
+        let v = version!(1, 2, 3, vec![id!("rc"), id!(1)], vec![id!("build")]);
+        let successor = v.successor().unwrap();
+
+        assert_eq!(vec![id!("rc"), id!(1), id!(0)], successor.pre);
+        assert!(successor.build.is_empty());
+    }
+
+    // Verify that the neighboring releases can be found.
+    #[test]
+    fn test_version_releases() {
+        let max = u64::MAX;
+
+        assert_eq!(Some(version!(1, 2, 4)), version!(1, 2, 3).next_release());
+        assert_eq!(Some(version!(1, 2, 3)),
+                   version!(1, 2, 3, vec![id!("rc")]).next_release());
+        assert_eq!(None, version!(max, max, max).next_release());
+
+        assert_eq!(Some(version!(1, 2, 2)), version!(1, 2, 3).previous_release());
+        assert_eq!(Some(version!(1, 2, 2)),
+                   version!(1, 2, 3, vec![id!("rc")]).previous_release());
+        assert_eq!(Some(version!(1, 1, max)), version!(1, 2, 0).previous_release());
+        assert_eq!(Some(version!(0, max, max)), version!(1, 0, 0).previous_release());
+        assert_eq!(None, version!(0, 0, 0, vec![id!("rc")]).previous_release());
+    }
+
+    // Verify that the bounds of caret and tilde requirements can be found.
+    #[test]
+    fn test_version_bounds() {
+        let bounds = |(lower, upper): (Version, Option<Version>)| {
+            (lower.to_string(), upper.map(|v| v.to_string()))
+        };
+
+        let some = |lower: &str, upper: &str| (lower.to_string(), Some(upper.to_string()));
+
+        assert_eq!(some("1.2.3", "2.0.0-0"), bounds(version!(1, 2, 3).caret_bounds()));
+        assert_eq!(some("0.2.3", "0.3.0-0"), bounds(version!(0, 2, 3).caret_bounds()));
+        assert_eq!(some("0.0.3", "0.0.4-0"), bounds(version!(0, 0, 3).caret_bounds()));
+        assert_eq!(some("1.2.3-rc", "1.3.0-0"),
+                   bounds(version!(1, 2, 3, vec![id!("rc")], vec![id!(7)]).tilde_bounds()));
+        assert_eq!(some("0.0.3", "0.1.0-0"), bounds(version!(0, 0, 3).tilde_bounds()));
+        assert_eq!(None, version!(u64::MAX, 0, 0).caret_bounds().1);
+    }
+
+    proptest! {
+        // Verify that a version survives being displayed and parsed.
+        #[test]
+        fn test_version_display_parse(v: Version) {
+            let parsed: Version = v.to_string().parse().unwrap();
+
+            prop_assert_eq!(&parsed, &v);
+            prop_assert_eq!(&parsed.build, &v.build);
+            prop_assert_eq!(parsed.to_string(), v.to_string());
+        }
+
+        // Verify that generated versions are valid.
+        #[test]
+        fn test_version_validate_arbitrary(v: Version) {
+            prop_assert_eq!(v.validate(), Ok(()));
+        }
+
+        // Verify that versions are totally ordered, consistently with equality.
+        #[test]
+        fn test_version_cmp_total(a: Version, b: Version) {
+            prop_assert_eq!(a == b, a.cmp(&b) == Ordering::Equal);
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            prop_assert_eq!(a.partial_cmp(&b), Some(a.cmp(&b)));
+            prop_assert_eq!(a.cmp(&a), Ordering::Equal);
+        }
+
+        // Verify that the ordering of versions is transitive.
+        #[test]
+        fn test_version_cmp_transitive(mut versions in vec(any::<Version>(), 0..12)) {
+            versions.sort();
+
+            for (i, a) in versions.iter().enumerate() {
+                for b in &versions[i..] {
+                    prop_assert!(a <= b, "{} <= {}", a, b);
+                }
+            }
+        }
+
+        // Verify that build identifiers do not affect the ordering.
+        #[test]
+        fn test_version_cmp_build(v: Version, build in identifiers()) {
+            let mut w = v.clone();
+
+            w.build = build;
+
+            prop_assert_eq!(v.cmp(&w), Ordering::Equal);
+            prop_assert_eq!(&v, &w);
+        }
+
+        // Verify that each increment produces a greater version.
+        #[test]
+        fn test_version_increment(v: Version) {
+            prop_assume!(v.major < u64::MAX && v.minor < u64::MAX && v.patch < u64::MAX);
+
+            let (mut major, mut minor, mut patch) = (v.clone(), v.clone(), v.clone());
+
+            major.increment_major();
+            minor.increment_minor();
+            patch.increment_patch();
+
+            prop_assert!(major > v, "{} > {}", major, v);
+            prop_assert!(minor > v, "{} > {}", minor, v);
+            prop_assert!(patch > v, "{} > {}", patch, v);
+            prop_assert!(major > minor && minor > patch);
+        }
+
+        // Verify that nothing comes between a version and its successor.
+        #[test]
+        fn test_version_successor_next(v: Version, w: Version) {
+            if let Some(successor) = v.successor() {
+                prop_assert!(successor > v);
+                prop_assert!(!(v < w && w < successor), "{} < {} < {}", v, w, successor);
+            }
+        }
+
+        // Verify that no release comes between a version and the releases
+        // next to it.
+        #[test]
+        fn test_version_releases_next(v: Version, w: Version) {
+            let w = Version { pre: Vec::new(), ..w };
+
+            if let Some(next) = v.next_release() {
+                prop_assert!(next > v && next.pre.is_empty());
+                prop_assert!(!(v < w && w < next), "{} < {} < {}", v, w, next);
+            }
+
+            if let Some(previous) = v.previous_release() {
+                prop_assert!(previous < v && previous.pre.is_empty());
+                prop_assert!(!(previous < w && w < v), "{} < {} < {}", previous, w, v);
+            }
+        }
+    }
+
 }
@@ -0,0 +1,210 @@
+//! Exposes version numbers and constraints to JavaScript.
+//!
+//! The **wasm** module provides you with WebAssembly bindings, generated with
+//! [wasm-bindgen][], for browsers and Node. Since JavaScript has no operator
+//! overloading, `Version.compare` can be passed to `Array.prototype.sort`.
+//!
+//! ```js
+//! import { Requirement, Version } from "recital";
+//!
+//! const versions = ["1.0.0", "1.0.0-rc.1", "1.0.0-beta.11", "1.0.0-beta.2"]
+//!     .map(Version.parse)
+//!     .sort(Version.compare);
+//!
+//! versions.map(String); // ["1.0.0-beta.2", "1.0.0-beta.11", "1.0.0-rc.1", "1.0.0"]
+//!
+//! Requirement.parse("^1.2 || 2.x", "npm").allows(Version.parse("2.1.0")); // true
+//! ```
+//!
+//! Invalid version numbers and requirements throw an `Error`. The bindings and
+//! their TypeScript types are generated with:
+//!
+//! ```sh
+//! cargo rustc --release --lib --features wasm --crate-type cdylib --target wasm32-unknown-unknown
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/recital.wasm
+//! ```
+//!
+//! The version of `wasm-bindgen` has to be the same as that of the crate.
+//!
+//! [wasm-bindgen]: https://rustwasm.github.io/docs/wasm-bindgen/
+use std::cmp::Ordering;
+use wasm_bindgen::prelude::*;
+use super::cargo;
+use super::composer;
+use super::npm;
+use super::resolve::Constraint;
+use super::scheme::{Bump, VersionScheme};
+use super::version::{join, Version};
+
+#[wasm_bindgen(typescript_custom_section)]
+const SYNTAX: &'static str = r#"
+/** The syntax of a version requirement. */
+export type Syntax = "cargo" | "composer" | "npm";
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Syntax")]
+    pub type Syntax;
+}
+
+/// Represents a semantic version number.
+///
+/// Version numbers are immutable, so the bump methods return a new one.
+#[wasm_bindgen(js_name = Version)]
+pub struct JsVersion {
+    version: Version,
+}
+
+impl JsVersion {
+    /// Creates a copy of the version number that has been bumped.
+    fn bumped(&self, level: Bump) -> JsVersion {
+        let mut version = self.version.clone();
+
+        version.bump(level);
+
+        JsVersion { version }
+    }
+}
+
+#[wasm_bindgen(js_class = Version)]
+impl JsVersion {
+    /// Creates a version number without any identifiers.
+    #[wasm_bindgen(constructor)]
+    pub fn new(major: u32, minor: u32, patch: u32) -> JsVersion {
+        JsVersion { version: version!(major as u64, minor as u64, patch as u64) }
+    }
+
+    /// Parses a string as a version number, throwing if it is not valid.
+    pub fn parse(s: &str) -> Result<JsVersion, JsError> {
+        s.parse()
+            .map(|version| JsVersion { version })
+            .map_err(|e: String| JsError::new(&e))
+    }
+
+    /// Compares two version numbers, for sorting them with `Array.sort`.
+    pub fn compare(a: &JsVersion, b: &JsVersion) -> i32 {
+        a.compare_to(b)
+    }
+
+    /// Compares the version number with another, returning `-1`, `0`, or
+    /// `1`, where build identifiers are ignored.
+    #[wasm_bindgen(js_name = compareTo)]
+    pub fn compare_to(&self, other: &JsVersion) -> i32 {
+        match self.version.cmp(&other.version) {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        }
+    }
+
+    /// Checks if the version number has the same precedence as another.
+    pub fn equals(&self, other: &JsVersion) -> bool {
+        self.version == other.version
+    }
+
+    /// The major version number, which is only exact up to
+    /// `Number.MAX_SAFE_INTEGER`.
+    #[wasm_bindgen(getter)]
+    pub fn major(&self) -> f64 {
+        self.version.major as f64
+    }
+
+    /// The minor version number, which is only exact up to
+    /// `Number.MAX_SAFE_INTEGER`.
+    #[wasm_bindgen(getter)]
+    pub fn minor(&self) -> f64 {
+        self.version.minor as f64
+    }
+
+    /// The patch version number, which is only exact up to
+    /// `Number.MAX_SAFE_INTEGER`.
+    #[wasm_bindgen(getter)]
+    pub fn patch(&self) -> f64 {
+        self.version.patch as f64
+    }
+
+    /// The pre-release identifiers, joined with dots (`.`).
+    #[wasm_bindgen(getter)]
+    pub fn pre(&self) -> String {
+        join(&self.version.pre)
+    }
+
+    /// The build identifiers, joined with dots (`.`).
+    #[wasm_bindgen(getter)]
+    pub fn build(&self) -> String {
+        join(&self.version.build)
+    }
+
+    /// Whether the major version number is not zero (`0`), and there are no
+    /// pre-release identifiers.
+    #[wasm_bindgen(getter, js_name = isStable)]
+    pub fn is_stable(&self) -> bool {
+        self.version.is_stable()
+    }
+
+    #[wasm_bindgen(js_name = bumpMajor)]
+    pub fn bump_major(&self) -> JsVersion {
+        self.bumped(Bump::Major)
+    }
+
+    #[wasm_bindgen(js_name = bumpMinor)]
+    pub fn bump_minor(&self) -> JsVersion {
+        self.bumped(Bump::Minor)
+    }
+
+    #[wasm_bindgen(js_name = bumpPatch)]
+    pub fn bump_patch(&self) -> JsVersion {
+        self.bumped(Bump::Patch)
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_string(&self) -> String {
+        self.version.to_string()
+    }
+}
+
+/// Represents a version requirement.
+#[wasm_bindgen(js_name = Requirement)]
+pub struct JsRequirement {
+    constraint: Box<dyn Constraint>,
+    source: String,
+}
+
+#[wasm_bindgen(js_class = Requirement)]
+impl JsRequirement {
+    /// Parses a version requirement, throwing if it is not valid.
+    ///
+    /// The syntax is that of `cargo` (`^1.2, <1.5`), which is the default,
+    /// `npm` (`^1.2 || 2.x`), or `composer` (`^1.2|^2.0`).
+    pub fn parse(s: &str, syntax: Option<Syntax>) -> Result<JsRequirement, JsError> {
+        let syntax = syntax.and_then(|s| s.as_string()).unwrap_or_else(|| "cargo".to_string());
+
+        let constraint: Result<Box<dyn Constraint>, String> = match &syntax[..] {
+            "cargo" => s.parse::<cargo::Requirement>().map(|r| Box::new(r) as _),
+            "composer" => s.parse::<composer::Requirement>().map(|r| Box::new(r) as _),
+            "npm" => s.parse::<npm::Range>().map(|r| Box::new(r) as _),
+            _ => return Err(JsError::new(&format!("Unknown syntax: {}", syntax))),
+        };
+
+        constraint.map(|constraint| {
+                JsRequirement {
+                    constraint,
+                    source: s.to_string(),
+                }
+            })
+            .map_err(|e| JsError::new(&e))
+    }
+
+    /// Checks if a version number satisfies the requirement.
+    pub fn allows(&self, version: &JsVersion) -> bool {
+        self.constraint.allows(&version.version)
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_string(&self) -> String {
+        self.source.clone()
+    }
+}
@@ -0,0 +1,430 @@
+//! Plans coordinated releases for a workspace of packages.
+//!
+//! The **workspace** module provides you with a model of several packages
+//! that depend on each other, each with its own version number and a set of
+//! constraints on the other packages in the workspace. Given the packages
+//! that have changed, and how much they have changed, a release plan can be
+//! created that also bumps every dependent whose constraints would no longer
+//! be satisfied.
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate recital;
+//! use recital::resolve::Operation::*;
+//! use recital::workspace::{Bump, Mode, Package, Workspace};
+//!
+//! # fn main() {
+//! let mut workspace = Workspace::new();
+//!
+//! workspace.add(Package::new("core", version!(1, 2, 3)));
+//! workspace.add(Package::new("cli", version!(0, 4, 0))
+//!                   .depends("core",
+//!                            constraints!(And,
+//!                                         GreaterThanOrEqualTo(version!(1, 0, 0)),
+//!                                         LessThan(version!(2, 0, 0)))));
+//!
+//! let plan = workspace.plan(&[("core", Bump::Major)], Mode::Independent).unwrap();
+//!
+//! assert_eq!("core", plan[0].name);
+//! assert_eq!(version!(2, 0, 0), plan[0].to);
+//! assert_eq!("cli", plan[1].name);
+//! assert_eq!(version!(0, 4, 1), plan[1].to);
+//! assert_eq!(vec!["core".to_string()], plan[1].requirements);
+//! # }
+//! ```
+use std::cmp;
+use std::collections::HashMap;
+use super::resolve::Constraint;
+use super::version::Version;
+
+pub use super::scheme::Bump;
+
+/// Represents how the packages in a workspace are versioned.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    /// Every package is versioned on its own.
+    ///
+    /// Only the packages that have changed, and the dependents that can no
+    /// longer use their new version numbers, are released.
+    Independent,
+
+    /// Every package shares the same version number.
+    ///
+    /// All packages are released together using the greatest version number
+    /// in the workspace, incremented by the greatest level of change.
+    Lockstep,
+}
+
+/// Represents a requirement on another package in the same workspace.
+pub struct Dependency {
+    /// The name of the package that is depended on.
+    pub name: String,
+
+    /// The constraint the version number of that package must satisfy.
+    pub constraint: Box<dyn Constraint>,
+}
+
+/// Represents a single package in a workspace.
+pub struct Package {
+    /// The name of the package.
+    pub name: String,
+
+    /// The current version number of the package.
+    pub version: Version,
+
+    /// The requirements on other packages in the workspace.
+    pub dependencies: Vec<Dependency>,
+}
+
+impl Package {
+    /// Adds a requirement on another package in the workspace.
+    pub fn depends<C>(mut self, name: &str, constraint: C) -> Package
+        where C: Constraint + 'static
+    {
+        self.dependencies.push(Dependency {
+            name: name.to_string(),
+            constraint: Box::new(constraint),
+        });
+
+        self
+    }
+
+    /// Creates a new package without any dependencies.
+    pub fn new(name: &str, version: Version) -> Package {
+        Package {
+            name: name.to_string(),
+            version,
+            dependencies: Vec::new(),
+        }
+    }
+}
+
+/// Represents the release of a single package in a plan.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Release {
+    /// The name of the package.
+    pub name: String,
+
+    /// The current version number of the package.
+    pub from: Version,
+
+    /// The version number the package will be released as.
+    pub to: Version,
+
+    /// The level of change that was applied.
+    pub bump: Bump,
+
+    /// The names of the dependencies whose constraints must be updated.
+    ///
+    /// These are the dependencies whose constraints do not allow the new
+    /// version numbers in the plan. The constraints will have to be changed
+    /// before the package is released.
+    pub requirements: Vec<String>,
+}
+
+/// Represents a collection of packages that are released together.
+#[derive(Default)]
+pub struct Workspace {
+    /// The packages in the workspace.
+    pub packages: Vec<Package>,
+}
+
+impl Workspace {
+    /// Adds a package to the workspace.
+    pub fn add(&mut self, package: Package) {
+        self.packages.push(package);
+    }
+
+    /// Creates a new empty workspace.
+    pub fn new() -> Workspace {
+        Default::default()
+    }
+
+    /// Creates a release plan for the given changes.
+    ///
+    /// Each change is the name of a package and the level of change that was
+    /// made to it. The changes are propagated to every dependent whose
+    /// constraints would no longer allow the new version number of one of
+    /// its dependencies, which then receives a patch release of its own. The
+    /// releases are returned in topological order, so every package comes
+    /// after the packages it depends on.
+    ///
+    /// An error is returned if a change names an unknown package, or if the
+    /// dependencies in the workspace contain a cycle.
+    pub fn plan(&self, changes: &[(&str, Bump)], mode: Mode) -> Result<Vec<Release>, String> {
+        let order = self.order()?;
+        let mut bumps: HashMap<&str, Bump> = HashMap::new();
+
+        for &(name, bump) in changes {
+            if self.find(name).is_none() {
+                return Err(format!("Unknown package: {}", name));
+            }
+
+            let entry = bumps.entry(name).or_insert(bump);
+
+            *entry = cmp::max(*entry, bump);
+        }
+
+        if mode == Mode::Lockstep {
+            return Ok(self.plan_lockstep(&order, &bumps));
+        }
+
+        let mut versions: HashMap<&str, Version> = HashMap::new();
+        let mut releases = Vec::new();
+
+        for &index in &order {
+            let package = &self.packages[index];
+            let mut requirements = Vec::new();
+
+            for dependency in &package.dependencies {
+                if let Some(version) = versions.get(dependency.name.as_str()) {
+                    if !dependency.constraint.allows(version) {
+                        requirements.push(dependency.name.clone());
+                    }
+                }
+            }
+
+            let bump = match bumps.get(package.name.as_str()) {
+                Some(bump) => *bump,
+                None if !requirements.is_empty() => Bump::Patch,
+                None => continue,
+            };
+
+            let mut to = package.version.clone();
+
+            bump.apply(&mut to);
+            versions.insert(&package.name, to.clone());
+
+            releases.push(Release {
+                name: package.name.clone(),
+                from: package.version.clone(),
+                to,
+                bump,
+                requirements,
+            });
+        }
+
+        Ok(releases)
+    }
+
+    /// Returns the requirements that are not satisfied after a plan.
+    ///
+    /// The version numbers in the plan replace the current version numbers
+    /// of the packages, and every constraint in the workspace is checked
+    /// against them. Constraints that the plan has already marked as needing
+    /// an update are skipped. The name of the dependent and the name of the
+    /// dependency are returned for every constraint that is not satisfied.
+    pub fn unsatisfied(&self, plan: &[Release]) -> Vec<(String, String)> {
+        let mut unsatisfied = Vec::new();
+
+        for package in &self.packages {
+            let release = plan.iter().find(|r| r.name == package.name);
+
+            for dependency in &package.dependencies {
+                let updated = match release {
+                    Some(release) => release.requirements.contains(&dependency.name),
+                    None => false,
+                };
+
+                if updated {
+                    continue;
+                }
+
+                let version = match plan.iter().find(|r| r.name == dependency.name) {
+                    Some(release) => &release.to,
+                    None => {
+                        match self.find(&dependency.name) {
+                            Some(index) => &self.packages[index].version,
+                            None => continue,
+                        }
+                    }
+                };
+
+                if !dependency.constraint.allows(version) {
+                    unsatisfied.push((package.name.clone(), dependency.name.clone()));
+                }
+            }
+        }
+
+        unsatisfied
+    }
+
+    /// Finds the index of a package by its name.
+    fn find(&self, name: &str) -> Option<usize> {
+        self.packages.iter().position(|p| p.name == name)
+    }
+
+    /// Sorts the packages so that dependencies come before their dependents.
+    ///
+    /// Packages that do not depend on each other keep the order they were
+    /// added to the workspace in. Dependencies on packages that are not in
+    /// the workspace are ignored.
+    fn order(&self) -> Result<Vec<usize>, String> {
+        let mut order = Vec::new();
+        let mut placed = vec![false; self.packages.len()];
+
+        while order.len() < self.packages.len() {
+            let next = (0..self.packages.len()).find(|&index| {
+                !placed[index] &&
+                self.packages[index].dependencies.iter().all(|d| {
+                    match self.find(&d.name) {
+                        Some(dependency) => placed[dependency],
+                        None => true,
+                    }
+                })
+            });
+
+            match next {
+                Some(index) => {
+                    placed[index] = true;
+                    order.push(index);
+                }
+
+                None => {
+                    let cycle: Vec<&str> = (0..self.packages.len())
+                        .filter(|&index| !placed[index])
+                        .map(|index| self.packages[index].name.as_str())
+                        .collect();
+
+                    return Err(format!("Dependency cycle between: {}", cycle.join(", ")));
+                }
+            }
+        }
+
+        Ok(order)
+    }
+
+    /// Creates a release plan where every package shares one version number.
+    fn plan_lockstep(&self, order: &[usize], bumps: &HashMap<&str, Bump>) -> Vec<Release> {
+        let bump = match bumps.values().max() {
+            Some(bump) => *bump,
+            None => return Vec::new(),
+        };
+
+        let mut to = match self.packages.iter().map(|p| &p.version).max() {
+            Some(version) => version.clone(),
+            None => return Vec::new(),
+        };
+
+        bump.apply(&mut to);
+
+        order.iter()
+            .map(|&index| {
+                let package = &self.packages[index];
+                let requirements = package.dependencies
+                    .iter()
+                    .filter(|d| self.find(&d.name).is_some() && !d.constraint.allows(&to))
+                    .map(|d| d.name.clone())
+                    .collect();
+
+                Release {
+                    name: package.name.clone(),
+                    from: package.version.clone(),
+                    to: to.clone(),
+                    bump,
+                    requirements,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::super::resolve::Operation::*;
+    use super::{Bump, Mode, Package, Workspace};
+
+    fn workspace() -> Workspace {
+        let mut w = Workspace::new();
+
+        w.add(Package::new("app", version!(3, 0, 0))
+            .depends("cli", GreaterThanOrEqualTo(version!(0, 4, 0)))
+            .depends("core", LessThan(version!(2, 0, 0))));
+
+        w.add(Package::new("cli", version!(0, 4, 0))
+            .depends("core",
+                     constraints!(And,
+                                  GreaterThanOrEqualTo(version!(1, 0, 0)),
+                                  LessThan(version!(2, 0, 0)))));
+
+        w.add(Package::new("core", version!(1, 2, 3)));
+        w.add(Package::new("docs", version!(1, 0, 0)));
+
+        w
+    }
+
+    #[test]
+    fn test_plan_independent() {
+        let w = workspace();
+        let plan = w.plan(&[("core", Bump::Minor)], Mode::Independent).unwrap();
+
+        assert_eq!(1, plan.len());
+        assert_eq!("core", plan[0].name);
+        assert_eq!(version!(1, 3, 0), plan[0].to);
+        assert!(plan[0].requirements.is_empty());
+    }
+
+    #[test]
+    fn test_plan_propagate() {
+        let w = workspace();
+        let plan = w.plan(&[("core", Bump::Major), ("app", Bump::Minor)], Mode::Independent)
+            .unwrap();
+
+        let names: Vec<&str> = plan.iter().map(|r| r.name.as_str()).collect();
+
+        assert_eq!(vec!["core", "cli", "app"], names);
+
+        assert_eq!(version!(0, 4, 1), plan[1].to);
+        assert_eq!(Bump::Patch, plan[1].bump);
+        assert_eq!(vec!["core".to_string()], plan[1].requirements);
+
+        assert_eq!(version!(3, 1, 0), plan[2].to);
+        assert_eq!(Bump::Minor, plan[2].bump);
+        assert_eq!(vec!["core".to_string()], plan[2].requirements);
+
+        assert!(w.unsatisfied(&plan).is_empty());
+    }
+
+    #[test]
+    fn test_plan_lockstep() {
+        let w = workspace();
+        let plan = w.plan(&[("core", Bump::Patch), ("cli", Bump::Minor)], Mode::Lockstep)
+            .unwrap();
+
+        let names: Vec<&str> = plan.iter().map(|r| r.name.as_str()).collect();
+
+        assert_eq!(vec!["core", "cli", "app", "docs"], names);
+
+        for release in &plan {
+            assert_eq!(version!(3, 1, 0), release.to);
+        }
+
+        assert_eq!(vec!["core".to_string()], plan[1].requirements);
+        assert!(w.unsatisfied(&plan).is_empty());
+    }
+
+    #[test]
+    fn test_plan_errors() {
+        let mut w = workspace();
+
+        assert!(w.plan(&[("missing", Bump::Patch)], Mode::Independent).is_err());
+
+        w.add(Package::new("a", version!(1, 0, 0)).depends("b", Exactly(version!(1, 0, 0))));
+        w.add(Package::new("b", version!(1, 0, 0)).depends("a", Exactly(version!(1, 0, 0))));
+
+        assert_eq!(Err("Dependency cycle between: a, b".to_string()),
+                   w.plan(&[("core", Bump::Patch)], Mode::Independent));
+    }
+
+    #[test]
+    fn test_unsatisfied() {
+        let w = workspace();
+        let mut plan = w.plan(&[("core", Bump::Major)], Mode::Independent).unwrap();
+
+        plan[1].requirements.clear();
+
+        assert_eq!(vec![("cli".to_string(), "core".to_string())], w.unsatisfied(&plan));
+    }
+
+}
@@ -0,0 +1,64 @@
+//! Builds the library with the `capi` feature, and runs a C program with it.
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Runs a command, failing with its output if it does not succeed.
+fn run(command: &mut Command) {
+    let output = command.output().unwrap_or_else(|e| panic!("Could not run {:?}: {}", command, e));
+
+    assert!(output.status.success(),
+            "{:?}\n{}{}",
+            command,
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_c_program() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target = root.join("target/capi");
+
+    run(Command::new(env!("CARGO"))
+        .args(["rustc", "--lib", "--features", "capi", "--crate-type", "staticlib"])
+        .arg("--manifest-path")
+        .arg(root.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target));
+
+    let program = target.join("capi-test");
+
+    run(Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(root.join("include"))
+        .arg(root.join("tests/capi/test.c"))
+        .arg(target.join("debug/librecital.a"))
+        .args(["-lpthread", "-ldl", "-lm"])
+        .arg("-o")
+        .arg(&program));
+
+    run(&mut Command::new(&program));
+}
+
+#[test]
+fn test_header() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let source = fs::read_to_string(root.join("src/capi.rs")).unwrap();
+    let header = fs::read_to_string(root.join("include/recital.h")).unwrap();
+
+    // Every exported function has to be declared in the header.
+    for line in source.lines() {
+        if let Some(i) = line.find("extern \"C\" fn ") {
+            let name = line[i + 14..].split('(').next().unwrap();
+
+            assert!(header.contains(&format!(" {}(", name)) ||
+                    header.contains(&format!("*{}(", name)),
+                    "{} is not declared in include/recital.h",
+                    name);
+        }
+    }
+}
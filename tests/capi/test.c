@@ -0,0 +1,174 @@
+/*
+ * Checks the C ABI, as a C program would use it.
+ *
+ * The program exits with a non-zero status if any check fails.
+ */
+#include <stdio.h>
+#include <string.h>
+
+#include "recital.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                    \
+    do {                                                                    \
+        if (!(condition)) {                                                 \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, \
+                    #condition);                                            \
+            failures++;                                                     \
+        }                                                                   \
+    } while (0)
+
+static RecitalVersion *parse(const char *input) {
+    RecitalVersion *version = NULL;
+
+    CHECK(recital_version_parse(input, &version, NULL) == RECITAL_STATUS_OK);
+
+    return version;
+}
+
+static void test_parse(void) {
+    RecitalVersion *version = NULL;
+    char *error = NULL;
+    char *string;
+
+    version = parse("1.2.3-rc.1+build.7");
+    string = recital_version_to_string(version);
+
+    CHECK(strcmp(string, "1.2.3-rc.1+build.7") == 0);
+    CHECK(recital_version_major(version) == 1);
+    CHECK(recital_version_minor(version) == 2);
+    CHECK(recital_version_patch(version) == 3);
+
+    recital_string_free(string);
+    recital_version_free(version);
+    version = NULL;
+
+    CHECK(recital_version_parse("1.2", &version, &error) == RECITAL_STATUS_INVALID_VERSION);
+    CHECK(version == NULL);
+    CHECK(strcmp(error, "Invalid version number: 1.2") == 0);
+
+    recital_string_free(error);
+
+    CHECK(recital_version_parse("\xff", &version, NULL) == RECITAL_STATUS_INVALID_UTF8);
+    CHECK(recital_version_parse(NULL, &version, NULL) == RECITAL_STATUS_NULL_POINTER);
+}
+
+static void test_compare(void) {
+    const char *ordered[] = {"1.0.0-alpha", "1.0.0-alpha.1", "1.0.0-beta.2", "1.0.0-beta.11",
+                             "1.0.0-rc.1", "1.0.0", "1.0.1", "1.10.0"};
+    size_t count = sizeof(ordered) / sizeof(ordered[0]);
+    size_t i;
+
+    for (i = 0; i + 1 < count; i++) {
+        RecitalVersion *a = parse(ordered[i]);
+        RecitalVersion *b = parse(ordered[i + 1]);
+
+        CHECK(recital_version_compare(a, b) < 0);
+        CHECK(recital_version_compare(b, a) > 0);
+
+        recital_version_free(a);
+        recital_version_free(b);
+    }
+
+    RecitalVersion *a = parse("1.0.0+a");
+    RecitalVersion *b = parse("1.0.0+b");
+
+    CHECK(recital_version_compare(a, b) == 0);
+
+    recital_version_free(a);
+    recital_version_free(b);
+}
+
+static void test_increment(void) {
+    RecitalVersion *version = parse("1.2.3-rc.1");
+    RecitalVersion *copy = recital_version_clone(version);
+    char *string;
+
+    CHECK(recital_version_increment(version, RECITAL_BUMP_MAJOR) == RECITAL_STATUS_OK);
+
+    string = recital_version_to_string(version);
+
+    CHECK(strcmp(string, "2.0.0") == 0);
+    CHECK(recital_version_compare(copy, version) < 0);
+    CHECK(recital_version_increment(NULL, RECITAL_BUMP_PATCH) == RECITAL_STATUS_NULL_POINTER);
+    CHECK(recital_version_increment(version, 7) == RECITAL_STATUS_INVALID_ENUM);
+
+    recital_string_free(string);
+    recital_version_free(version);
+    recital_version_free(copy);
+
+    version = recital_version_new(UINT64_MAX, 0, 0);
+
+    CHECK(recital_version_increment(version, RECITAL_BUMP_MAJOR) == RECITAL_STATUS_OVERFLOW);
+    CHECK(recital_version_major(version) == UINT64_MAX);
+
+    recital_version_free(version);
+}
+
+static void test_constraints(void) {
+    /* (>= 1.0.0 and < 2.0.0 and != 1.4.5) or ^3.1 */
+    RecitalConstraints *tree = recital_constraints_new(RECITAL_JOIN_OR);
+    RecitalConstraints *group = recital_constraints_new(RECITAL_JOIN_AND);
+    RecitalConstraints *requirement = NULL;
+    RecitalVersion *low = recital_version_new(1, 0, 0);
+    RecitalVersion *high = recital_version_new(2, 0, 0);
+    RecitalVersion *broken = parse("1.4.5");
+    char *error = NULL;
+
+    CHECK(recital_constraints_add_operation(group, RECITAL_OPERATOR_GREATER_THAN_OR_EQUAL_TO,
+                                            low) == RECITAL_STATUS_OK);
+    CHECK(recital_constraints_add_operation(group, RECITAL_OPERATOR_LESS_THAN, high) ==
+          RECITAL_STATUS_OK);
+    CHECK(recital_constraints_add_operation(group, RECITAL_OPERATOR_EXACTLY_NOT, broken) ==
+          RECITAL_STATUS_OK);
+    CHECK(recital_constraints_add_group(tree, group) == RECITAL_STATUS_OK);
+    CHECK(recital_requirement_parse("^3.1", &requirement, NULL) == RECITAL_STATUS_OK);
+    CHECK(recital_constraints_add_group(tree, requirement) == RECITAL_STATUS_OK);
+
+    struct {
+        const char *version;
+        bool allowed;
+    } cases[] = {{"0.9.0", false}, {"1.0.0", true},  {"1.4.5", false}, {"1.9.9", true},
+                 {"2.0.0", false}, {"3.1.0", true},  {"3.9.0", true},  {"4.0.0", false},
+                 {"3.2.0-rc.1", false}};
+    size_t i;
+
+    for (i = 0; i < sizeof(cases) / sizeof(cases[0]); i++) {
+        RecitalVersion *version = parse(cases[i].version);
+
+        if (recital_constraints_allows(tree, version) != cases[i].allowed) {
+            fprintf(stderr, "%s should%s be allowed\n", cases[i].version,
+                    cases[i].allowed ? "" : " not");
+            failures++;
+        }
+
+        recital_version_free(version);
+    }
+
+    CHECK(recital_requirement_parse("~>1", &requirement, &error) ==
+          RECITAL_STATUS_INVALID_REQUIREMENT);
+    CHECK(error != NULL);
+    CHECK(!recital_constraints_allows(NULL, low));
+
+    recital_string_free(error);
+    recital_version_free(low);
+    recital_version_free(high);
+    recital_version_free(broken);
+    recital_constraints_free(tree);
+}
+
+int main(void) {
+    test_parse();
+    test_compare();
+    test_increment();
+    test_constraints();
+
+    if (failures > 0) {
+        fprintf(stderr, "%d check(s) failed\n", failures);
+
+        return 1;
+    }
+
+    return 0;
+}
@@ -0,0 +1,49 @@
+//! Builds a `no_std` crate that uses recital without its `std` feature.
+//!
+//! The build needs a target without `std` (e.g. `thumbv7em-none-eabihf`), so
+//! the test is ignored by default. Run it with `RECITAL_NO_STD_TARGET` set to
+//! one, and `cargo test --test no_std -- --ignored`.
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// Checks if the standard library for a target is installed.
+fn is_installed(target: &str) -> bool {
+    let output = Command::new("rustc").args(["--print", "sysroot"]).output();
+
+    match output {
+        Ok(output) => {
+            let sysroot = String::from_utf8_lossy(&output.stdout);
+
+            Path::new(sysroot.trim()).join("lib/rustlib").join(target).exists()
+        }
+
+        Err(_) => false,
+    }
+}
+
+#[test]
+#[ignore = "needs RECITAL_NO_STD_TARGET"]
+fn test_builds_without_std() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target = env::var("RECITAL_NO_STD_TARGET")
+        .expect("Set RECITAL_NO_STD_TARGET to a target without `std`.");
+
+    assert!(is_installed(&target),
+            "The {0} target is not installed. Install it with `rustup target add {0}`, or set \
+             RECITAL_NO_STD_TARGET to another target without `std`.",
+            target);
+
+    let output = Command::new(env!("CARGO"))
+        .arg("build")
+        .arg("--manifest-path")
+        .arg(root.join("tests/no_std/Cargo.toml"))
+        .arg("--target-dir")
+        .arg(root.join("target/no_std"))
+        .arg("--target")
+        .arg(&target)
+        .output()
+        .expect("Could not run cargo.");
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
@@ -0,0 +1,44 @@
+//! Uses recital without `std`, as a firmware update agent would.
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+
+#[macro_use]
+extern crate recital;
+
+use alloc::string::{String, ToString};
+use recital::borrowed::VersionRef;
+use recital::compact::CompactVersion;
+use recital::resolve::Constraint;
+use recital::resolve::Operation::*;
+use recital::version::Version;
+
+/// Checks if an available firmware version should be installed.
+pub fn is_update(installed: &str, available: &str) -> Result<bool, String> {
+    let installed: Version = installed.parse()?;
+    let available: Version = available.parse()?;
+    let constraints = constraints!(And,
+                                   GreaterThan(installed),
+                                   LessThan(version!(2, 0, 0)),
+                                   ExactlyNot(version!(1, 4, 0, vec![id!("rc"), id!(1)])));
+
+    Ok(constraints.allows(&available))
+}
+
+/// Displays the newer of two version numbers without copying them.
+pub fn newer(a: &str, b: &str) -> Result<String, String> {
+    let a = VersionRef::parse(a)?;
+    let b = VersionRef::parse(b)?;
+
+    Ok(if a < b { b } else { a }.to_string())
+}
+
+/// Increments the minor version number of a compact version number.
+pub fn next_minor(version: &str) -> Result<CompactVersion, String> {
+    let mut version: Version = version.parse()?;
+
+    version.increment_minor();
+
+    Ok(CompactVersion::from(version))
+}
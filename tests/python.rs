@@ -0,0 +1,56 @@
+//! Builds the library as a Python extension module, and runs its tests with pytest.
+#![cfg(feature = "python")]
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn test_python_module() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target = root.join("target/python");
+    let python = env::var("PYTHON").unwrap_or_else(|_| "python3".to_string());
+
+    let pytest = Command::new(&python).args(["-c", "import pytest"]).output();
+
+    // Passing without running the Python tests would hide any failure in them.
+    assert!(pytest.map(|o| o.status.success()).unwrap_or(false),
+            "pytest is not installed for {0}. Install it with `{0} -m pip install pytest`, or \
+             set PYTHON to an interpreter that has it.",
+            python);
+
+    let status = Command::new(env!("CARGO"))
+        .args(["rustc", "--lib", "--features", "python,pyo3/extension-module"])
+        .args(["--crate-type", "cdylib"])
+        .arg("--manifest-path")
+        .arg(root.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target)
+        .env("PYO3_PYTHON", &python)
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+
+    // Python only imports the library under the name of the module.
+    let module = target.join("module");
+
+    fs::create_dir_all(&module).unwrap();
+    fs::copy(target.join("debug/librecital.so"), module.join("recital.so")).unwrap();
+
+    let mut paths = vec![module];
+
+    if let Some(existing) = env::var_os("PYTHONPATH") {
+        paths.extend(env::split_paths(&existing));
+    }
+
+    let status = Command::new(&python)
+        .args(["-m", "pytest", "-q", "-p", "no:cacheprovider"])
+        .arg(root.join("tests/python"))
+        .env("PYTHONPATH", env::join_paths(paths).unwrap())
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+}
@@ -0,0 +1,129 @@
+"""Tests the recital Python extension module."""
+
+import pytest
+
+from recital import Requirement, Version, resolve
+
+
+def test_parse():
+    version = Version.parse("1.2.3-alpha.1+build.7")
+
+    assert (version.major, version.minor, version.patch) == (1, 2, 3)
+    assert version.pre == ["alpha", 1]
+    assert version.build == ["build", 7]
+    assert not version.is_stable
+
+
+def test_parse_invalid():
+    with pytest.raises(ValueError):
+        Version.parse("1.2")
+
+
+def test_new():
+    assert Version() == Version.parse("0.0.0")
+    assert Version(1, 2) == Version.parse("1.2.0")
+    assert Version(1, 2, 3).is_stable
+
+    version = Version(1, 2, 3, pre=["rc", 1], build=["build", "007"])
+
+    assert str(version) == "1.2.3-rc.1+build.007"
+    assert version.pre == ["rc", 1]
+
+
+def test_new_invalid():
+    for pre in [["rc.1"], [""], ["01"], [-1]]:
+        with pytest.raises(ValueError):
+            Version(1, 2, 3, pre=pre)
+
+    with pytest.raises(ValueError):
+        Version(1, 2, 3, build=["a b"])
+
+
+def test_str():
+    version = Version.parse("1.2.3-rc.1+build")
+
+    assert str(version) == "1.2.3-rc.1+build"
+    assert repr(version) == "Version('1.2.3-rc.1+build')"
+
+
+def test_compare():
+    ordered = [
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha.beta",
+        "1.0.0-beta",
+        "1.0.0-beta.2",
+        "1.0.0-beta.11",
+        "1.0.0-rc.1",
+        "1.0.0",
+    ]
+
+    versions = [Version.parse(v) for v in ordered]
+
+    for a, b in zip(versions, versions[1:]):
+        assert a < b
+        assert a <= b
+        assert b > a
+        assert b >= a
+        assert a != b
+
+    assert sorted(reversed(versions)) == versions
+    assert Version.parse("1.0.0") == Version.parse("1.0.0+build")
+
+
+def test_hash():
+    versions = {Version.parse("1.0.0"), Version.parse("1.0.0+build"), Version(2)}
+
+    assert len(versions) == 2
+    assert hash(Version.parse("1.0.0-rc.1")) == hash(Version.parse("1.0.0-rc.1+a"))
+
+
+def test_bump():
+    version = Version.parse("1.2.3-rc.1")
+
+    assert str(version.bump_major()) == "2.0.0"
+    assert str(version.bump_minor()) == "1.3.0"
+    assert str(version.bump_patch()) == "1.2.4"
+    assert str(version) == "1.2.3-rc.1"
+
+
+def test_bump_overflow():
+    largest = 18446744073709551615
+
+    with pytest.raises(OverflowError):
+        Version(largest).bump_major()
+
+    with pytest.raises(OverflowError):
+        Version(1, largest).bump_minor()
+
+    with pytest.raises(OverflowError):
+        Version(1, 2, largest).bump_patch()
+
+    assert str(Version(1, 2, largest).bump_minor()) == "1.3.0"
+
+
+def test_requirement():
+    requirement = Requirement.parse("^1.2")
+
+    assert requirement.allows(Version(1, 2, 5))
+    assert Version(1, 9) in requirement
+    assert Version(2) not in requirement
+    assert repr(requirement) == "Requirement('^1.2')"
+
+    assert Version(2, 1) in Requirement.parse("^1.2 || 2.x", syntax="npm")
+    assert Version(2, 1) in Requirement.parse("^1.2|^2.0", syntax="composer")
+
+
+def test_requirement_invalid():
+    with pytest.raises(ValueError):
+        Requirement.parse("^^1")
+
+    with pytest.raises(ValueError):
+        Requirement.parse("^1.2", syntax="pip")
+
+
+def test_resolve():
+    versions = [Version.parse(v) for v in ["2.0.0", "1.1.0", "1.3.0", "1.2.5"]]
+
+    assert resolve(versions, Requirement.parse("^1.2")) == [Version(1, 3), Version(1, 2, 5)]
+    assert resolve([], Requirement.parse("*")) == []
@@ -0,0 +1,48 @@
+//! Builds the WebAssembly bindings, and runs their tests with Node.
+#![cfg(feature = "wasm")]
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Runs a command, failing with its output if it does not succeed.
+fn run(command: &mut Command) {
+    let output = command.output().unwrap_or_else(|e| panic!("Could not run {:?}: {}", command, e));
+
+    assert!(output.status.success(),
+            "{:?}\n{}{}",
+            command,
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_node() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target = root.join("target/wasm");
+    let package = target.join("pkg");
+
+    run(Command::new(env!("CARGO"))
+        .args(["rustc", "--lib", "--features", "wasm", "--crate-type", "cdylib"])
+        .args(["--target", "wasm32-unknown-unknown"])
+        .arg("--manifest-path")
+        .arg(root.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target));
+
+    run(Command::new("wasm-bindgen")
+        .args(["--target", "nodejs", "--out-dir"])
+        .arg(&package)
+        .arg(target.join("wasm32-unknown-unknown/debug/recital.wasm")));
+
+    let types = fs::read_to_string(package.join("recital.d.ts")).unwrap();
+
+    assert!(types.contains("export type Syntax = \"cargo\" | \"composer\" | \"npm\";"));
+    assert!(types.contains("static parse(s: string, syntax?: Syntax | null): Requirement;"));
+    assert!(types.contains("static compare(a: Version, b: Version): number;"));
+
+    run(Command::new("node")
+        .arg("--test")
+        .arg(root.join("tests/wasm/test.js"))
+        .env("RECITAL_WASM", package.join("recital.js")));
+}
@@ -0,0 +1,64 @@
+// Tests the recital WebAssembly bindings under Node.
+"use strict";
+
+const assert = require("node:assert");
+const test = require("node:test");
+const { Requirement, Version } = require(process.env.RECITAL_WASM);
+
+test("parse", () => {
+    const version = Version.parse("1.2.3-alpha.1+build.7");
+
+    assert.deepStrictEqual([version.major, version.minor, version.patch], [1, 2, 3]);
+    assert.strictEqual(version.pre, "alpha.1");
+    assert.strictEqual(version.build, "build.7");
+    assert.strictEqual(version.isStable, false);
+    assert.strictEqual(String(version), "1.2.3-alpha.1+build.7");
+    assert.throws(() => Version.parse("1.2"), Error);
+});
+
+test("new", () => {
+    assert.ok(new Version(1, 2, 3).equals(Version.parse("1.2.3")));
+    assert.strictEqual(new Version(1, 2, 3).isStable, true);
+});
+
+test("compare", () => {
+    const ordered = [
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha.beta",
+        "1.0.0-beta",
+        "1.0.0-beta.2",
+        "1.0.0-beta.11",
+        "1.0.0-rc.1",
+        "1.0.0",
+    ];
+
+    const sorted = [...ordered].reverse().map(Version.parse).sort(Version.compare);
+
+    assert.deepStrictEqual(sorted.map(String), ordered);
+    assert.strictEqual(sorted[0].compareTo(sorted[1]), -1);
+    assert.strictEqual(sorted[1].compareTo(sorted[0]), 1);
+    assert.strictEqual(Version.parse("1.0.0").compareTo(Version.parse("1.0.0+build")), 0);
+});
+
+test("bump", () => {
+    const version = Version.parse("1.2.3-rc.1");
+
+    assert.strictEqual(version.bumpMajor().toString(), "2.0.0");
+    assert.strictEqual(version.bumpMinor().toString(), "1.3.0");
+    assert.strictEqual(version.bumpPatch().toString(), "1.2.4");
+    assert.strictEqual(version.toString(), "1.2.3-rc.1");
+});
+
+test("requirement", () => {
+    const requirement = Requirement.parse("^1.2");
+
+    assert.ok(requirement.allows(new Version(1, 9, 0)));
+    assert.ok(!requirement.allows(new Version(2, 0, 0)));
+    assert.strictEqual(String(requirement), "^1.2");
+
+    assert.ok(Requirement.parse("^1.2 || 2.x", "npm").allows(new Version(2, 1, 0)));
+    assert.ok(Requirement.parse("^1.2|^2.0", "composer").allows(new Version(2, 1, 0)));
+    assert.throws(() => Requirement.parse("^^1"), Error);
+    assert.throws(() => Requirement.parse("^1.2", "pip"), /Unknown syntax: pip/);
+});